@@ -55,16 +55,32 @@ public:
 // Need to include rust::Str for cxx compatibility
 #include "rust/cxx.h"
 
+// Shared structs defined by the cxx bridge (see rust/src/ffi.rs)
+struct PerftEntry;
+struct EngineSearchStats;
+
 // Board operations - simplified for initial FFI
 std::unique_ptr<opera::Board> create_board();
 bool board_set_fen(opera::Board& board, rust::Str fen);
 bool board_make_move(opera::Board& board, rust::Str move_str);
+bool board_undo_move(opera::Board& board, rust::Str fen_before, rust::Str move_str);
 rust::String board_get_fen(const opera::Board& board);
+rust::String board_to_ascii(const opera::Board& board);
 bool board_is_valid_move(const opera::Board& board, rust::Str move_str);
 void board_reset(opera::Board& board);
 bool board_is_in_check(const opera::Board& board);
 bool board_is_checkmate(const opera::Board& board);
 bool board_is_stalemate(const opera::Board& board);
+uint32_t board_material_count(const opera::Board& board);
+uint64_t board_position_key(const opera::Board& board);
+uint64_t board_color_bitboard(const opera::Board& board, bool white);
+uint8_t board_piece_at(const opera::Board& board, uint8_t square);
+uint32_t board_halfmove_clock(const opera::Board& board);
+uint32_t board_fullmove_number(const opera::Board& board);
+rust::Vec<rust::String> board_legal_moves(const opera::Board& board);
+rust::Vec<PerftEntry> board_perft_divide(const opera::Board& board, uint32_t depth);
+rust::String board_move_to_san(const opera::Board& board, rust::Str move_str);
+rust::String board_san_to_uci(const opera::Board& board, rust::Str san_str);
 
 // Search operations - simplified interface
 std::unique_ptr<opera::Search> create_search();
@@ -72,11 +88,29 @@ bool search_start(opera::Search& search, const opera::Board& board, int32_t dept
 void search_stop(opera::Search& search);
 rust::String search_get_best_move(const opera::Search& search);
 bool search_is_searching(const opera::Search& search);
+int32_t search_static_eval(const opera::Search& search, const opera::Board& board);
+rust::String engine_search_mate(const opera::Board& board, uint32_t moves, uint64_t max_nodes);
+uint64_t engine_bench_search(const opera::Board& board, uint32_t depth);
+uint32_t engine_search(const opera::Board& board, uint64_t max_time_ms);
+void engine_stop_search();
+EngineSearchStats engine_get_search_stats();
+uint32_t engine_seldepth();
 
 // Engine configuration
 bool engine_set_hash_size(uint32_t size_mb);
 bool engine_set_threads(uint32_t thread_count);
 bool engine_clear_hash();
+uint16_t engine_hashfull();
+bool engine_set_contempt(int32_t contempt_cp);
+bool engine_warmup_hash();
+bool engine_set_morphy_style(bool enabled);
+bool engine_set_sacrifice_threshold(int32_t threshold_cp);
+bool engine_set_tactical_depth(uint32_t plies);
+bool engine_set_node_limit(uint64_t max_nodes);
+bool engine_set_depth_limit(uint32_t depth);
+bool engine_set_root_move_order(rust::Str moves);
+bool engine_set_chess960(bool enabled);
+bool engine_set_strength(uint32_t elo);
 
 // Rust callback declarations (implemented in Rust)
 void on_search_progress(const opera::SearchInfo& info);
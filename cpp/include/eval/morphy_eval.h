@@ -65,6 +65,8 @@ public:
      *
      * Supported options:
      * - MorphyBias: 0.0-2.0 (default 1.0)
+     * - SacrificeThreshold: 0-1000 cp of compensation credited for sacrificed
+     *   material (default 100)
      *
      * @param options Map of option names to values
      */
@@ -77,6 +79,13 @@ public:
      */
     double get_morphy_bias() const { return morphy_bias_; }
 
+    /**
+     * @brief Get the most compensation credited for sacrificed material
+     *
+     * @return Compensation cap in centipawns (0-1000)
+     */
+    int get_sacrifice_threshold() const { return sacrifice_threshold_; }
+
 private:
     /**
      * @brief Calculate material sacrifice compensation based on initiative
@@ -90,7 +99,7 @@ private:
      * @param board The board position
      * @param color The side to evaluate
      * @param material_deficit Material disadvantage in centipawns (negative)
-     * @return Compensation bonus (0-100cp)
+     * @return Compensation bonus (0 up to the sacrifice threshold)
      */
     int calculate_sacrifice_compensation(const Board& board, Color color, int material_deficit) const;
 
@@ -123,10 +132,11 @@ private:
     static constexpr double DEVELOPMENT_BIAS = 1.2;    ///< Development weight multiplier
     static constexpr double KING_SAFETY_BIAS = 1.5;    ///< King attack weight multiplier
     static constexpr double MOBILITY_BIAS = 1.1;       ///< Mobility/initiative multiplier
-    static constexpr int SACRIFICE_COMPENSATION = 100; ///< Max compensation for sacrifices (cp)
+    static constexpr int SACRIFICE_COMPENSATION = 100; ///< Default max compensation for sacrifices (cp)
     static constexpr int UNCASTLED_PENALTY = 50;       ///< Penalty for uncastled enemy king (cp)
 
     double morphy_bias_;  ///< Overall bias multiplier (0.0-2.0)
+    int sacrifice_threshold_;  ///< Max compensation for sacrifices (0-1000cp)
 };
 
 } // namespace eval
@@ -1,5 +1,6 @@
 #pragma once
 
+#include <algorithm>
 #include <atomic>
 #include <vector>
 #include <chrono>
@@ -38,6 +39,7 @@ constexpr int DEFAULT_MIN_DEPTH_FOR_NMP = 3;           // Minimum depth for null
 constexpr int DEFAULT_MIN_DEPTH_FOR_LMR = 2;           // Minimum depth for late move reductions
 constexpr int DEFAULT_MIN_DEPTH_FOR_FUTILITY = 1;     // Minimum depth for futility pruning
 constexpr int DEFAULT_MIN_DEPTH_FOR_RAZORING = 2;     // Minimum depth for razoring
+constexpr int DEFAULT_TACTICAL_DEPTH = 2;             // Plies extensions and quiescence may add to a line
 
 /**
  * Search statistics for performance analysis
@@ -56,12 +58,33 @@ struct SearchStats {
     uint64_t lmr_reductions = 0;           // Late move reductions applied
     uint64_t futility_prunes = 0;          // Futility pruning cutoffs
     uint64_t razoring_prunes = 0;          // Razoring pruning cutoffs
+    uint64_t qsearch_nodes = 0;            // Nodes visited in quiescence search
+    int seldepth = 0;                      // Deepest ply reached, extensions and quiescence included
     
     // Reset all statistics
     void reset() {
         nodes = beta_cutoffs = first_move_cutoffs = 0;
         tt_hits = tt_cutoffs = extensions = reductions = 0;
         null_move_cutoffs = lmr_reductions = futility_prunes = razoring_prunes = 0;
+        qsearch_nodes = 0;
+        seldepth = 0;
+    }
+    
+    // Add the counters of another search, e.g. to total the iterations of one search
+    void add(const SearchStats& other) {
+        nodes += other.nodes;
+        beta_cutoffs += other.beta_cutoffs;
+        first_move_cutoffs += other.first_move_cutoffs;
+        tt_hits += other.tt_hits;
+        tt_cutoffs += other.tt_cutoffs;
+        extensions += other.extensions;
+        reductions += other.reductions;
+        null_move_cutoffs += other.null_move_cutoffs;
+        lmr_reductions += other.lmr_reductions;
+        futility_prunes += other.futility_prunes;
+        razoring_prunes += other.razoring_prunes;
+        qsearch_nodes += other.qsearch_nodes;
+        seldepth = std::max(seldepth, other.seldepth);
     }
     
     // Get move ordering effectiveness (first move cutoff rate)
@@ -108,6 +131,12 @@ private:
     int min_depth_for_lmr = DEFAULT_MIN_DEPTH_FOR_LMR;
     int min_depth_for_futility = DEFAULT_MIN_DEPTH_FOR_FUTILITY;
     int min_depth_for_razoring = DEFAULT_MIN_DEPTH_FOR_RAZORING;
+    int tactical_depth = DEFAULT_TACTICAL_DEPTH;
+    int contempt = 0;                       // Centipawns the root side gives up by drawing
+    
+    // Deepest ply any line of the current search may reach: the nominal depth
+    // plus tactical_depth
+    int line_limit = MAX_PLY;
     
 public:
     /**
@@ -200,6 +229,8 @@ public:
     void set_min_depth_for_lmr(int depth) { min_depth_for_lmr = depth; }
     void set_min_depth_for_futility(int depth) { min_depth_for_futility = depth; }
     void set_min_depth_for_razoring(int depth) { min_depth_for_razoring = depth; }
+    void set_tactical_depth(int plies) { tactical_depth = plies; }
+    void set_contempt(int centipawns) { contempt = centipawns; }
     
     /**
      * Get current search optimization parameters
@@ -213,6 +244,8 @@ public:
     int get_min_depth_for_lmr() const { return min_depth_for_lmr; }
     int get_min_depth_for_futility() const { return min_depth_for_futility; }
     int get_min_depth_for_razoring() const { return min_depth_for_razoring; }
+    int get_tactical_depth() const { return tactical_depth; }
+    int get_contempt() const { return contempt; }
     
     // Testing methods (public for validation)
     int get_lmr_reduction(int depth, int move_number, bool is_pv_node, const MoveGen& move) const;
@@ -227,6 +260,17 @@ private:
      */
     int evaluate();
     
+    /**
+     * Score of a drawn position for the side to move
+     * 
+     * Positive contempt makes a draw worth less than equality to the side to
+     * move at the root, and as much more to its opponent.
+     * 
+     * @param ply Current ply from root
+     * @return Draw score in centipawns
+     */
+    int draw_score(int ply) const;
+    
     /**
      * Check if the current position is in check
      * 
@@ -104,6 +104,10 @@ public:
     
     /**
      * Sort moves by their scores in descending order (best moves first)
+     *
+     * Moves with equal ordering scores are tie-broken by from-square, then
+     * to-square, then promotion piece (all ascending), so the resulting order
+     * depends only on the position and never on generation order.
      * @param moves Move list to sort (must be pre-scored)
      */
     template<size_t MAX_MOVES>
@@ -234,12 +238,21 @@ void MoveOrdering::score_moves(MoveGenList<MAX_MOVES>& moves, int depth) {
 
 template<size_t MAX_MOVES>
 void MoveOrdering::sort_moves(MoveGenList<MAX_MOVES>& moves) {
-    // Sort using the stored move scores
+    // Sort using the stored move scores, with a stable square-based tie-break
     std::sort(moves.begin(), moves.begin() + moves.size(), 
               [this](const MoveGen& a, const MoveGen& b) {
                   int score_a = get_move_score(a);
                   int score_b = get_move_score(b);
-                  return score_a > score_b;  // Descending order
+                  if (score_a != score_b) {
+                      return score_a > score_b;  // Descending order
+                  }
+                  if (a.from() != b.from()) {
+                      return a.from() < b.from();
+                  }
+                  if (a.to() != b.to()) {
+                      return a.to() < b.to();
+                  }
+                  return a.promotionPiece() < b.promotionPiece();
               });
 }
 
@@ -49,6 +49,7 @@ struct SearchResult {
     Move ponder_move = NULL_MOVE;          // Move to ponder on
     int score = 0;                         // Position evaluation in centipawns
     int depth = 0;                         // Depth searched
+    int seldepth = 0;                      // Deepest ply reached by the completed iterations
     uint64_t nodes = 0;                    // Total nodes searched
     uint64_t time_ms = 0;                  // Time taken in milliseconds
     std::vector<Move> principal_variation; // Principal variation
@@ -99,6 +100,7 @@ private:
     
     // Search statistics (now delegated to AlphaBetaSearch)
     uint64_t nodes_searched = 0;           // Total nodes searched this session
+    SearchStats search_stats;              // AlphaBetaSearch counters summed over the current search
     std::vector<Move> pv_line;             // Current principal variation
     
 public:
@@ -154,6 +156,16 @@ public:
      */
     const SearchInfo& get_search_info() const;
     
+    /**
+     * Get the counters of the current or last search
+     * 
+     * Unlike AlphaBetaSearch::get_stats(), which covers only its latest call,
+     * these sum every iteration and aspiration re-search.
+     * 
+     * @return Summed SearchStats
+     */
+    const SearchStats& get_stats() const;
+    
     /**
      * Reset search statistics (for new game)
      */
@@ -171,6 +183,8 @@ public:
     void set_min_depth_for_lmr(int depth);
     void set_min_depth_for_futility(int depth);
     void set_min_depth_for_razoring(int depth);
+    void set_tactical_depth(int plies);
+    void set_contempt(int centipawns);
 
     /**
      * Configure evaluation parameters (UCI options)
@@ -191,6 +205,8 @@ public:
     int get_min_depth_for_lmr() const;
     int get_min_depth_for_futility() const;
     int get_min_depth_for_razoring() const;
+    int get_tactical_depth() const;
+    int get_contempt() const;
 
 private:
     /**
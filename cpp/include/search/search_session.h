@@ -0,0 +1,61 @@
+#pragma once
+
+#include <cstdint>
+#include "Board.h"
+#include "search/alphabeta.h"
+
+namespace opera {
+
+/**
+ * Limits and settings of one search run for the UCI bridge
+ */
+struct SessionLimits {
+    int max_depth = MAX_PLY;               // Deepest iteration to run
+    uint64_t max_nodes = UINT64_MAX;       // Maximum nodes to search
+    uint64_t max_time_ms = UINT64_MAX;     // Maximum time in milliseconds
+    int tactical_depth = DEFAULT_TACTICAL_DEPTH; // Plies extensions and quiescence may add to a line
+    int contempt = 0;                      // Centipawns the searching side gives up by drawing
+    bool use_morphy_style = false;         // Evaluate with the Morphy evaluator
+};
+
+/**
+ * Outcome of a search run for the UCI bridge
+ */
+struct SessionResult {
+    int depth = 0;                         // Deepest completed iteration
+    int seldepth = 0;                      // Deepest ply reached by the completed iterations
+    int score = 0;                         // Score of that iteration
+    SearchStats stats;                     // Counters summed over every iteration
+};
+
+/**
+ * Search a copy of `board` with the bridge's SearchEngine
+ *
+ * UCIBridge.h declares its own SearchLimits and SearchInfo, so the bridge
+ * cannot include search_engine.h and searches through these functions instead.
+ * One engine serves every search and keeps its transposition table between
+ * them. Blocks until a limit is reached or stop_session_search() is called;
+ * searches run one at a time, so a second caller waits for the first.
+ *
+ * @param board Position to search; the engine works on its own copy
+ * @param limits Search constraints and evaluator choice
+ * @return Result of the search
+ */
+SessionResult run_session_search(const Board& board, const SessionLimits& limits);
+
+/**
+ * Ask the running session search, if any, to stop
+ */
+void stop_session_search();
+
+/**
+ * Get the result of the most recent session search
+ *
+ * Waits for a running search to finish, so call stop_session_search() first
+ * to read it promptly.
+ *
+ * @return Result of the last search; all zero before the first
+ */
+SessionResult last_session_result();
+
+} // namespace opera
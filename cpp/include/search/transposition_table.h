@@ -162,6 +162,12 @@ public:
      */
     void clear();
     
+    /**
+     * Touch every memory page of the table without changing any entry,
+     * so the first search does not pay for page faults
+     */
+    void touch_pages();
+    
     /**
      * Age the table (increment generation)
      */
@@ -173,6 +179,12 @@ public:
      */
     size_t size_mb() const { return size_bytes / (1024 * 1024); }
     
+    /**
+     * Estimate table occupancy for the UCI hashfull field
+     * @return Per-mille of sampled entries written in the current search (0..1000)
+     */
+    int hashfull() const;
+    
     /**
      * Get table statistics
      * @return Reference to statistics object
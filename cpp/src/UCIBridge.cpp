@@ -1,9 +1,17 @@
 #include "UCIBridge.h"
 #include "Board.h"
 #include "MoveGen.h"
-#include <iostream>
+#include "eval/handcrafted_eval.h"
+#include "eval/morphy_eval.h"
+#include "search/search_session.h"
+#include "search/transposition_table.h"
+#include <algorithm>
+#include <memory>
+#include <mutex>
+#include <new>
 #include <sstream>
 #include "rust/cxx.h"
+#include "opera-uci/src/ffi.rs.h"
 
 namespace opera {
 
@@ -55,9 +63,70 @@ std::unique_ptr<opera::Board> create_board() {
     }
 }
 
+namespace {
+// Accept Shredder-FEN castling files (UCI_Chess960)
+bool g_chess960 = false;
+
+// File (0-7) of `king` on its back rank in a FEN piece placement, or -1
+int king_file(const std::string& placement, char king) {
+    std::string rank = king == 'K' ? placement.substr(placement.rfind('/') + 1)
+                                   : placement.substr(0, placement.find('/'));
+    int file = 0;
+    for (char c : rank) {
+        if (c >= '1' && c <= '8') {
+            file += c - '0';
+        } else if (c == king) {
+            return file;
+        } else {
+            ++file;
+        }
+    }
+    return -1;
+}
+
+// Rewrite Shredder-FEN castling files (e.g. "HAha") as KQkq relative to the kings
+std::string standard_castling_fen(const std::string& fen) {
+    std::istringstream fields(fen);
+    std::string placement, side, castling, rest;
+    if (!(fields >> placement >> side >> castling)) {
+        return fen;
+    }
+    std::getline(fields, rest);
+
+    int white_king = king_file(placement, 'K');
+    int black_king = king_file(placement, 'k');
+    const std::string names = "KQkq";
+    bool rights[4] = {false, false, false, false};
+    for (char c : castling) {
+        if (names.find(c) != std::string::npos) {
+            rights[names.find(c)] = true;
+        } else if (c >= 'A' && c <= 'H' && white_king >= 0) {
+            rights[c - 'A' > white_king ? 0 : 1] = true;
+        } else if (c >= 'a' && c <= 'h' && black_king >= 0) {
+            rights[c - 'a' > black_king ? 2 : 3] = true;
+        } else if (c == '-') {
+            continue;
+        } else {
+            return fen; // Let setFromFEN reject it
+        }
+    }
+
+    std::string standard;
+    for (int i = 0; i < 4; ++i) {
+        if (rights[i]) {
+            standard += names[i];
+        }
+    }
+    return placement + " " + side + " " + (standard.empty() ? "-" : standard) + rest;
+}
+}
+
 bool board_set_fen(opera::Board& board, rust::Str fen) {
     try {
         std::string fen_str(fen);
+        if (g_chess960) {
+            fen_str = standard_castling_fen(fen_str);
+        }
         board.setFromFEN(fen_str);
         return true;
     } catch (const std::exception&) {
@@ -67,51 +136,38 @@ bool board_set_fen(opera::Board& board, rust::Str fen) {
 }
 
 bool board_make_move(opera::Board& board, rust::Str move_str) {
-    // Parse move string (simplified implementation for now)
-    std::string move_string(move_str);
-    
-    try {
-        if (move_string.length() < 4) {
-            return false;
-        }
-        
-        // Extract from/to squares from UCI format (e.g., "e2e4")
-        int from_file = move_string[0] - 'a';
-        int from_rank = move_string[1] - '1';
-        int to_file = move_string[2] - 'a';
-        int to_rank = move_string[3] - '1';
-        
-        if (from_file < 0 || from_file > 7 || from_rank < 0 || from_rank > 7 ||
-            to_file < 0 || to_file > 7 || to_rank < 0 || to_rank > 7) {
-            return false;
-        }
-        
-        opera::Square from = static_cast<opera::Square>(from_rank * 8 + from_file);
-        opera::Square to = static_cast<opera::Square>(to_rank * 8 + to_file);
-        
-        // Create MoveGen object
-        opera::MoveGen::MoveType moveType = opera::MoveGen::MoveType::NORMAL;
-        opera::Piece promotion = opera::NO_PIECE;
-        
-        // Handle promotion
-        if (move_string.length() == 5) {
-            moveType = opera::MoveGen::MoveType::PROMOTION;
-            char promo = move_string[4];
-            opera::Color color = board.getSideToMove();
-            
-            switch (promo) {
-                case 'q': promotion = color == opera::WHITE ? opera::WHITE_QUEEN : opera::BLACK_QUEEN; break;
-                case 'r': promotion = color == opera::WHITE ? opera::WHITE_ROOK : opera::BLACK_ROOK; break;
-                case 'b': promotion = color == opera::WHITE ? opera::WHITE_BISHOP : opera::BLACK_BISHOP; break;
-                case 'n': promotion = color == opera::WHITE ? opera::WHITE_KNIGHT : opera::BLACK_KNIGHT; break;
-                default:
-                    return false;
+    try {
+        // Only generated moves are played: they carry the castling and en passant
+        // flags a bare from/to move lacks, and anything else is illegal here
+        std::string uci(move_str);
+        opera::MoveGenList<> legal_moves;
+        opera::generateAllLegalMoves(board, legal_moves, board.getSideToMove());
+        for (size_t i = 0; i < legal_moves.size(); ++i) {
+            if (legal_moves[i].toString() == uci) {
+                return board.makeMove(legal_moves[i]);
             }
         }
-        
-        opera::MoveGen move(from, to, moveType, promotion);
-        return board.makeMove(move);
-        
+        return false;
+    } catch (const std::exception&) {
+        return false;
+    }
+}
+
+bool board_undo_move(opera::Board& board, rust::Str fen_before, rust::Str move_str) {
+    try {
+        // unmakeMove dispatches on the castling, en passant and promotion flags,
+        // so find the move as it was generated in the position it was made from
+        std::string uci(move_str);
+        opera::Board before(std::string{fen_before});
+        opera::MoveGenList<> legal_moves;
+        opera::generateAllLegalMoves(before, legal_moves, before.getSideToMove());
+        for (size_t i = 0; i < legal_moves.size(); ++i) {
+            if (legal_moves[i].toString() == uci) {
+                board.unmakeMove(legal_moves[i]);
+                return true;
+            }
+        }
+        return false;
     } catch (const std::exception&) {
         return false;
     }
@@ -125,6 +181,20 @@ rust::String board_get_fen(const opera::Board& board) {
     }
 }
 
+rust::String board_to_ascii(const opera::Board& board) {
+    try {
+        // toString() ends with the FEN, which callers report separately
+        std::string art = board.toString();
+        size_t fen_pos = art.find("FEN:");
+        if (fen_pos != std::string::npos) {
+            art.erase(fen_pos);
+        }
+        return rust::String(art);
+    } catch (const std::exception&) {
+        return rust::String("");
+    }
+}
+
 bool board_is_valid_move(const opera::Board& board, rust::Str move_str) {
     try {
         // Create a copy of the board to test the move
@@ -170,6 +240,227 @@ bool board_is_stalemate(const opera::Board& board) {
     }
 }
 
+uint32_t board_material_count(const opera::Board& board) {
+    static constexpr uint32_t PIECE_VALUES[] = {100, 320, 330, 500, 900};
+
+    uint32_t total = 0;
+    for (opera::Color color : {opera::WHITE, opera::BLACK}) {
+        for (int type = opera::PAWN; type <= opera::QUEEN; ++type) {
+            int count = board.getPieceCount(color, static_cast<opera::PieceType>(type));
+            total += PIECE_VALUES[type] * static_cast<uint32_t>(count);
+        }
+    }
+    return total;
+}
+
+uint64_t board_position_key(const opera::Board& board) {
+    return board.getZobristKey();
+}
+
+uint64_t board_color_bitboard(const opera::Board& board, bool white) {
+    return board.getColorBitboard(white ? opera::WHITE : opera::BLACK);
+}
+
+uint8_t board_piece_at(const opera::Board& board, uint8_t square) {
+    return static_cast<uint8_t>(board.getPiece(static_cast<opera::Square>(square)));
+}
+
+uint32_t board_halfmove_clock(const opera::Board& board) {
+    return static_cast<uint32_t>(board.getHalfmoveClock());
+}
+
+uint32_t board_fullmove_number(const opera::Board& board) {
+    return static_cast<uint32_t>(board.getFullmoveNumber());
+}
+
+namespace {
+
+// Count leaf nodes reachable in exactly `depth` plies
+uint64_t perft_nodes(opera::Board& board, uint32_t depth) {
+    if (depth == 0) return 1;
+
+    opera::MoveGenList<> moves;
+    opera::generateAllLegalMoves(board, moves, board.getSideToMove());
+    if (depth == 1) return moves.size();
+
+    uint64_t nodes = 0;
+    for (size_t i = 0; i < moves.size(); ++i) {
+        if (board.makeMove(moves[i])) {
+            nodes += perft_nodes(board, depth - 1);
+            board.unmakeMove(moves[i]);
+        }
+    }
+    return nodes;
+}
+
+// Standard Algebraic Notation for a legal move (without check suffix)
+std::string san_body(const opera::Board& board, const opera::MoveGen& move,
+                     const opera::MoveGenList<>& legal_moves) {
+    static const char PIECE_LETTERS[] = {'P', 'N', 'B', 'R', 'Q', 'K'};
+
+    opera::Square from = move.from();
+    opera::Square to = move.to();
+    opera::PieceType piece_type = opera::typeOf(board.getPiece(from));
+    std::string uci = move.toString();
+    std::string destination = uci.substr(2, 2);
+    bool is_capture = move.isCapture() || move.isEnPassant();
+
+    if (move.isCastling()) {
+        return opera::fileOf(to) > opera::fileOf(from) ? "O-O" : "O-O-O";
+    }
+
+    std::string san;
+    if (piece_type == opera::PAWN) {
+        if (is_capture) {
+            san += uci[0];
+            san += 'x';
+        }
+        san += destination;
+        if (move.isPromotion()) {
+            san += '=';
+            san += PIECE_LETTERS[opera::typeOf(move.promotionPiece())];
+        }
+        return san;
+    }
+
+    san += PIECE_LETTERS[piece_type];
+
+    // Disambiguate between identical pieces that can reach the same square
+    bool ambiguous = false;
+    bool same_file = false;
+    bool same_rank = false;
+    for (size_t i = 0; i < legal_moves.size(); ++i) {
+        const opera::MoveGen& other = legal_moves[i];
+        if (other.to() != to || other.from() == from) continue;
+        if (opera::typeOf(board.getPiece(other.from())) != piece_type) continue;
+
+        ambiguous = true;
+        same_file |= opera::fileOf(other.from()) == opera::fileOf(from);
+        same_rank |= opera::rankOf(other.from()) == opera::rankOf(from);
+    }
+    if (ambiguous) {
+        if (!same_file) {
+            san += uci[0];
+        } else if (!same_rank) {
+            san += uci[1];
+        } else {
+            san += uci.substr(0, 2);
+        }
+    }
+
+    if (is_capture) san += 'x';
+    san += destination;
+    return san;
+}
+
+// Full SAN for a legal move, including the check or checkmate suffix
+std::string san_with_suffix(const opera::Board& board, const opera::MoveGen& move,
+                            const opera::MoveGenList<>& legal_moves) {
+    std::string san = san_body(board, move, legal_moves);
+
+    opera::Board after = board;
+    if (after.makeMove(move)) {
+        opera::Color opponent = after.getSideToMove();
+        if (after.isInCheck(opponent)) {
+            opera::MoveGenList<> replies;
+            opera::generateAllLegalMoves(after, replies, opponent);
+            san += replies.size() == 0 ? '#' : '+';
+        }
+    }
+    return san;
+}
+
+// Strip check/mate suffixes, annotations and '=' so SAN variants compare equal
+std::string normalize_san(const std::string& san) {
+    std::string normalized;
+    for (char c : san) {
+        if (c == '+' || c == '#' || c == '!' || c == '?' || c == '=') continue;
+        normalized += c == '0' ? 'O' : c;
+    }
+    return normalized;
+}
+
+} // namespace
+
+rust::String board_move_to_san(const opera::Board& board, rust::Str move_str) {
+    try {
+        std::string uci(move_str);
+        opera::MoveGenList<> legal_moves;
+        opera::generateAllLegalMoves(board, legal_moves, board.getSideToMove());
+
+        for (size_t i = 0; i < legal_moves.size(); ++i) {
+            const opera::MoveGen& move = legal_moves[i];
+            if (move.toString() != uci) continue;
+            return rust::String(san_with_suffix(board, move, legal_moves));
+        }
+    } catch (const std::exception&) {
+        // Fall through to empty result
+    }
+
+    return rust::String("");
+}
+
+rust::String board_san_to_uci(const opera::Board& board, rust::Str san_str) {
+    try {
+        std::string wanted = normalize_san(std::string(san_str));
+        if (wanted.empty()) return rust::String("");
+
+        opera::MoveGenList<> legal_moves;
+        opera::generateAllLegalMoves(board, legal_moves, board.getSideToMove());
+
+        for (size_t i = 0; i < legal_moves.size(); ++i) {
+            const opera::MoveGen& move = legal_moves[i];
+            if (normalize_san(san_body(board, move, legal_moves)) == wanted) {
+                return rust::String(move.toString());
+            }
+        }
+    } catch (const std::exception&) {
+        // Fall through to empty result
+    }
+
+    return rust::String("");
+}
+
+rust::Vec<rust::String> board_legal_moves(const opera::Board& board) {
+    rust::Vec<rust::String> result;
+
+    try {
+        opera::MoveGenList<> moves;
+        opera::generateAllLegalMoves(board, moves, board.getSideToMove());
+
+        for (size_t i = 0; i < moves.size(); ++i) {
+            result.push_back(rust::String(moves[i].toString()));
+        }
+    } catch (const std::exception&) {
+        // Return whatever was generated before the failure
+    }
+
+    return result;
+}
+
+rust::Vec<PerftEntry> board_perft_divide(const opera::Board& board, uint32_t depth) {
+    rust::Vec<PerftEntry> entries;
+    if (depth == 0) return entries;
+
+    try {
+        opera::Board work = board;
+        opera::MoveGenList<> moves;
+        opera::generateAllLegalMoves(work, moves, work.getSideToMove());
+
+        for (size_t i = 0; i < moves.size(); ++i) {
+            if (!work.makeMove(moves[i])) continue;
+            uint64_t nodes = perft_nodes(work, depth - 1);
+            work.unmakeMove(moves[i]);
+
+            entries.push_back(PerftEntry{rust::String(moves[i].toString()), nodes});
+        }
+    } catch (const std::exception&) {
+        // Return whatever was counted before the failure
+    }
+
+    return entries;
+}
+
 // Search operations (stub implementations)
 std::unique_ptr<opera::Search> create_search() {
     try {
@@ -217,22 +508,342 @@ bool search_is_searching(const opera::Search& search) {
     }
 }
 
-// Engine configuration (stub implementations)
+namespace {
+// Evaluation style: MorphyStyle swaps in the Morphy evaluator, whose credit for
+// sacrificed material is capped at SacrificeThreshold
+bool g_morphy_style = false;
+int32_t g_sacrifice_threshold = 100;
+
+// Evaluate from white's point of view with the configured style
+int32_t evaluate_for_white(const opera::Board& board, opera::Color side) {
+    if (g_morphy_style) {
+        opera::eval::MorphyEvaluator evaluator;
+        evaluator.configure_options({{"SacrificeThreshold", std::to_string(g_sacrifice_threshold)}});
+        return evaluator.evaluate(board, side);
+    }
+    opera::eval::HandcraftedEvaluator evaluator;
+    return evaluator.evaluate(board, side);
+}
+}
+
+int32_t search_static_eval(const opera::Search& /*search*/, const opera::Board& board) {
+    // Mirrors MATE_SCORE in bridge/search.rs
+    static constexpr int32_t MATE_SCORE = 32000;
+
+    try {
+        opera::Color side = board.getSideToMove();
+        opera::MoveGenList<> moves;
+        opera::generateAllLegalMoves(board, moves, side);
+        if (moves.size() == 0) {
+            return board.isInCheck(side) ? -MATE_SCORE : 0;
+        }
+
+        // The evaluator scores from white's point of view
+        int32_t white_score = evaluate_for_white(board, side);
+        return side == opera::WHITE ? white_score : -white_score;
+    } catch (const std::exception&) {
+        return 0;
+    }
+}
+
+namespace {
+
+// Whether the side to move forces mate within `moves` of its own moves.
+// Each move played spends one node of `nodes`; once it runs out every line
+// counts as unproven, so a result is only ever a proven mate.
+bool forces_mate(opera::Board& board, uint32_t moves, uint64_t& nodes, opera::MoveGen* first_move) {
+    opera::MoveGenList<> attacks;
+    opera::generateAllLegalMoves(board, attacks, board.getSideToMove());
+
+    for (size_t i = 0; i < attacks.size(); ++i) {
+        if (nodes == 0) return false;
+        --nodes;
+        if (!board.makeMove(attacks[i])) continue;
+
+        opera::Color defender = board.getSideToMove();
+        opera::MoveGenList<> replies;
+        opera::generateAllLegalMoves(board, replies, defender);
+
+        bool mates;
+        if (replies.size() == 0) {
+            mates = board.isInCheck(defender);  // Stalemate is not a mate
+        } else {
+            mates = moves > 1;
+            for (size_t j = 0; mates && j < replies.size(); ++j) {
+                if (nodes == 0) {
+                    mates = false;
+                    break;
+                }
+                --nodes;
+                if (!board.makeMove(replies[j])) continue;
+                mates = forces_mate(board, moves - 1, nodes, nullptr);
+                board.unmakeMove(replies[j]);
+            }
+        }
+        board.unmakeMove(attacks[i]);
+
+        if (mates) {
+            if (first_move) *first_move = attacks[i];
+            return true;
+        }
+    }
+    return false;
+}
+
+}
+
+rust::String engine_search_mate(const opera::Board& board, uint32_t moves, uint64_t max_nodes) {
+    if (moves == 0) return rust::String();
+
+    try {
+        opera::Board work = board;
+        opera::MoveGen first_move;
+        uint64_t nodes = max_nodes;
+        if (forces_mate(work, moves, nodes, &first_move)) {
+            return rust::String(first_move.toString());
+        }
+    } catch (const std::exception&) {
+        // No proven mate
+    }
+    return rust::String();
+}
+
+namespace {
+
+// Fixed-depth negamax over the handcrafted evaluation, counting every position
+// visited in `nodes`. Scores are from the side to move's point of view.
+int32_t bench_negamax(opera::Board& board, opera::eval::HandcraftedEvaluator& evaluator,
+                      uint32_t depth, int32_t alpha, int32_t beta, uint64_t& nodes) {
+    // Mirrors MATE_SCORE in bridge/search.rs
+    static constexpr int32_t MATE_SCORE = 32000;
+
+    ++nodes;
+    opera::Color side = board.getSideToMove();
+    opera::MoveGenList<> moves;
+    opera::generateAllLegalMoves(board, moves, side);
+    if (moves.size() == 0) {
+        return board.isInCheck(side) ? -MATE_SCORE : 0;
+    }
+    if (depth == 0) {
+        int32_t white_score = evaluator.evaluate(board, side);
+        return side == opera::WHITE ? white_score : -white_score;
+    }
+
+    for (size_t i = 0; i < moves.size(); ++i) {
+        if (!board.makeMove(moves[i])) continue;
+        int32_t score = -bench_negamax(board, evaluator, depth - 1, -beta, -alpha, nodes);
+        board.unmakeMove(moves[i]);
+
+        if (score >= beta) return beta;
+        if (score > alpha) alpha = score;
+    }
+    return alpha;
+}
+
+}
+
+uint64_t engine_bench_search(const opera::Board& board, uint32_t depth) {
+    // TODO: Run SearchEngine::search to the depth once the bridge owns one; until
+    // then a plain alpha-beta keeps bench measuring move generation and evaluation
+    static constexpr int32_t INFINITE_SCORE = 1000000;
+
+    try {
+        opera::Board work = board;
+        opera::eval::HandcraftedEvaluator evaluator;
+        uint64_t nodes = 0;
+        bench_negamax(work, evaluator, depth, -INFINITE_SCORE, INFINITE_SCORE, nodes);
+        return nodes;
+    } catch (const std::exception&) {
+        return 0;
+    }
+}
+
+namespace {
+// Hash size until the first `setoption name Hash`; mirrors the Hash option default
+constexpr uint32_t DEFAULT_HASH_MB = 16;
+// Transposition table sized by the Hash option; locked because hash warmup
+// runs on a blocking thread while the event loop may clear or resize it
+std::mutex g_tt_mutex;
+std::unique_ptr<opera::TranspositionTable> g_tt;
+
+opera::TranspositionTable& transposition_table() {
+    if (!g_tt) {
+        g_tt = std::make_unique<opera::TranspositionTable>(DEFAULT_HASH_MB);
+    }
+    return *g_tt;
+}
+}
+
+// Engine configuration
 bool engine_set_hash_size(uint32_t size_mb) {
-    // TODO: Implement hash table size setting
-    std::cout << "Setting hash size to " << size_mb << " MB" << std::endl;
-    return true;
+    if (size_mb == 0) {
+        return false;
+    }
+    // Allocate before swapping so a failed allocation keeps the old table
+    try {
+        auto table = std::make_unique<opera::TranspositionTable>(size_mb);
+        std::lock_guard<std::mutex> lock(g_tt_mutex);
+        g_tt = std::move(table);
+        return true;
+    } catch (const std::bad_alloc&) {
+        return false;
+    }
+}
+
+namespace {
+// Workers of the next search; mirrors MAX_SEARCH_THREADS in search_threads.rs
+constexpr uint32_t MAX_SEARCH_THREADS = 64;
+uint32_t g_thread_count = 1;
 }
 
 bool engine_set_threads(uint32_t thread_count) {
-    // TODO: Implement thread count setting  
-    std::cout << "Setting thread count to " << thread_count << std::endl;
+    // TODO: Size the Lazy-SMP helper pool once the bridge owns a SearchEngine
+    if (thread_count < 1 || thread_count > MAX_SEARCH_THREADS) {
+        return false;
+    }
+    g_thread_count = thread_count;
     return true;
 }
 
 bool engine_clear_hash() {
-    // TODO: Implement hash table clearing
-    std::cout << "Clearing hash tables" << std::endl;
+    std::lock_guard<std::mutex> lock(g_tt_mutex);
+    transposition_table().clear();
+    return true;
+}
+
+uint16_t engine_hashfull() {
+    // TODO: The table only fills once the bridge's search stores into it
+    std::lock_guard<std::mutex> lock(g_tt_mutex);
+    return static_cast<uint16_t>(transposition_table().hashfull());
+}
+
+namespace {
+// Draw score offset in centipawns from the engine's side
+int32_t g_contempt = 0;
+}
+
+bool engine_set_contempt(int32_t contempt_cp) {
+    if (contempt_cp < -200 || contempt_cp > 200) {
+        return false;
+    }
+    g_contempt = contempt_cp;
+    return true;
+}
+
+bool engine_warmup_hash() {
+    std::lock_guard<std::mutex> lock(g_tt_mutex);
+    transposition_table().touch_pages();
+    return true;
+}
+
+namespace {
+// Plies extensions and quiescence may add to a line in the next search
+uint32_t g_tactical_depth = opera::DEFAULT_TACTICAL_DEPTH;
+// Node budget of the next search; UINT64_MAX searches without one
+uint64_t g_node_limit = UINT64_MAX;
+// Depth limit of the next search; 0 searches to the engine's maximum depth
+uint32_t g_depth_limit = 0;
+}
+
+bool engine_set_morphy_style(bool enabled) {
+    g_morphy_style = enabled;
+    return true;
+}
+
+bool engine_set_sacrifice_threshold(int32_t threshold_cp) {
+    if (threshold_cp < 0 || threshold_cp > 1000) {
+        return false;
+    }
+    g_sacrifice_threshold = threshold_cp;
+    return true;
+}
+
+bool engine_set_tactical_depth(uint32_t plies) {
+    if (plies > 8) {
+        return false;
+    }
+    g_tactical_depth = plies;
+    return true;
+}
+
+bool engine_set_node_limit(uint64_t max_nodes) {
+    g_node_limit = max_nodes;
+    return true;
+}
+
+bool engine_set_depth_limit(uint32_t depth) {
+    g_depth_limit = depth;
+    return true;
+}
+
+uint32_t engine_search(const opera::Board& board, uint64_t max_time_ms) {
+    opera::SessionLimits limits;
+    if (g_depth_limit > 0) {
+        limits.max_depth = static_cast<int>(std::min<uint32_t>(g_depth_limit, opera::MAX_PLY));
+    }
+    limits.max_nodes = g_node_limit;
+    limits.max_time_ms = max_time_ms;
+    limits.tactical_depth = static_cast<int>(g_tactical_depth);
+    limits.contempt = g_contempt;
+    limits.use_morphy_style = g_morphy_style;
+
+    try {
+        return static_cast<uint32_t>(opera::run_session_search(board, limits).depth);
+    } catch (const std::exception&) {
+        return 0;
+    }
+}
+
+void engine_stop_search() {
+    opera::stop_session_search();
+}
+
+EngineSearchStats engine_get_search_stats() {
+    const opera::SearchStats stats = opera::last_session_result().stats;
+    return EngineSearchStats{stats.tt_hits, stats.lmr_reductions, stats.null_move_cutoffs,
+                             stats.qsearch_nodes};
+}
+
+uint32_t engine_seldepth() {
+    return static_cast<uint32_t>(opera::last_session_result().seldepth);
+}
+
+namespace {
+// Root moves to try first in the next search, best first; empty leaves the
+// search's own move ordering in charge
+std::vector<std::string> g_root_move_order;
+}
+
+bool engine_set_root_move_order(rust::Str moves) {
+    // TODO: Seed the root move list of SearchEngine::search once the bridge owns one
+    std::istringstream stream{std::string(moves)};
+    std::vector<std::string> order;
+    std::string move;
+    while (stream >> move) {
+        order.push_back(move);
+    }
+    g_root_move_order = std::move(order);
+    return true;
+}
+
+bool engine_set_chess960(bool enabled) {
+    // TODO: Generate Chess960 castling moves; only Shredder-FEN loading is supported
+    g_chess960 = enabled;
+    return true;
+}
+
+namespace {
+// Target playing strength; 0 plays at full strength
+uint32_t g_strength_elo = 0;
+}
+
+bool engine_set_strength(uint32_t elo) {
+    // TODO: Add evaluation noise and occasional blunders scaled to the target
+    // once the bridge owns a SearchEngine
+    if (elo != 0 && (elo < 1320 || elo > 3190)) {
+        return false;
+    }
+    g_strength_elo = elo;
     return true;
 }
 
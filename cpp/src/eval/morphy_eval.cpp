@@ -17,7 +17,8 @@ namespace opera {
 namespace eval {
 
 MorphyEvaluator::MorphyEvaluator(double morphy_bias)
-    : HandcraftedEvaluator(), morphy_bias_(morphy_bias) {
+    : HandcraftedEvaluator(), morphy_bias_(morphy_bias),
+      sacrifice_threshold_(SACRIFICE_COMPENSATION) {
     // Clamp bias to valid range [0.0, 2.0]
     morphy_bias_ = std::max(0.0, std::min(2.0, morphy_bias_));
 }
@@ -125,6 +126,12 @@ void MorphyEvaluator::configure_options(const std::map<std::string, std::string>
         double bias = std::atof(it->second.c_str());
         morphy_bias_ = std::max(0.0, std::min(2.0, bias));  // Clamp to [0.0, 2.0]
     }
+
+    it = options.find("SacrificeThreshold");
+    if (it != options.end()) {
+        int threshold = std::atoi(it->second.c_str());
+        sacrifice_threshold_ = std::max(0, std::min(1000, threshold));  // Clamp to [0, 1000]
+    }
 }
 
 // ============================================================================
@@ -164,9 +171,9 @@ int MorphyEvaluator::calculate_sacrifice_compensation(
         }
     }
 
-    // Scale compensation by morphy_bias and cap at SACRIFICE_COMPENSATION
+    // Scale compensation by morphy_bias and cap at the sacrifice threshold
     compensation = static_cast<int>(compensation * morphy_bias_);
-    compensation = std::min(SACRIFICE_COMPENSATION, compensation);
+    compensation = std::min(sacrifice_threshold_, compensation);
 
     return compensation;
 }
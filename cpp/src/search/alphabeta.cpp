@@ -40,6 +40,7 @@ int AlphaBetaSearch::search(int depth, int alpha, int beta) {
     pv_line.clear();
     node_check_counter = 0;
     search_start_time = std::chrono::high_resolution_clock::now();
+    line_limit = std::min(depth + tactical_depth, MAX_PLY);
     
     // Clear PV table
     for (auto& line : pv_table) {
@@ -57,12 +58,18 @@ int AlphaBetaSearch::search(int depth, int alpha, int beta) {
 
 int AlphaBetaSearch::pvs(int depth, int ply, int alpha, int beta, bool is_pv_node) {
     stats.nodes++;
+    stats.seldepth = std::max(stats.seldepth, ply);
     
     // Check for search termination frequently for responsive control
     if ((node_check_counter++ & 255) == 0 && should_stop()) {
         return alpha;
     }
     
+    // Fifty-move and repetition draws below the root
+    if (ply > 0 && (board.getHalfmoveClock() >= 100 || board.isThreefoldRepetition())) {
+        return draw_score(ply);
+    }
+    
     // Terminal node - call quiescence
     if (depth <= 0) {
         return quiescence(ply, alpha, beta);
@@ -104,7 +111,7 @@ int AlphaBetaSearch::pvs(int depth, int ply, int alpha, int beta, bool is_pv_nod
         if (in_check_flag) {
             return -CHECKMATE_SCORE + ply;  // Checkmate - prefer shorter mates
         } else {
-            return 0;  // Stalemate
+            return draw_score(ply);  // Stalemate
         }
     }
     
@@ -167,8 +174,9 @@ int AlphaBetaSearch::pvs(int depth, int ply, int alpha, int beta, bool is_pv_nod
         legal_moves++;
         bool gives_check = in_check();
         
-        // Calculate extensions
+        // Calculate extensions, keeping the line within the tactical depth
         int extension = get_extensions(move_gen, in_check_flag, gives_check);
+        extension = std::max(0, std::min(extension, line_limit - ply - depth));
         stats.extensions += extension;
         
         // Futility Pruning - skip quiet moves that can't improve alpha
@@ -214,6 +222,8 @@ int AlphaBetaSearch::pvs(int depth, int ply, int alpha, int beta, bool is_pv_nod
         // Unmake move
         board.unmakeMove(move_gen);
         
+        // Strict comparison: among equal scores the earliest move in the
+        // (deterministically sorted) move list wins
         if (score > best_score) {
             best_score = score;
             best_move = move;
@@ -255,7 +265,7 @@ int AlphaBetaSearch::pvs(int depth, int ply, int alpha, int beta, bool is_pv_nod
         if (in_check_flag) {
             return -CHECKMATE_SCORE + ply;
         } else {
-            return 0;  // Stalemate
+            return draw_score(ply);  // Stalemate
         }
     }
     
@@ -276,14 +286,16 @@ int AlphaBetaSearch::pvs(int depth, int ply, int alpha, int beta, bool is_pv_nod
 
 int AlphaBetaSearch::quiescence(int ply, int alpha, int beta) {
     stats.nodes++;
+    stats.qsearch_nodes++;
+    stats.seldepth = std::max(stats.seldepth, ply);
     
     // Check for search termination
     if ((node_check_counter++ & 1023) == 0 && should_stop()) {
         return alpha;
     }
     
-    // Maximum ply reached
-    if (ply >= MAX_PLY) {
+    // Maximum ply reached, or the line has used up its tactical depth
+    if (ply >= MAX_PLY || ply >= line_limit) {
         return evaluate();
     }
     
@@ -396,6 +408,10 @@ int AlphaBetaSearch::evaluate() {
     return material;
 }
 
+int AlphaBetaSearch::draw_score(int ply) const {
+    return (ply % 2 == 0) ? -contempt : contempt;
+}
+
 bool AlphaBetaSearch::in_check() const {
     Color us = board.getSideToMove();
     Square our_king = board.getKingSquare(us);
@@ -71,6 +71,7 @@ SearchResult SearchEngine::search(const SearchLimits& limits) {
     // Reset search state
     searching = true;
     nodes_searched = 0;
+    search_stats.reset();
     current_info = SearchInfo{};
     search_start_time = std::chrono::high_resolution_clock::now();
     last_info_time = search_start_time;  // Initialize info timer
@@ -116,8 +117,13 @@ const SearchInfo& SearchEngine::get_search_info() const {
     return current_info;
 }
 
+const SearchStats& SearchEngine::get_stats() const {
+    return search_stats;
+}
+
 void SearchEngine::reset_statistics() {
     nodes_searched = 0;
+    search_stats.reset();
     current_info = SearchInfo{};
     if (alphabeta) {
         alphabeta->reset();
@@ -195,6 +201,7 @@ SearchResult SearchEngine::iterative_deepening() {
         
         // Update best result with completed depth
         best_result.depth = depth;
+        best_result.seldepth = search_stats.seldepth;
         best_result.score = score;
         
         // Get statistics from AlphaBetaSearch
@@ -265,6 +272,7 @@ int SearchEngine::aspiration_search(int depth, int prev_score) {
     }
     
     int score = alphabeta->search(depth, alpha, beta);
+    search_stats.add(alphabeta->get_stats());
     
     // Handle aspiration window failures with progressive widening
     while ((score <= alpha || score >= beta) && window < MAX_WINDOW && !should_stop_search()) {
@@ -282,6 +290,7 @@ int SearchEngine::aspiration_search(int depth, int prev_score) {
         
         // Re-search with widened window
         score = alphabeta->search(depth, alpha, beta);
+        search_stats.add(alphabeta->get_stats());
     }
     
     return score;
@@ -439,6 +448,18 @@ void SearchEngine::set_min_depth_for_razoring(int depth) {
     }
 }
 
+void SearchEngine::set_tactical_depth(int plies) {
+    if (alphabeta) {
+        alphabeta->set_tactical_depth(plies);
+    }
+}
+
+void SearchEngine::set_contempt(int centipawns) {
+    if (alphabeta) {
+        alphabeta->set_contempt(centipawns);
+    }
+}
+
 // UCI option getter methods
 int SearchEngine::get_null_move_reduction() const {
     return alphabeta ? alphabeta->get_null_move_reduction() : DEFAULT_NULL_MOVE_REDUCTION;
@@ -476,6 +497,14 @@ int SearchEngine::get_min_depth_for_razoring() const {
     return alphabeta ? alphabeta->get_min_depth_for_razoring() : DEFAULT_MIN_DEPTH_FOR_RAZORING;
 }
 
+int SearchEngine::get_tactical_depth() const {
+    return alphabeta ? alphabeta->get_tactical_depth() : DEFAULT_TACTICAL_DEPTH;
+}
+
+int SearchEngine::get_contempt() const {
+    return alphabeta ? alphabeta->get_contempt() : 0;
+}
+
 // Evaluator configuration methods
 void SearchEngine::set_morphy_bias(double bias) {
     if (morphy_evaluator) {
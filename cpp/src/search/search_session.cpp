@@ -0,0 +1,55 @@
+#include "search/search_session.h"
+#include "search/search_engine.h"
+#include <algorithm>
+#include <atomic>
+#include <memory>
+#include <mutex>
+
+namespace opera {
+
+namespace {
+// Held for the whole of a search, so only one runs at a time
+std::mutex g_session_mutex;
+// Set by stop_session_search(); SearchEngine::search clears it as it starts
+std::atomic<bool> g_session_stop{false};
+// Position the engine searches; the engine keeps a reference to it
+std::unique_ptr<Board> g_session_board;
+std::unique_ptr<SearchEngine> g_session_engine;
+SessionResult g_last_result;
+}
+
+SessionResult run_session_search(const Board& board, const SessionLimits& limits) {
+    std::lock_guard<std::mutex> lock(g_session_mutex);
+    if (!g_session_engine) {
+        g_session_board = std::make_unique<Board>(board);
+        g_session_engine = std::make_unique<SearchEngine>(*g_session_board, g_session_stop);
+    } else {
+        *g_session_board = board;
+    }
+    g_session_engine->set_use_morphy_style(limits.use_morphy_style);
+    g_session_engine->set_tactical_depth(limits.tactical_depth);
+    g_session_engine->set_contempt(limits.contempt);
+
+    SearchLimits engine_limits;
+    engine_limits.max_depth = std::clamp(limits.max_depth, 1, MAX_PLY);
+    engine_limits.max_nodes = limits.max_nodes;
+    engine_limits.max_time_ms = limits.max_time_ms;
+
+    SearchResult result = g_session_engine->search(engine_limits);
+    g_last_result.depth = result.depth;
+    g_last_result.seldepth = result.seldepth;
+    g_last_result.score = result.score;
+    g_last_result.stats = g_session_engine->get_stats();
+    return g_last_result;
+}
+
+void stop_session_search() {
+    g_session_stop.store(true);
+}
+
+SessionResult last_session_result() {
+    std::lock_guard<std::mutex> lock(g_session_mutex);
+    return g_last_result;
+}
+
+} // namespace opera
@@ -98,6 +98,34 @@ void TranspositionTable::clear() {
     stats.reset();
 }
 
+void TranspositionTable::touch_pages() {
+    // Rewrite one byte per page; volatile keeps the compiler from eliding it
+    static constexpr size_t PAGE_BYTES = 4096;
+    volatile unsigned char* bytes = reinterpret_cast<volatile unsigned char*>(table.get());
+    size_t table_bytes = cluster_count * sizeof(TTCluster);
+    
+    for (size_t offset = 0; offset < table_bytes; offset += PAGE_BYTES) {
+        bytes[offset] = bytes[offset];
+    }
+}
+
+int TranspositionTable::hashfull() const {
+    // Sample the first 1000 entries; the table is uniformly filled by hashing
+    static constexpr size_t SAMPLE_ENTRIES = 1000;
+    size_t sample_clusters = std::min(cluster_count, SAMPLE_ENTRIES / TTCluster::CLUSTER_SIZE);
+    
+    int used = 0;
+    for (size_t i = 0; i < sample_clusters; ++i) {
+        for (const TTEntry& entry : table[i].entries) {
+            if (entry.get_key() != 0 && entry.get_age() == current_age) {
+                ++used;
+            }
+        }
+    }
+    
+    return static_cast<int>(used * 1000 / (sample_clusters * TTCluster::CLUSTER_SIZE));
+}
+
 int TranspositionTable::find_replace_index(TTCluster& cluster, uint64_t /* zobrist_key */, 
                                           uint8_t depth) const {
     int best_idx = 0;
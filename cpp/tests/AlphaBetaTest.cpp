@@ -251,6 +251,38 @@ TEST_F(AlphaBetaTest, ExtensionImpactsNodes) {
     }
 }
 
+TEST_F(AlphaBetaTest, TacticalDepthBoundsLines) {
+    setPosition("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+    
+    // Without tactical depth no line runs past the nominal depth
+    search->set_tactical_depth(0);
+    search->search(3);
+    EXPECT_EQ(search->get_stats().extensions, 0);
+    EXPECT_EQ(search->get_stats().seldepth, 3);
+    
+    // Extensions and captures may add at most the tactical depth
+    search->set_tactical_depth(2);
+    search->search(3);
+    EXPECT_GT(search->get_stats().extensions, 0);
+    EXPECT_LE(search->get_stats().seldepth, 5);
+}
+
+TEST_F(AlphaBetaTest, ContemptScoresDraws) {
+    // Every move reaches the fifty-move limit without mating
+    setPosition("8/8/4k3/8/8/8/8/R3K3 w - - 99 80");
+    
+    EXPECT_EQ(search->search(1), 0);
+    
+    // The root side avoids the draw with positive contempt and seeks it with negative
+    search->set_contempt(50);
+    tt->clear();
+    EXPECT_EQ(search->search(1), -50);
+    
+    search->set_contempt(-30);
+    tt->clear();
+    EXPECT_EQ(search->search(1), 30);
+}
+
 // Performance and Statistics Tests
 
 TEST_F(AlphaBetaTest, NodeCountIncrease) {
@@ -290,6 +290,36 @@ TEST_F(MoveOrderingTest, SortMovesByScore) {
     }
 }
 
+TEST_F(MoveOrderingTest, EqualScoresTieBreakBySquares) {
+    // Fresh ordering in the starting position: every quiet move scores 0
+    auto moves = generateMoves();
+    move_ordering->score_moves(moves, 0);
+    move_ordering->sort_moves(moves);
+    
+    // Equal-scored moves must come out ordered by from-square, then to-square
+    for (size_t i = 1; i < moves.size(); ++i) {
+        int prev_score = move_ordering->get_move_score(moves[i-1]);
+        int curr_score = move_ordering->get_move_score(moves[i]);
+        if (prev_score != curr_score) continue;
+        
+        bool ordered = moves[i-1].from() < moves[i].from() ||
+                       (moves[i-1].from() == moves[i].from() && moves[i-1].to() < moves[i].to());
+        EXPECT_TRUE(ordered) << "Tie-break violated at index " << i << ": "
+                             << moves[i-1].toString() << " before " << moves[i].toString();
+    }
+    
+    // Reversing the input must not change the sorted result
+    auto reversed = generateMoves();
+    std::reverse(reversed.begin(), reversed.begin() + reversed.size());
+    move_ordering->score_moves(reversed, 0);
+    move_ordering->sort_moves(reversed);
+    
+    ASSERT_EQ(moves.size(), reversed.size());
+    for (size_t i = 0; i < moves.size(); ++i) {
+        EXPECT_EQ(moves[i], reversed[i]) << "Order differs at index " << i;
+    }
+}
+
 // Best Move First Rate Tests
 TEST_F(MoveOrderingTest, BestMoveFirstSimulation) {
     // Test with multiple tactical positions
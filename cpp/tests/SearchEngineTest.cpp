@@ -301,6 +301,33 @@ TEST_F(SearchEngineTest, MinimalNodePerformance) {
     EXPECT_GT(result.nodes, 100);  // Should search meaningful number of nodes
 }
 
+TEST_F(SearchEngineTest, EqualScoresGiveDeterministicBestMove) {
+    // Mirror-symmetric position: Kd2/Kf2 and Kd1/Kf1 evaluate identically
+    const std::string symmetric_fen = "4k3/8/8/8/8/8/8/4K3 w - - 0 1";
+    
+    SearchLimits limits;
+    limits.max_depth = 4;
+    limits.max_time_ms = 10000;
+    
+    board->setFromFEN(symmetric_fen);
+    auto first = search_engine->search(limits);
+    ASSERT_NE(first.best_move, NULL_MOVE);
+    
+    // Repeated searches, on the same engine and on fresh engines, must agree
+    for (int run = 0; run < 3; ++run) {
+        board->setFromFEN(symmetric_fen);
+        auto repeat = search_engine->search(limits);
+        EXPECT_EQ(repeat.best_move, first.best_move) << "Same-engine run " << run;
+        
+        Board fresh_board;
+        fresh_board.setFromFEN(symmetric_fen);
+        std::atomic<bool> fresh_stop(false);
+        SearchEngine fresh_engine(fresh_board, fresh_stop);
+        auto fresh = fresh_engine.search(limits);
+        EXPECT_EQ(fresh.best_move, first.best_move) << "Fresh-engine run " << run;
+    }
+}
+
 // UCI Integration Tests  
 TEST_F(SearchEngineTest, UCIBridgeCompatibility) {
     // Test that SearchEngine works with existing UCIBridge interface
@@ -395,4 +395,37 @@ TEST_F(TranspositionTableTest, PrefetchOperation) {
     
     TTEntry entry;
     EXPECT_TRUE(tt->probe(key, entry));
-}
\ No newline at end of file
+}
+TEST_F(TranspositionTableTest, HashfullTracksCurrentSearchEntries) {
+    TranspositionTable small_tt(0);  // Clamped to the 1024-cluster minimum
+    EXPECT_EQ(small_tt.hashfull(), 0);
+    
+    // Far more stores than slots fill every sampled cluster
+    uint64_t key = 0x9E3779B97F4A7C15ULL;
+    for (int i = 0; i < 100000; ++i) {
+        key = key * 6364136223846793005ULL + 1442695040888963407ULL;
+        small_tt.store(key | 0x100000000ULL, Move(E2, E4), 10, 4, TTEntryType::EXACT);
+    }
+    EXPECT_EQ(small_tt.hashfull(), 1000);
+    
+    // Entries from earlier searches do not count
+    small_tt.new_search();
+    EXPECT_EQ(small_tt.hashfull(), 0);
+    
+    small_tt.clear();
+    EXPECT_EQ(small_tt.hashfull(), 0);
+}
+
+TEST_F(TranspositionTableTest, TouchPagesKeepsEntries) {
+    uint64_t key = 0xB000000000000000ULL;
+    Move move(G1, F3);
+    tt->store(key, move, 42, 6, TTEntryType::LOWER_BOUND);
+    
+    tt->touch_pages();
+    
+    TTEntry entry;
+    ASSERT_TRUE(tt->probe(key, entry));
+    EXPECT_EQ(entry.get_score(), 42);
+    EXPECT_EQ(entry.get_depth(), 6);
+    EXPECT_EQ(entry.get_type(), TTEntryType::LOWER_BOUND);
+}
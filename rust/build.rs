@@ -21,6 +21,14 @@ fn main() {
             .file("../cpp/src/board/Board.cpp")
             .file("../cpp/src/board/MoveGenerator.cpp")
             .file("../cpp/src/utils/Types.cpp")
+            .file("../cpp/src/eval/handcrafted_eval.cpp")
+            .file("../cpp/src/eval/morphy_eval.cpp")
+            .file("../cpp/src/search/alphabeta.cpp")
+            .file("../cpp/src/search/move_ordering.cpp")
+            .file("../cpp/src/search/search_engine.cpp")
+            .file("../cpp/src/search/search_session.cpp")
+            .file("../cpp/src/search/see.cpp")
+            .file("../cpp/src/search/transposition_table.cpp")
             .include(&cpp_include_path)
             .flag("-std=c++17")
             .flag("-O3")
@@ -11,6 +11,74 @@ use cxx::UniquePtr;
 use std::fmt;
 use tracing::{debug, error, instrument, warn};
 
+/// Side of the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// The side that moves first
+    White,
+    /// The side that moves second
+    Black,
+}
+
+/// Kind of chess piece, regardless of color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PieceKind {
+    /// Pawn
+    Pawn,
+    /// Knight
+    Knight,
+    /// Bishop
+    Bishop,
+    /// Rook
+    Rook,
+    /// Queen
+    Queen,
+    /// King
+    King,
+}
+
+/// A piece standing on the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Piece {
+    /// Side the piece belongs to
+    pub color: Color,
+    /// What kind of piece it is
+    pub kind: PieceKind,
+}
+
+impl Piece {
+    /// Decode the C++ piece index: 0-5 white pawn..king, 6-11 black; `None` otherwise
+    fn from_index(index: u8) -> Option<Self> {
+        const KINDS: [PieceKind; 6] = [
+            PieceKind::Pawn,
+            PieceKind::Knight,
+            PieceKind::Bishop,
+            PieceKind::Rook,
+            PieceKind::Queen,
+            PieceKind::King,
+        ];
+        let color = match index / 6 {
+            0 => Color::White,
+            1 => Color::Black,
+            _ => return None,
+        };
+        Some(Self {
+            color,
+            kind: KINDS[usize::from(index % 6)],
+        })
+    }
+}
+
+/// Index (0 = a1, 63 = h8) of a square in algebraic notation such as `e4`
+fn square_index(square: &str) -> UCIResult<u8> {
+    match square.as_bytes() {
+        &[file @ b'a'..=b'h', rank @ b'1'..=b'8'] => Ok((rank - b'1') * 8 + (file - b'a')),
+        _ => Err(UCIError::Position {
+            message: format!("Invalid square: {}", square),
+        }),
+    }
+}
+
 /// Safe wrapper around the C++ Board with RAII memory management
 ///
 /// This wrapper ensures that the C++ Board is properly initialized,
@@ -19,6 +87,10 @@ use tracing::{debug, error, instrument, warn};
 pub struct Board {
     /// The underlying C++ Board instance
     inner: UniquePtr<ffi::Board>,
+    /// Position before each move and the move itself, for SAN and undo
+    move_stack: Vec<(String, String)>,
+    /// Accept Shredder-FEN castling files (`UCI_Chess960`)
+    chess960: bool,
 }
 
 impl Board {
@@ -49,11 +121,30 @@ impl Board {
             });
         }
 
-        let board = Board { inner };
+        let board = Board {
+            inner,
+            move_stack: Vec::new(),
+            chess960: false,
+        };
         debug!("Successfully created chess board");
         Ok(board)
     }
 
+    /// Create a board, retrying with exponential backoff if the C++ side fails
+    ///
+    /// Allocation can fail transiently under memory pressure. Creation is
+    /// attempted up to `max_retries + 1` times, waiting `initial_delay_ms` before
+    /// the first retry and doubling the wait each time.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Board)` - A board was created
+    /// - `Err(UCIError::Ffi)` - Every attempt failed; the last error is returned
+    pub async fn new_with_retry(max_retries: usize, initial_delay_ms: u64) -> UCIResult<Self> {
+        crate::runtime::async_utils::create_with_retry(Self::new, max_retries, initial_delay_ms)
+            .await
+    }
+
     /// Set the board position from a FEN string
     ///
     /// # Arguments
@@ -100,6 +191,7 @@ impl Board {
             });
         }
 
+        self.move_stack.clear();
         debug!(fen = %fen, "Successfully set board position from FEN");
         Ok(())
     }
@@ -136,6 +228,26 @@ impl Board {
         Ok(fen.to_string())
     }
 
+    /// Render the board as an ASCII diagram
+    ///
+    /// White pieces are uppercase, black lowercase and empty squares `.`, with
+    /// rank 8 on the first line and a file legend on the last.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(String)` - Newline-separated diagram
+    /// - `Err(UCIError::Ffi)` - Failed to render the C++ board
+    #[instrument(level = "debug", skip(self))]
+    pub fn to_ascii(&self) -> UCIResult<String> {
+        let art = ffi::board_to_ascii(&self.inner);
+        if art.is_empty() {
+            return Err(UCIError::Ffi {
+                message: "Failed to render board - empty result".to_string(),
+            });
+        }
+        Ok(art.to_string())
+    }
+
     /// Make a move on the board
     ///
     /// # Arguments
@@ -175,6 +287,7 @@ impl Board {
             });
         }
 
+        let fen_before = self.get_fen()?;
         let success = ffi::board_make_move(self.inner.pin_mut(), move_str);
         if !success {
             warn!(move_str = %move_str, "C++ board rejected move");
@@ -182,11 +295,65 @@ impl Board {
                 message: format!("Illegal move: {}", move_str),
             });
         }
+        self.move_stack.push((fen_before, move_str.to_string()));
 
         debug!(move_str = %move_str, "Successfully made move on board");
         Ok(())
     }
 
+    /// Take back the most recent move made with [`Board::make_move`]
+    ///
+    /// Moves can be undone back to the position last set by creation, `reset`
+    /// or `set_from_fen`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` - Board is back in the position before the move
+    /// - `Err(UCIError::Move)` - There is no move to undo
+    /// - `Err(UCIError::Position)` - The C++ board could not take the move back
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let mut board = Board::new()?;
+    /// board.make_move("e2e4")?;
+    /// board.undo_move()?;
+    /// assert_eq!(board.get_fen()?, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn undo_move(&mut self) -> UCIResult<()> {
+        let Some((fen_before, move_str)) = self.move_stack.pop() else {
+            return Err(UCIError::Move {
+                message: "No move to undo".to_string(),
+            });
+        };
+
+        // Restoring from FEN instead would wipe the history repetition detection needs
+        if !ffi::board_undo_move(self.inner.pin_mut(), &fen_before, &move_str) {
+            error!(move_str = %move_str, "C++ board failed to undo move");
+            self.move_stack.push((fen_before, move_str.clone()));
+            return Err(UCIError::Position {
+                message: format!("Failed to undo move: {}", move_str),
+            });
+        }
+        let fen_after = ffi::board_get_fen(&self.inner);
+        if fen_after != fen_before.as_str() {
+            error!(move_str = %move_str, fen = %fen_after, "Undo left a different position");
+            return Err(UCIError::Position {
+                message: format!(
+                    "Undoing {} reached {} instead of {}",
+                    move_str, fen_after, fen_before
+                ),
+            });
+        }
+
+        debug!(move_str = %move_str, "Successfully undid move");
+        Ok(())
+    }
+
     /// Check if a move is valid without making it
     ///
     /// # Arguments
@@ -245,6 +412,7 @@ impl Board {
     pub fn reset(&mut self) {
         debug!("Resetting board to starting position");
         ffi::board_reset(self.inner.pin_mut());
+        self.move_stack.clear();
         debug!("Board reset to starting position");
     }
 
@@ -323,6 +491,406 @@ impl Board {
         Ok(is_stale)
     }
 
+    /// Total material on the board in centipawns, both sides, kings excluded
+    ///
+    /// Uses P=100, N=320, B=330, R=500, Q=900, so the starting position is 8000.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let board = Board::new()?;
+    /// assert_eq!(board.material_count()?, 8000);
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn material_count(&self) -> UCIResult<u32> {
+        Ok(ffi::board_material_count(&self.inner))
+    }
+
+    /// Zobrist hash of the current position
+    ///
+    /// Identical positions (same pieces, side to move, castling rights and
+    /// en passant square) share a key, which makes it suitable for repetition
+    /// detection.
+    #[instrument(level = "debug", skip(self))]
+    pub fn position_key(&self) -> UCIResult<u64> {
+        Ok(ffi::board_position_key(&self.inner))
+    }
+
+    /// Squares occupied by `color`'s pieces, in algebraic notation
+    ///
+    /// Squares are ordered a1, b1, ..., h8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::{Board, Color};
+    ///
+    /// let board = Board::new()?;
+    /// assert_eq!(board.occupied_squares(Color::White)?.len(), 16);
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn occupied_squares(&self, color: Color) -> UCIResult<Vec<String>> {
+        let bitboard = ffi::board_color_bitboard(&self.inner, color == Color::White);
+
+        Ok((0..64u8)
+            .filter(|square| bitboard & (1u64 << square) != 0)
+            .map(|square| {
+                let file = (b'a' + square % 8) as char;
+                let rank = (b'1' + square / 8) as char;
+                format!("{}{}", file, rank)
+            })
+            .collect())
+    }
+
+    /// Piece standing on `square` (`a1`..`h8`), or `None` if it is empty
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(Piece))` - The piece on the square
+    /// - `Ok(None)` - The square is empty
+    /// - `Err(UCIError::Position)` - `square` is not a square name
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::{Board, Color, Piece, PieceKind};
+    ///
+    /// let board = Board::new()?;
+    /// let king = Piece { color: Color::White, kind: PieceKind::King };
+    /// assert_eq!(board.piece_at("e1")?, Some(king));
+    /// assert_eq!(board.piece_at("e4")?, None);
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn piece_at(&self, square: &str) -> UCIResult<Option<Piece>> {
+        let index = square_index(square)?;
+        Ok(Piece::from_index(ffi::board_piece_at(&self.inner, index)))
+    }
+
+    /// Plies played since the last capture or pawn move
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let mut board = Board::new()?;
+    /// board.make_move("g1f3")?;
+    /// assert_eq!(board.halfmove_clock()?, 1);
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn halfmove_clock(&self) -> UCIResult<u32> {
+        Ok(ffi::board_halfmove_clock(&self.inner))
+    }
+
+    /// Current move number, starting at 1 and incremented after each Black move
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let mut board = Board::new()?;
+    /// board.make_move("e2e4")?;
+    /// board.make_move("e7e5")?;
+    /// assert_eq!(board.fullmove_number()?, 2);
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn fullmove_number(&self) -> UCIResult<u32> {
+        Ok(ffi::board_fullmove_number(&self.inner))
+    }
+
+    /// Check whether the fifty-move rule allows a draw claim
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(true)` - 100 or more plies without a capture or pawn move
+    /// - `Ok(false)` - The clock is below 100
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let mut board = Board::new()?;
+    /// board.set_from_fen("8/8/4k3/8/8/4K3/8/8 w - - 100 80")?;
+    /// assert!(board.is_fifty_move_draw()?);
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn is_fifty_move_draw(&self) -> UCIResult<bool> {
+        Ok(self.halfmove_clock()? >= 100)
+    }
+
+    /// Number of pieces on the board, kings and pawns included
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let board = Board::new()?;
+    /// assert_eq!(board.piece_count()?, 32);
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn piece_count(&self) -> UCIResult<u32> {
+        let white = ffi::board_color_bitboard(&self.inner, true);
+        let black = ffi::board_color_bitboard(&self.inner, false);
+        Ok((white | black).count_ones())
+    }
+
+    /// Enumerate all legal moves for the side to move
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<String>)` - Legal moves in UCI notation; empty on checkmate or stalemate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let board = Board::new()?;
+    /// assert_eq!(board.legal_moves()?.len(), 20);
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn legal_moves(&self) -> UCIResult<Vec<String>> {
+        let moves: Vec<String> = ffi::board_legal_moves(&self.inner)
+            .into_iter()
+            .map(|mv| mv.to_string())
+            .collect();
+
+        debug!(count = moves.len(), "Generated legal moves");
+        Ok(moves)
+    }
+
+    /// Count leaf nodes below each legal root move (perft "divide")
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - Number of plies to expand; depth 0 yields no root moves
+    ///
+    /// # Returns
+    ///
+    /// `(move, nodes)` pairs in UCI notation, one per legal root move
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let board = Board::new()?;
+    /// let divide = board.perft_divide(1)?;
+    /// assert_eq!(divide.len(), 20);
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn perft_divide(&self, depth: u32) -> UCIResult<Vec<(String, u64)>> {
+        debug!(depth, "Running perft divide");
+
+        let entries = ffi::board_perft_divide(&self.inner, depth)
+            .into_iter()
+            .map(|entry| (entry.uci_move, entry.nodes))
+            .collect();
+
+        Ok(entries)
+    }
+
+    /// Count all leaf nodes reachable in exactly `depth` plies
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let board = Board::new()?;
+    /// assert_eq!(board.perft(2)?, 400);
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn perft(&self, depth: u32) -> UCIResult<u64> {
+        if depth == 0 {
+            return Ok(1);
+        }
+
+        Ok(self
+            .perft_divide(depth)?
+            .iter()
+            .map(|(_, nodes)| nodes)
+            .sum())
+    }
+
+    /// Convert a legal move from UCI notation to Standard Algebraic Notation
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(String)` - SAN including check/mate suffix (e.g. "Nf3", "exd5", "O-O", "Qh4#")
+    /// - `Err(UCIError::Move)` - Move is malformed or illegal in this position
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let board = Board::new()?;
+    /// assert_eq!(board.uci_to_san("g1f3")?, "Nf3");
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn uci_to_san(&self, move_str: &str) -> UCIResult<String> {
+        if !self.is_valid_move_format(move_str) {
+            return Err(UCIError::Move {
+                message: format!("Invalid move format: {}", move_str),
+            });
+        }
+
+        let san = ffi::board_move_to_san(&self.inner, move_str);
+        if san.is_empty() {
+            return Err(UCIError::Move {
+                message: format!("Illegal move in current position: {}", move_str),
+            });
+        }
+
+        Ok(san.to_string())
+    }
+
+    /// Convert a move from Standard Algebraic Notation to UCI notation
+    ///
+    /// Check/mate suffixes, annotations (`!`, `?`) and the promotion `=` are
+    /// optional, and `0-0` is accepted for castling.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(String)` - The matching legal move in UCI notation (e.g. "g1f3", "e7e8q")
+    /// - `Err(UCIError::Move)` - No legal move matches, or the SAN is ambiguous
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let board = Board::new()?;
+    /// assert_eq!(board.san_to_uci("Nf3")?, "g1f3");
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn san_to_uci(&self, san: &str) -> UCIResult<String> {
+        let uci = ffi::board_san_to_uci(&self.inner, san.trim());
+        if uci.is_empty() {
+            return Err(UCIError::Move {
+                message: format!("No legal move matches SAN: {}", san),
+            });
+        }
+
+        Ok(uci.to_string())
+    }
+
+    /// Standard Algebraic Notation for the most recent move made on this board
+    ///
+    /// SAN is computed against the position before the move, so disambiguation
+    /// and check/mate suffixes are correct.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(String))` - SAN of the last move
+    /// - `Ok(None)` - No move made since creation, reset or the last FEN was set
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let mut board = Board::new()?;
+    /// board.make_move("g1f3")?;
+    /// assert_eq!(board.last_move_san()?, Some("Nf3".to_string()));
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn last_move_san(&self) -> UCIResult<Option<String>> {
+        let Some((fen_before, move_str)) = self.move_stack.last() else {
+            return Ok(None);
+        };
+
+        let mut previous = Board::new()?;
+        previous.set_from_fen(fen_before)?;
+        previous.uci_to_san(move_str).map(Some)
+    }
+
+    /// FEN reached by replaying a principal variation from this position
+    ///
+    /// The PV is played on a copy, so this board is left untouched. Replay stops
+    /// at the first move that is not legal, and the FEN up to that point is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let board = Board::new()?;
+    /// let pv = ["e2e3".to_string(), "e7e6".to_string()];
+    /// assert!(board.pv_end_fen(&pv)?.starts_with("rnbqkbnr/pppp1ppp/4p3"));
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn pv_end_fen(&self, pv: &[String]) -> UCIResult<String> {
+        let mut scratch = self.snapshot()?;
+
+        for (ply, pv_move) in pv.iter().enumerate() {
+            if !scratch.legal_moves()?.contains(pv_move) {
+                debug!(ply, pv_move = %pv_move, "PV truncated at illegal move");
+                break;
+            }
+            scratch.make_move(pv_move)?;
+        }
+
+        scratch.get_fen()
+    }
+
+    /// FEN after playing `uci_move`, leaving this board unchanged
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(String)` - FEN of the resulting position
+    /// - `Err(UCIError::Move)` - Move is malformed or illegal in this position
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::board::Board;
+    ///
+    /// let board = Board::new()?;
+    /// assert!(board.fen_after_move("g1f3")?.starts_with("rnbqkbnr/pppppppp/8/8/8/5N2"));
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip(self))]
+    pub fn fen_after_move(&self, uci_move: &str) -> UCIResult<String> {
+        if !self.legal_moves()?.iter().any(|mv| mv == uci_move) {
+            return Err(UCIError::Move {
+                message: format!("Illegal move: {}", uci_move),
+            });
+        }
+
+        let mut scratch = self.snapshot()?;
+        scratch.make_move(uci_move)?;
+        scratch.get_fen()
+    }
+
+    /// Accept Shredder-FEN castling rights such as `HAha` in `set_from_fen`
+    ///
+    /// The C++ core must be switched over separately with `engine_set_chess960`.
+    pub fn set_chess960(&mut self, enabled: bool) {
+        self.chess960 = enabled;
+    }
+
     /// Get a reference to the underlying C++ Board for advanced operations
     ///
     /// This method provides safe access to the C++ Board for interfacing with
@@ -337,6 +905,14 @@ impl Board {
 
     // Private helper methods
 
+    /// Independent copy of the current position (without move history)
+    fn snapshot(&self) -> UCIResult<Board> {
+        let mut copy = Board::new()?;
+        copy.chess960 = self.chess960;
+        copy.set_from_fen(&self.get_fen()?)?;
+        Ok(copy)
+    }
+
     /// Validate FEN string format
     fn is_valid_fen_format(&self, fen: &str) -> bool {
         // Basic FEN validation - should have 6 space-separated fields
@@ -379,6 +955,7 @@ impl Board {
             for ch in castling.chars() {
                 match ch {
                     'K' | 'Q' | 'k' | 'q' => continue,
+                    'A'..='H' | 'a'..='h' if self.chess960 => continue,
                     _ => return false,
                 }
             }
@@ -473,6 +1050,15 @@ mod tests {
         assert!(board.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_board_creation_with_retry() {
+        let board = Board::new_with_retry(2, 1).await.unwrap();
+        assert_eq!(
+            board.get_fen().unwrap(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+    }
+
     #[test]
     fn test_starting_position() {
         let board = Board::new().unwrap();
@@ -588,6 +1174,20 @@ mod tests {
         // 7 ranks instead of 8
     }
 
+    #[test]
+    fn test_chess960_castling_fen_validation() {
+        let mut board = Board::new().unwrap();
+        let shredder = "bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w HAha - 0 1";
+
+        assert!(!board.is_valid_fen_format(shredder));
+
+        board.set_chess960(true);
+        assert!(board.is_valid_fen_format(shredder));
+        assert!(
+            !board.is_valid_fen_format("bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w HAhx - 0 1")
+        );
+    }
+
     #[test]
     fn test_move_format_validation() {
         let board = Board::new().unwrap();
@@ -606,6 +1206,463 @@ mod tests {
         assert!(!board.is_valid_move_format("e2e4k")); // Invalid promotion
     }
 
+    #[test]
+    fn test_legal_moves() {
+        let mut board = Board::new().unwrap();
+
+        let moves = board.legal_moves().unwrap();
+        assert_eq!(moves.len(), 20);
+        assert!(moves.contains(&"e2e4".to_string()));
+        assert!(moves.contains(&"g1f3".to_string()));
+
+        // Fool's mate: White has no legal moves
+        board
+            .set_from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3")
+            .unwrap();
+        assert!(board.legal_moves().unwrap().is_empty());
+
+        // Stalemate: Black has no legal moves either
+        board
+            .set_from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1")
+            .unwrap();
+        assert!(board.legal_moves().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_perft_from_startpos() {
+        let board = Board::new().unwrap();
+
+        assert_eq!(board.perft(0).unwrap(), 1);
+        assert_eq!(board.perft(1).unwrap(), 20);
+        assert_eq!(board.perft(2).unwrap(), 400);
+        assert_eq!(board.perft(3).unwrap(), 8902);
+
+        let divide = board.perft_divide(2).unwrap();
+        assert_eq!(divide.len(), 20);
+        assert!(divide.iter().all(|(_, nodes)| *nodes == 20));
+        assert!(divide.iter().any(|(mv, _)| mv == "g1f3"));
+
+        // Perft must leave the board untouched
+        assert_eq!(
+            board.get_fen().unwrap(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+    }
+
+    #[test]
+    fn test_uci_to_san() {
+        let mut board = Board::new().unwrap();
+
+        assert_eq!(board.uci_to_san("g1f3").unwrap(), "Nf3");
+        assert_eq!(board.uci_to_san("e2e4").unwrap(), "e4");
+        assert!(board.uci_to_san("e2e5").is_err());
+
+        // Captures, check and mate (fool's mate)
+        for mv in ["f2f3", "e7e5", "g2g4"] {
+            board.make_move(mv).unwrap();
+        }
+        assert_eq!(board.uci_to_san("d8h4").unwrap(), "Qh4#");
+
+        // Castling
+        board
+            .set_from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1")
+            .unwrap();
+        assert_eq!(board.uci_to_san("e1g1").unwrap(), "O-O");
+        assert_eq!(board.uci_to_san("e1c1").unwrap(), "O-O-O");
+        assert_eq!(board.uci_to_san("a1a8").unwrap(), "Rxa8+");
+
+        // File and rank disambiguation
+        board
+            .set_from_fen("7k/8/8/8/R7/8/8/R4RK1 w - - 0 1")
+            .unwrap();
+        assert_eq!(board.uci_to_san("a1d1").unwrap(), "Rad1");
+        assert_eq!(board.uci_to_san("f1d1").unwrap(), "Rfd1");
+        assert_eq!(board.uci_to_san("a1a2").unwrap(), "R1a2");
+
+        // Knight disambiguation
+        board
+            .set_from_fen("rnbqkbnr/pppppppp/8/8/3P4/5N2/PPP1PPPP/RNBQKB1R w KQkq - 0 1")
+            .unwrap();
+        assert_eq!(board.uci_to_san("b1d2").unwrap(), "Nbd2");
+        assert_eq!(board.uci_to_san("f3d2").unwrap(), "Nfd2");
+
+        // Pawn capture and promotion
+        board
+            .set_from_fen("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2")
+            .unwrap();
+        assert_eq!(board.uci_to_san("e4d5").unwrap(), "exd5");
+        board
+            .set_from_fen("k7/4P3/8/8/8/8/8/4K3 w - - 0 1")
+            .unwrap();
+        assert_eq!(board.uci_to_san("e7e8q").unwrap(), "e8=Q+");
+        assert_eq!(board.uci_to_san("e7e8n").unwrap(), "e8=N");
+    }
+
+    #[test]
+    fn test_make_move_castles_and_captures_en_passant() {
+        let mut board = Board::new().unwrap();
+
+        board
+            .set_from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1")
+            .unwrap();
+        board.make_move("e1g1").unwrap();
+        board.make_move("e8c8").unwrap();
+        assert!(board
+            .get_fen()
+            .unwrap()
+            .starts_with("2kr3r/8/8/8/8/8/8/R4RK1 w - -"));
+
+        board
+            .set_from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1")
+            .unwrap();
+        board.make_move("e5d6").unwrap();
+        assert!(board
+            .get_fen()
+            .unwrap()
+            .starts_with("4k3/8/3P4/8/8/8/8/4K3 b"));
+    }
+
+    #[test]
+    fn test_make_move_rejects_illegal_move() {
+        let mut board = Board::new().unwrap();
+        let fen = board.get_fen().unwrap();
+
+        // Well-formed but not legal here: a pawn three squares, a knight onto its own pawn
+        assert!(matches!(
+            board.make_move("e2e5"),
+            Err(UCIError::Move { .. })
+        ));
+        assert!(matches!(
+            board.make_move("g1e2"),
+            Err(UCIError::Move { .. })
+        ));
+        assert_eq!(board.get_fen().unwrap(), fen);
+    }
+
+    #[test]
+    fn test_san_to_uci() {
+        let mut board = Board::new().unwrap();
+        assert_eq!(board.san_to_uci("Nf3").unwrap(), "g1f3");
+        assert_eq!(board.san_to_uci("e4").unwrap(), "e2e4");
+        assert!(board.san_to_uci("e5").is_err());
+        assert!(board.san_to_uci("").is_err());
+
+        // Disambiguation is required when two knights reach the same square
+        board
+            .set_from_fen("rnbqkbnr/pppppppp/8/8/3P4/5N2/PPP1PPPP/RNBQKB1R w KQkq - 0 1")
+            .unwrap();
+        assert_eq!(board.san_to_uci("Nbd2").unwrap(), "b1d2");
+        assert_eq!(board.san_to_uci("Nfd2").unwrap(), "f3d2");
+        assert!(board.san_to_uci("Nd2").is_err(), "Ambiguous SAN must fail");
+
+        board
+            .set_from_fen("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2")
+            .unwrap();
+        assert_eq!(board.san_to_uci("exd5").unwrap(), "e4d5");
+
+        board
+            .set_from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1")
+            .unwrap();
+        assert_eq!(board.san_to_uci("O-O").unwrap(), "e1g1");
+        assert_eq!(board.san_to_uci("0-0-0").unwrap(), "e1c1");
+        assert_eq!(board.san_to_uci("Rxa8+").unwrap(), "a1a8");
+        assert_eq!(board.san_to_uci("Rxa8").unwrap(), "a1a8");
+
+        board
+            .set_from_fen("k7/4P3/8/8/8/8/8/4K3 w - - 0 1")
+            .unwrap();
+        assert_eq!(board.san_to_uci("e8=Q+").unwrap(), "e7e8q");
+        assert_eq!(board.san_to_uci("e8N").unwrap(), "e7e8n");
+
+        // Fool's mate
+        board.reset();
+        for mv in ["f2f3", "e7e5", "g2g4"] {
+            board.make_move(mv).unwrap();
+        }
+        assert_eq!(board.san_to_uci("Qh4#").unwrap(), "d8h4");
+    }
+
+    #[test]
+    fn test_last_move_san() {
+        let mut board = Board::new().unwrap();
+        assert_eq!(board.last_move_san().unwrap(), None);
+
+        board.make_move("g1f3").unwrap();
+        assert_eq!(board.last_move_san().unwrap(), Some("Nf3".to_string()));
+
+        // Capture
+        for mv in ["d7d5", "e2e4"] {
+            board.make_move(mv).unwrap();
+        }
+        board.make_move("d5e4").unwrap();
+        assert_eq!(board.last_move_san().unwrap(), Some("dxe4".to_string()));
+
+        // Disambiguation needs the pre-move position
+        board
+            .set_from_fen("7k/8/8/8/R7/8/8/R4RK1 w - - 0 1")
+            .unwrap();
+        assert_eq!(board.last_move_san().unwrap(), None);
+        board.make_move("a1d1").unwrap();
+        assert_eq!(board.last_move_san().unwrap(), Some("Rad1".to_string()));
+
+        // A rejected move keeps the previous last move
+        assert!(board.make_move("h8h1").is_err());
+        assert_eq!(board.last_move_san().unwrap(), Some("Rad1".to_string()));
+
+        board.reset();
+        assert_eq!(board.last_move_san().unwrap(), None);
+    }
+
+    #[test]
+    fn test_undo_move() {
+        let mut board = Board::new().unwrap();
+        assert!(matches!(board.undo_move(), Err(UCIError::Move { .. })));
+
+        let start = board.get_fen().unwrap();
+        board.make_move("e2e4").unwrap();
+        board.undo_move().unwrap();
+        assert_eq!(board.get_fen().unwrap(), start);
+
+        // Several moves including a capture unwind one at a time
+        let mut fens = vec![start.clone()];
+        for mv in ["e2e4", "d7d5", "e4d5", "d8d5"] {
+            board.make_move(mv).unwrap();
+            fens.push(board.get_fen().unwrap());
+        }
+        fens.pop();
+        while let Some(expected) = fens.pop() {
+            board.undo_move().unwrap();
+            assert_eq!(board.get_fen().unwrap(), expected);
+        }
+        assert!(board.undo_move().is_err());
+        assert_eq!(board.last_move_san().unwrap(), None);
+
+        // Promotion, and nothing to undo past a new FEN
+        let fen = "8/4P3/8/8/8/8/k7/4K3 w - - 0 1";
+        board.set_from_fen(fen).unwrap();
+        board.make_move("e7e8q").unwrap();
+        board.undo_move().unwrap();
+        assert_eq!(board.get_fen().unwrap(), fen);
+        assert!(board.undo_move().is_err());
+    }
+
+    #[test]
+    fn test_undo_special_moves_restores_position() {
+        let mut board = Board::new().unwrap();
+        let cases = [
+            // Castling on both wings must put the rook back
+            ("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "e1g1"),
+            ("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "e1c1"),
+            ("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1", "e8g8"),
+            // En passant must restore the captured pawn
+            ("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"),
+            ("4k3/8/8/8/3Pp3/8/8/4K3 b - d3 0 1", "e4d3"),
+            // Promotion, with and without a capture, must restore the pawn
+            ("3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7e8n"),
+            ("3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7d8q"),
+        ];
+
+        for (fen, mv) in cases {
+            board.set_from_fen(fen).unwrap();
+            let key = board.position_key().unwrap();
+            board.make_move(mv).unwrap();
+            board.undo_move().unwrap();
+            assert_eq!(board.get_fen().unwrap(), fen, "undoing {}", mv);
+            assert_eq!(board.position_key().unwrap(), key, "undoing {}", mv);
+        }
+    }
+
+    #[test]
+    fn test_undo_keeps_history_for_repetition() {
+        let mut board = Board::new().unwrap();
+        let start = board.position_key().unwrap();
+        for mv in ["g1f3", "g8f6", "f3g1"] {
+            board.make_move(mv).unwrap();
+        }
+
+        // Taking back and replaying a move goes through the C++ history, not a FEN reload
+        board.undo_move().unwrap();
+        board.make_move("f3g1").unwrap();
+        board.make_move("f6g8").unwrap();
+        assert_eq!(board.position_key().unwrap(), start);
+        for _ in 0..4 {
+            board.undo_move().unwrap();
+        }
+        assert_eq!(board.position_key().unwrap(), start);
+        assert!(board.undo_move().is_err());
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        let board = Board::new().unwrap();
+        let art = board.to_ascii().unwrap();
+        let lines: Vec<&str> = art.lines().collect();
+
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0].trim_end(), "8 r n b q k b n r");
+        assert_eq!(lines[7].trim_end(), "1 R N B Q K B N R");
+        assert_eq!(lines[8].trim_end(), "  a b c d e f g h");
+    }
+
+    #[test]
+    fn test_piece_at() {
+        let mut board = Board::new().unwrap();
+        let piece = |color, kind| Some(Piece { color, kind });
+
+        assert_eq!(
+            board.piece_at("e1").unwrap(),
+            piece(Color::White, PieceKind::King)
+        );
+        assert_eq!(
+            board.piece_at("d8").unwrap(),
+            piece(Color::Black, PieceKind::Queen)
+        );
+        assert_eq!(
+            board.piece_at("g7").unwrap(),
+            piece(Color::Black, PieceKind::Pawn)
+        );
+        assert_eq!(board.piece_at("e4").unwrap(), None);
+
+        board.make_move("e2e4").unwrap();
+        assert_eq!(
+            board.piece_at("e4").unwrap(),
+            piece(Color::White, PieceKind::Pawn)
+        );
+        assert_eq!(board.piece_at("e2").unwrap(), None);
+    }
+
+    #[test]
+    fn test_move_counters() {
+        let mut board = Board::new().unwrap();
+        assert_eq!(board.halfmove_clock().unwrap(), 0);
+        assert_eq!(board.fullmove_number().unwrap(), 1);
+
+        for (i, mv) in ["g1f3", "g8f6", "f3g1", "f6g8"].iter().enumerate() {
+            board.make_move(mv).unwrap();
+            assert_eq!(board.halfmove_clock().unwrap(), i as u32 + 1);
+        }
+        assert_eq!(board.fullmove_number().unwrap(), 3);
+
+        // Pawn move resets the clock
+        board.make_move("e2e4").unwrap();
+        assert_eq!(board.halfmove_clock().unwrap(), 0);
+
+        board.make_move("b8c6").unwrap();
+        board.make_move("b1c3").unwrap();
+        assert_eq!(board.halfmove_clock().unwrap(), 2);
+
+        // Capture resets the clock
+        board.make_move("c6d4").unwrap();
+        board.make_move("g1f3").unwrap();
+        assert_eq!(board.halfmove_clock().unwrap(), 4);
+        board.make_move("d4f3").unwrap();
+        assert_eq!(board.halfmove_clock().unwrap(), 0);
+        assert_eq!(board.fullmove_number().unwrap(), 6);
+    }
+
+    #[test]
+    fn test_is_fifty_move_draw() {
+        let mut board = Board::new().unwrap();
+        assert!(!board.is_fifty_move_draw().unwrap());
+
+        board
+            .set_from_fen("8/8/4k3/8/8/4K3/8/8 w - - 99 80")
+            .unwrap();
+        assert_eq!(board.halfmove_clock().unwrap(), 99);
+        assert_eq!(board.fullmove_number().unwrap(), 80);
+        assert!(!board.is_fifty_move_draw().unwrap());
+
+        board.make_move("e3d3").unwrap();
+        assert!(board.is_fifty_move_draw().unwrap());
+    }
+
+    #[test]
+    fn test_piece_at_rejects_bad_square() {
+        let board = Board::new().unwrap();
+        for square in ["z9", "e", "e10", "E1", "i1", ""] {
+            assert!(matches!(
+                board.piece_at(square),
+                Err(UCIError::Position { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_occupied_squares() {
+        let mut board = Board::new().unwrap();
+
+        let white = board.occupied_squares(Color::White).unwrap();
+        assert_eq!(white.len(), 16);
+        assert!(white
+            .iter()
+            .all(|sq| sq.ends_with('1') || sq.ends_with('2')));
+        assert_eq!(white[0], "a1");
+
+        let black = board.occupied_squares(Color::Black).unwrap();
+        assert_eq!(black.len(), 16);
+        assert!(black
+            .iter()
+            .all(|sq| sq.ends_with('7') || sq.ends_with('8')));
+        assert_eq!(black[15], "h8");
+
+        board
+            .set_from_fen("8/8/8/4k3/8/8/4P3/4K3 w - - 0 1")
+            .unwrap();
+        assert_eq!(board.occupied_squares(Color::White).unwrap(), ["e1", "e2"]);
+        assert_eq!(board.occupied_squares(Color::Black).unwrap(), ["e5"]);
+    }
+
+    #[test]
+    fn test_fen_after_move() {
+        let board = Board::new().unwrap();
+        let start_fen = board.get_fen().unwrap();
+
+        assert_eq!(
+            board.fen_after_move("e2e4").unwrap(),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+        assert_eq!(board.get_fen().unwrap(), start_fen);
+
+        assert!(matches!(
+            board.fen_after_move("e2e5"),
+            Err(UCIError::Move { .. })
+        ));
+        assert!(board.fen_after_move("xyz").is_err());
+        assert_eq!(board.get_fen().unwrap(), start_fen);
+    }
+
+    #[test]
+    fn test_pv_end_fen() {
+        let board = Board::new().unwrap();
+        let start_fen = board.get_fen().unwrap();
+        let pv: Vec<String> = ["g1f3", "g8f6", "b1c3"]
+            .iter()
+            .map(|mv| mv.to_string())
+            .collect();
+
+        let expected = "rnbqkb1r/pppppppp/5n2/8/8/2N2N2/PPPPPPPP/R1BQKB1R b KQkq - 3 2";
+        assert_eq!(board.pv_end_fen(&pv).unwrap(), expected);
+        assert_eq!(board.get_fen().unwrap(), start_fen);
+
+        // An illegal tail is cut off
+        let mut truncated = pv.clone();
+        truncated.extend(["e1e3".to_string(), "e7e5".to_string()]);
+        assert_eq!(board.pv_end_fen(&truncated).unwrap(), expected);
+
+        assert_eq!(board.pv_end_fen(&[]).unwrap(), start_fen);
+    }
+
+    #[test]
+    fn test_material_count() {
+        let mut board = Board::new().unwrap();
+        assert_eq!(board.material_count().unwrap(), 8000);
+
+        board
+            .set_from_fen("8/8/8/4k3/8/8/4P3/4K3 w - - 0 1")
+            .unwrap();
+        assert_eq!(board.material_count().unwrap(), 100);
+    }
+
     #[test]
     fn test_debug_display() {
         let board = Board::new().unwrap();
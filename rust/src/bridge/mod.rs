@@ -7,6 +7,9 @@
 
 pub mod board;
 pub mod safety_tests;
+/// Safe wrapper for the C++ search
+pub mod search;
 
 // Re-export main bridge components
-pub use board::Board;
+pub use board::{Board, Color, Piece, PieceKind};
+pub use search::SearchEngine;
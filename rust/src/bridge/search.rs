@@ -0,0 +1,285 @@
+// Safe Rust wrapper for the C++ search through FFI
+//
+// Exposes the C++ search engine that runs `go` searches, and the parts of the
+// C++ search that the UCI layer queries directly outside of one.
+
+use crate::bridge::Board;
+use crate::error::{UCIError, UCIResult};
+use crate::ffi::ffi;
+use crate::uci::state::SearchCounters;
+use cxx::UniquePtr;
+use std::time::Duration;
+use tracing::{debug, instrument};
+
+/// Score reported for a side that is checkmated (negated for the mating side)
+pub const MATE_SCORE: i32 = 32000;
+
+/// Safe wrapper around the C++ search
+pub struct SearchEngine {
+    /// The underlying C++ Search instance
+    inner: UniquePtr<ffi::Search>,
+}
+
+impl SearchEngine {
+    /// Create a new search instance
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(SearchEngine)` - Successfully created search
+    /// - `Err(UCIError::Ffi)` - Failed to create C++ Search instance
+    pub fn new() -> UCIResult<Self> {
+        let inner = ffi::create_search();
+        if inner.is_null() {
+            return Err(UCIError::Ffi {
+                message: "Failed to create C++ Search instance".to_string(),
+            });
+        }
+
+        Ok(Self { inner })
+    }
+
+    /// Static evaluation of `board` without searching
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(i32)` - Centipawns from the side to move's point of view;
+    ///   `-MATE_SCORE` if checkmated and 0 in stalemate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::bridge::{Board, SearchEngine};
+    ///
+    /// let board = Board::new()?;
+    /// let score = SearchEngine::new()?.static_eval(&board)?;
+    /// assert!(score.abs() < 50);
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    #[instrument(level = "debug", skip_all)]
+    pub fn static_eval(&self, board: &Board) -> UCIResult<i32> {
+        let score = ffi::search_static_eval(&self.inner, board.inner());
+        debug!(score, "Static evaluation complete");
+        Ok(score)
+    }
+
+    /// Search `board` for a forced mate in at most `max_moves` moves
+    ///
+    /// Deepens one move at a time, so the mate found is the shortest. Each depth
+    /// may play up to `max_nodes` moves; a mate not proven within that budget is
+    /// not reported.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some((moves, first_move)))` - Mate in `moves` moves starting with `first_move`
+    /// - `Ok(None)` - No forced mate found
+    #[instrument(level = "debug", skip(board))]
+    pub fn search_mate(
+        board: &Board,
+        max_moves: u32,
+        max_nodes: u64,
+    ) -> UCIResult<Option<(u32, String)>> {
+        for moves in 1..=max_moves {
+            let first_move = ffi::engine_search_mate(board.inner(), moves, max_nodes);
+            if !first_move.is_empty() {
+                debug!(moves, first_move = %first_move, "Forced mate found");
+                return Ok(Some((moves, first_move)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Search `board` to a fixed `depth` and return the number of nodes visited
+    ///
+    /// The node count depends only on the position and depth, which makes it a
+    /// regression signal for `bench`.
+    #[instrument(level = "debug", skip(board))]
+    pub fn bench_search(board: &Board, depth: u32) -> UCIResult<u64> {
+        let nodes = ffi::engine_bench_search(board.inner(), depth);
+        if nodes == 0 {
+            return Err(UCIError::Search {
+                message: format!("Bench search to depth {} failed", depth),
+            });
+        }
+        Ok(nodes)
+    }
+
+    /// Search `board` with the C++ search engine for at most `max_time`
+    ///
+    /// Works on a copy of the position under the limits set with
+    /// `engine_set_depth_limit` and `engine_set_node_limit`, and blocks until one
+    /// of them is reached or [`SearchEngine::stop`] is called; with no `max_time`
+    /// only those end it. Searches run one at a time; a second caller waits for
+    /// the first.
+    ///
+    /// # Returns
+    ///
+    /// The deepest iteration the search completed; 0 if it completed none
+    #[instrument(level = "debug", skip(board))]
+    pub fn search(board: &Board, max_time: Option<Duration>) -> u32 {
+        let max_time_ms = max_time.map_or(u64::MAX, |max_time| {
+            u64::try_from(max_time.as_millis()).unwrap_or(u64::MAX)
+        });
+        let depth = ffi::engine_search(board.inner(), max_time_ms);
+        debug!(depth, "Search finished");
+        depth
+    }
+
+    /// Ask the running [`SearchEngine::search`], if any, to stop
+    pub fn stop() {
+        ffi::engine_stop_search();
+    }
+
+    /// Deepest ply the most recent search reached, for the UCI `seldepth` field
+    ///
+    /// Covers only the iterations the search completed, so it is at least their
+    /// depth unless every line ended in mate sooner; 0 before any search. Waits
+    /// for a running search to finish, so stop it first.
+    pub fn seldepth() -> u32 {
+        ffi::engine_seldepth()
+    }
+
+    /// Counters of the most recent search, summed over its iterations
+    ///
+    /// Waits for a running search to finish, so stop it first.
+    pub fn last_search_counters() -> SearchCounters {
+        let stats = ffi::engine_get_search_stats();
+        SearchCounters {
+            tt_hits: stats.tt_hits,
+            lmr_reductions: stats.lmr_reductions,
+            null_move_prunes: stats.null_move_prunes,
+            qsearch_nodes: stats.qsearch_nodes,
+        }
+    }
+
+    /// Transposition table occupancy in per-mille for the UCI `hashfull` field
+    ///
+    /// Sampled from the C++ table sized by `Hash`. Only entries written since the
+    /// table was last cleared or reallocated count, so this is 0 right after
+    /// `Clear Hash`, `ucinewgame` or a resize.
+    pub fn hashfull() -> u16 {
+        ffi::engine_hashfull().min(1000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_eval_start_position() {
+        let engine = SearchEngine::new().unwrap();
+        let board = Board::new().unwrap();
+
+        let score = engine.static_eval(&board).unwrap();
+        assert!(
+            score.abs() < 50,
+            "Start position should be level: {}",
+            score
+        );
+    }
+
+    #[test]
+    fn test_static_eval_is_side_to_move_relative() {
+        let engine = SearchEngine::new().unwrap();
+        let mut board = Board::new().unwrap();
+
+        // White is a queen up
+        board
+            .set_from_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1")
+            .unwrap();
+        assert!(engine.static_eval(&board).unwrap() > 500);
+
+        board
+            .set_from_fen("4k3/8/8/8/8/8/8/3QK3 b - - 0 1")
+            .unwrap();
+        assert!(engine.static_eval(&board).unwrap() < -500);
+    }
+
+    #[test]
+    fn test_search_mate_finds_shortest_mate() {
+        let mut board = Board::new().unwrap();
+
+        // Re8+ Rxe8 Rxe8#
+        board
+            .set_from_fen("1r4k1/5ppp/8/8/8/8/4RPPP/4R1K1 w - - 0 1")
+            .unwrap();
+        assert_eq!(SearchEngine::search_mate(&board, 1, 100_000).unwrap(), None);
+        assert_eq!(
+            SearchEngine::search_mate(&board, 3, 100_000).unwrap(),
+            Some((2, "e2e8".to_string()))
+        );
+
+        // A budget too small to prove the mate reports none
+        assert_eq!(SearchEngine::search_mate(&board, 2, 10).unwrap(), None);
+    }
+
+    #[test]
+    fn test_search_mate_ignores_stalemate() {
+        let mut board = Board::new().unwrap();
+
+        // A lone queen cannot mate; Qc7 only stalemates
+        board.set_from_fen("k7/8/1Q6/8/8/8/8/7K w - - 0 1").unwrap();
+        assert_eq!(SearchEngine::search_mate(&board, 1, 100_000).unwrap(), None);
+    }
+
+    #[test]
+    fn test_morphy_style_changes_static_eval() {
+        let engine = SearchEngine::new().unwrap();
+        let mut board = Board::new().unwrap();
+        board
+            .set_from_fen("r1bqk2r/pppp1ppp/2n2n2/2b1p3/2B1P3/5N2/PPPP1PPP/RNBQ1RK1 b kq - 0 1")
+            .unwrap();
+
+        assert!(ffi::engine_set_morphy_style(false));
+        let standard = engine.static_eval(&board).unwrap();
+        assert!(ffi::engine_set_morphy_style(true));
+        let morphy = engine.static_eval(&board).unwrap();
+        assert_ne!(standard, morphy);
+
+        // A pawn down with a lead in development: the threshold caps the credit
+        board
+            .set_from_fen("r1bqkb1r/pppp1ppp/2n5/4p3/2B1n3/5N2/PPPP1PPP/RNBQ1RK1 w kq - 0 1")
+            .unwrap();
+        assert!(ffi::engine_set_sacrifice_threshold(0));
+        let uncredited = engine.static_eval(&board).unwrap();
+        assert!(ffi::engine_set_sacrifice_threshold(100));
+        let credited = engine.static_eval(&board).unwrap();
+        assert!(credited > uncredited);
+
+        assert!(ffi::engine_set_morphy_style(false));
+    }
+
+    #[test]
+    fn test_hashfull_empty_after_clear() {
+        assert!(ffi::engine_clear_hash());
+        assert_eq!(SearchEngine::hashfull(), 0);
+    }
+
+    #[test]
+    fn test_hashfull_empty_after_resize() {
+        assert!(ffi::engine_set_hash_size(1));
+        assert_eq!(SearchEngine::hashfull(), 0);
+
+        // A zero-sized table is refused and the current one kept
+        assert!(!ffi::engine_set_hash_size(0));
+        assert_eq!(SearchEngine::hashfull(), 0);
+        assert!(ffi::engine_set_hash_size(16));
+    }
+
+    #[test]
+    fn test_static_eval_checkmate_and_stalemate() {
+        let engine = SearchEngine::new().unwrap();
+        let mut board = Board::new().unwrap();
+
+        // Fool's mate: white is checkmated
+        board
+            .set_from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3")
+            .unwrap();
+        assert_eq!(engine.static_eval(&board).unwrap(), -MATE_SCORE);
+
+        board
+            .set_from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1")
+            .unwrap();
+        assert_eq!(engine.static_eval(&board).unwrap(), 0);
+    }
+}
@@ -0,0 +1,235 @@
+// Extended Position Description (EPD) parsing
+//
+// EPD is the line format of engine test suites such as WAC and ERET: the first
+// four FEN fields followed by `;`-terminated operations like `bm Qg6;` or
+// `id "WAC.001";`. Move counters are not part of the position and come from the
+// `hmvc`/`fmvn` operations when present.
+
+use std::collections::BTreeMap;
+
+use crate::error::{UCIError, UCIResult};
+
+/// One EPD line: a position and its operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpdRecord {
+    fen: String,
+    operations: BTreeMap<String, Vec<String>>,
+}
+
+impl EpdRecord {
+    /// Parse an EPD line
+    ///
+    /// Quoted operands keep their spaces and semicolons, and the final
+    /// operation may omit its terminating `;`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::epd::EpdRecord;
+    ///
+    /// let record = EpdRecord::parse(
+    ///     "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";",
+    /// )?;
+    /// assert_eq!(record.best_moves(), ["Qg6"]);
+    /// assert_eq!(record.id(), Some("WAC.001"));
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    pub fn parse(line: &str) -> UCIResult<Self> {
+        let line = line.trim();
+        let mut rest = line;
+        let mut fields = Vec::with_capacity(4);
+        while fields.len() < 4 {
+            let trimmed = rest.trim_start();
+            let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+            if end == 0 {
+                return Err(invalid(line, "expected four position fields"));
+            }
+            fields.push(&trimmed[..end]);
+            rest = &trimmed[end..];
+        }
+
+        if fields[0].split('/').count() != 8 {
+            return Err(invalid(line, "piece placement must have eight ranks"));
+        }
+        if !matches!(fields[1], "w" | "b") {
+            return Err(invalid(line, "side to move must be 'w' or 'b'"));
+        }
+
+        let mut operations: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for operation in split_operations(rest, line)? {
+            let mut tokens = operation.into_iter();
+            let Some(opcode) = tokens.next() else {
+                continue;
+            };
+            if !is_valid_opcode(&opcode) {
+                return Err(invalid(line, &format!("bad opcode '{}'", opcode)));
+            }
+            operations.insert(opcode, tokens.collect());
+        }
+
+        let counter = |opcode: &str, default: &str| -> UCIResult<String> {
+            match operations.get(opcode).and_then(|operands| operands.first()) {
+                Some(value) if value.parse::<u32>().is_ok() => Ok(value.clone()),
+                Some(value) => Err(invalid(line, &format!("bad {} '{}'", opcode, value))),
+                None => Ok(default.to_string()),
+            }
+        };
+        let fen = format!(
+            "{} {} {}",
+            fields.join(" "),
+            counter("hmvc", "0")?,
+            counter("fmvn", "1")?
+        );
+
+        Ok(Self { fen, operations })
+    }
+
+    /// Full FEN, with move counters from `hmvc`/`fmvn` or `0 1`
+    pub fn fen(&self) -> &str {
+        &self.fen
+    }
+
+    /// Operands of `opcode`, if the record has that operation
+    pub fn operation(&self, opcode: &str) -> Option<&[String]> {
+        self.operations.get(opcode).map(Vec::as_slice)
+    }
+
+    /// All operations, keyed by opcode
+    pub fn operations(&self) -> &BTreeMap<String, Vec<String>> {
+        &self.operations
+    }
+
+    /// Position identifier (`id`)
+    pub fn id(&self) -> Option<&str> {
+        self.operation("id")
+            .and_then(|operands| operands.first())
+            .map(String::as_str)
+    }
+
+    /// Best moves in SAN (`bm`); empty if the record has none
+    pub fn best_moves(&self) -> &[String] {
+        self.operation("bm").unwrap_or_default()
+    }
+
+    /// Moves to avoid in SAN (`am`); empty if the record has none
+    pub fn avoid_moves(&self) -> &[String] {
+        self.operation("am").unwrap_or_default()
+    }
+}
+
+/// Split the operations part into operations of operand tokens, honouring quotes
+fn split_operations(text: &str, line: &str) -> UCIResult<Vec<Vec<String>>> {
+    let mut operations = Vec::new();
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                // A quoted operand is one token, even when empty
+                let mut quoted = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => quoted.push(c),
+                        None => return Err(invalid(line, "unterminated quoted operand")),
+                    }
+                }
+                tokens.push(quoted);
+            }
+            ';' => {
+                push_token(&mut tokens, &mut token);
+                operations.push(std::mem::take(&mut tokens));
+            }
+            c if c.is_whitespace() => push_token(&mut tokens, &mut token),
+            c => token.push(c),
+        }
+    }
+    push_token(&mut tokens, &mut token);
+    if !tokens.is_empty() {
+        operations.push(tokens);
+    }
+
+    Ok(operations)
+}
+
+fn push_token(tokens: &mut Vec<String>, token: &mut String) {
+    if !token.is_empty() {
+        tokens.push(std::mem::take(token));
+    }
+}
+
+/// Opcodes are a letter followed by up to 14 letters, digits or underscores
+fn is_valid_opcode(opcode: &str) -> bool {
+    opcode.len() <= 15
+        && opcode.starts_with(|c: char| c.is_ascii_alphabetic())
+        && opcode
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn invalid(line: &str, reason: &str) -> UCIError {
+    UCIError::Position {
+        message: format!("Invalid EPD ({}): {}", reason, line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wac_record() {
+        let record = EpdRecord::parse(
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";",
+        )
+        .unwrap();
+
+        assert_eq!(
+            record.fen(),
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1"
+        );
+        assert_eq!(record.best_moves(), ["Qg6"]);
+        assert!(record.avoid_moves().is_empty());
+        assert_eq!(record.id(), Some("WAC.001"));
+    }
+
+    #[test]
+    fn test_parse_multiple_best_moves_and_quoted_id() {
+        let record = EpdRecord::parse(
+            "r1bqk1r1/1p1p1n2/p1n2pN1/2p1b2Q/2P1Pp2/1PN5/PB4PP/R4RK1 w q - \
+             bm Rxf4 Nxe5; id \"ERET 001 - Relief; sac\";",
+        )
+        .unwrap();
+
+        assert_eq!(record.best_moves(), ["Rxf4", "Nxe5"]);
+        assert_eq!(record.id(), Some("ERET 001 - Relief; sac"));
+        assert_eq!(record.operations().len(), 2);
+    }
+
+    #[test]
+    fn test_parse_avoid_move_with_counters_and_no_trailing_semicolon() {
+        let record = EpdRecord::parse(
+            "6k1/5ppp/8/8/8/8/1q3PPP/3Q2K1 b - - am Qxb1+; hmvc 3; fmvn 17; c0 \"back rank\"",
+        )
+        .unwrap();
+
+        assert_eq!(record.fen(), "6k1/5ppp/8/8/8/8/1q3PPP/3Q2K1 b - - 3 17");
+        assert_eq!(record.avoid_moves(), ["Qxb1+"]);
+        assert!(record.best_moves().is_empty());
+        assert_eq!(record.operation("c0"), Some(&["back rank".to_string()][..]));
+        assert_eq!(record.id(), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_records() {
+        assert!(EpdRecord::parse("").is_err());
+        assert!(EpdRecord::parse("8/8/8/8/8/8/8/8 w -").is_err());
+        assert!(EpdRecord::parse("8/8/8/8 w - - bm e4;").is_err());
+        assert!(EpdRecord::parse("8/8/8/8/8/8/8/8 x - - bm e4;").is_err());
+        assert!(EpdRecord::parse("8/8/8/8/8/8/8/8 w - - id \"open;").is_err());
+        assert!(EpdRecord::parse("8/8/8/8/8/8/8/8 w - - 9bm e4;").is_err());
+        assert!(EpdRecord::parse("8/8/8/8/8/8/8/8 w - - fmvn x;").is_err());
+    }
+}
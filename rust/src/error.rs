@@ -15,8 +15,12 @@ use thiserror::Error;
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum UCIError {
     /// Errors related to UCI protocol parsing and validation
-    #[error("UCI protocol error: {message}")]
-    Protocol { message: String },
+    #[error("UCI protocol error: {message}{}", at_token(.token))]
+    Protocol {
+        message: String,
+        /// Offending token, for errors about a single token of the command line
+        token: Option<Box<ErrorToken>>,
+    },
 
     /// Errors from the C++ engine core via FFI
     #[error("Engine error: {message}")]
@@ -59,6 +63,25 @@ pub enum UCIError {
     Internal { message: String },
 }
 
+/// Token of a command line that a protocol error points at
+///
+/// Boxed in `UCIError::Protocol` to keep the error small.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorToken {
+    /// Position in the command line, the command itself being token 0
+    pub index: usize,
+    /// Token text; `None` when the token is missing, e.g. a value after its name
+    pub text: Option<String>,
+}
+
+/// ` at token N` suffix for errors that point at a token
+fn at_token(token: &Option<Box<ErrorToken>>) -> String {
+    token
+        .as_ref()
+        .map(|token| format!(" at token {}", token.index))
+        .unwrap_or_default()
+}
+
 /// Result type alias for UCI operations
 pub type UCIResult<T> = Result<T, UCIError>;
 
@@ -96,11 +119,53 @@ impl ErrorContext {
     }
 }
 
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.operation)?;
+        if !self.details.is_empty() {
+            write!(f, " ({})", self.details.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
 /// Enhanced error with context information
+///
+/// Context added to an already contextual result wraps the existing one, so
+/// `context` is always the outermost operation and `causes` lists the inner
+/// ones down to where the error originated.
 #[derive(Debug)]
 pub struct ContextualError {
     pub error: UCIError,
     pub context: ErrorContext,
+    /// Inner contexts, outermost first
+    pub causes: Vec<ErrorContext>,
+}
+
+impl ContextualError {
+    /// Wrap this error in a further, outer context
+    pub fn wrap(mut self, context: ErrorContext) -> Self {
+        let inner = std::mem::replace(&mut self.context, context);
+        self.causes.insert(0, inner);
+        self
+    }
+
+    /// Every context from outermost to innermost
+    pub fn chain(&self) -> impl Iterator<Item = &ErrorContext> {
+        std::iter::once(&self.context).chain(self.causes.iter())
+    }
+
+    /// Lines describing this error for `info string` output
+    ///
+    /// Always starts with the error itself; `verbose` (debug mode) appends the
+    /// full context chain, outermost to innermost, for bug reports.
+    pub fn report_lines(&self, verbose: bool) -> Vec<String> {
+        let mut lines = vec![format!("ERROR: {}", self.error)];
+        if verbose {
+            lines.extend(self.chain().map(|context| format!("context: {}", context)));
+        }
+        lines
+    }
 }
 
 impl fmt::Display for ContextualError {
@@ -129,7 +194,22 @@ pub trait ResultExt<T> {
 
 impl<T> ResultExt<T> for UCIResult<T> {
     fn with_context(self, context: ErrorContext) -> ContextualResult<T> {
-        self.map_err(|error| ContextualError { error, context })
+        self.map_err(|error| ContextualError {
+            error,
+            context,
+            causes: Vec::new(),
+        })
+    }
+
+    fn with_operation(self, operation: impl Into<String>) -> ContextualResult<T> {
+        let context = ErrorContext::new(operation);
+        self.with_context(context)
+    }
+}
+
+impl<T> ResultExt<T> for ContextualResult<T> {
+    fn with_context(self, context: ErrorContext) -> ContextualResult<T> {
+        self.map_err(|error| error.wrap(context))
     }
 
     fn with_operation(self, operation: impl Into<String>) -> ContextualResult<T> {
@@ -146,6 +226,7 @@ pub mod never_panic {
     pub fn safe_parse<T: std::str::FromStr>(input: &str, operation: &str) -> UCIResult<T> {
         input.parse().map_err(|_| UCIError::Protocol {
             message: format!("Failed to parse {} from '{}'", operation, input),
+            token: None,
         })
     }
 
@@ -178,6 +259,7 @@ pub mod never_panic {
                     "Start index {} exceeds string length {} in {}",
                     start, len, context
                 ),
+                token: None,
             });
         }
 
@@ -188,12 +270,14 @@ pub mod never_panic {
                     "End index {} exceeds string length {} in {}",
                     end, len, context
                 ),
+                token: None,
             });
         }
 
         if start > end {
             return Err(UCIError::Protocol {
                 message: format!("Start index {} > end index {} in {}", start, end, context),
+                token: None,
             });
         }
 
@@ -324,6 +408,7 @@ mod tests {
     fn test_error_creation() {
         let error = UCIError::Protocol {
             message: "Invalid command".to_string(),
+            token: None,
         };
         assert_eq!(error.to_string(), "UCI protocol error: Invalid command");
     }
@@ -400,6 +485,7 @@ mod tests {
 
         let protocol_error = UCIError::Protocol {
             message: "test".to_string(),
+            token: None,
         };
         let strategy = recovery_strategy(&protocol_error);
         assert!(matches!(strategy, RecoveryAction::ContinueWithDefault));
@@ -419,6 +505,7 @@ mod tests {
 
         let error_result: UCIResult<i32> = Err(UCIError::Protocol {
             message: "test error".to_string(),
+            token: None,
         });
         let contextual = error_result.with_operation("test operation");
         assert!(contextual.is_err());
@@ -426,4 +513,41 @@ mod tests {
         let ctx_error = contextual.unwrap_err();
         assert_eq!(ctx_error.context.operation, "test operation");
     }
+
+    #[test]
+    fn test_context_chain_report() {
+        let error_result: UCIResult<()> = Err(UCIError::Position {
+            message: "bad FEN".to_string(),
+        });
+        let contextual = error_result
+            .with_context(ErrorContext::new("Invalid FEN string").detail("8/8 w"))
+            .with_operation("Failed to setup base position")
+            .with_operation("position command");
+
+        let ctx_error = contextual.unwrap_err();
+        assert_eq!(ctx_error.context.operation, "position command");
+        let chain: Vec<_> = ctx_error.chain().map(|c| c.operation.as_str()).collect();
+        assert_eq!(
+            chain,
+            [
+                "position command",
+                "Failed to setup base position",
+                "Invalid FEN string"
+            ]
+        );
+
+        assert_eq!(
+            ctx_error.report_lines(false),
+            ["ERROR: Board position error: bad FEN"]
+        );
+        assert_eq!(
+            ctx_error.report_lines(true),
+            [
+                "ERROR: Board position error: bad FEN",
+                "context: position command",
+                "context: Failed to setup base position",
+                "context: Invalid FEN string (8/8 w)",
+            ]
+        );
+    }
 }
@@ -27,6 +27,28 @@ pub mod ffi {
         pub pv: String,
     }
 
+    /// Node count below a single root move (`go perft` divide output)
+    #[derive(Debug)]
+    pub struct PerftEntry {
+        /// Root move in UCI notation
+        pub uci_move: String,
+        /// Leaf nodes below this move
+        pub nodes: u64,
+    }
+
+    /// Counters of the most recent C++ search, summed over its iterations
+    #[derive(Debug, Default)]
+    pub struct EngineSearchStats {
+        /// Transposition table probes that found the position
+        pub tt_hits: u64,
+        /// Moves searched at reduced depth by late move reductions
+        pub lmr_reductions: u64,
+        /// Subtrees cut off by null move pruning
+        pub null_move_prunes: u64,
+        /// Nodes visited in quiescence search
+        pub qsearch_nodes: u64,
+    }
+
     // C++ side structs and enums
     unsafe extern "C++" {
         include!("UCIBridge.h");
@@ -43,12 +65,38 @@ pub mod ffi {
         fn create_board() -> UniquePtr<Board>;
         fn board_set_fen(board: Pin<&mut Board>, fen: &str) -> bool;
         fn board_make_move(board: Pin<&mut Board>, move_str: &str) -> bool;
+        /// Unmake `move_str`, which must be the last move made on the board, from
+        /// the position `fen_before` it was made in
+        fn board_undo_move(board: Pin<&mut Board>, fen_before: &str, move_str: &str) -> bool;
         fn board_get_fen(board: &Board) -> String;
+        /// ASCII diagram of the board, rank 8 first, with a file legend
+        fn board_to_ascii(board: &Board) -> String;
         fn board_is_valid_move(board: &Board, move_str: &str) -> bool;
         fn board_reset(board: Pin<&mut Board>);
         fn board_is_in_check(board: &Board) -> bool;
         fn board_is_checkmate(board: &Board) -> bool;
         fn board_is_stalemate(board: &Board) -> bool;
+        /// Material on the board in centipawns for both sides, kings excluded
+        fn board_material_count(board: &Board) -> u32;
+        /// Zobrist hash of the current position
+        fn board_position_key(board: &Board) -> u64;
+        /// Occupancy bitboard for one side (bit 0 = a1, bit 63 = h8)
+        fn board_color_bitboard(board: &Board, white: bool) -> u64;
+        /// Piece on a square (0 = a1, 63 = h8): 0-5 white pawn..king,
+        /// 6-11 black pawn..king, 12 when empty
+        fn board_piece_at(board: &Board, square: u8) -> u8;
+        /// Plies since the last capture or pawn move
+        fn board_halfmove_clock(board: &Board) -> u32;
+        /// Move number as it appears in FEN, starting at 1
+        fn board_fullmove_number(board: &Board) -> u32;
+        /// All legal moves for the side to move in UCI notation
+        fn board_legal_moves(board: &Board) -> Vec<String>;
+        /// Leaf node counts per legal root move at the given depth
+        fn board_perft_divide(board: &Board, depth: u32) -> Vec<PerftEntry>;
+        /// SAN for a legal move given in UCI notation (empty if illegal)
+        fn board_move_to_san(board: &Board, move_str: &str) -> String;
+        /// UCI notation for a legal move given in SAN (empty if unmatched)
+        fn board_san_to_uci(board: &Board, san: &str) -> String;
 
         // Search operations - simplified interface
         fn create_search() -> UniquePtr<Search>;
@@ -56,11 +104,55 @@ pub mod ffi {
         fn search_stop(search: Pin<&mut Search>);
         fn search_get_best_move(search: &Search) -> String;
         fn search_is_searching(search: &Search) -> bool;
+        /// Static evaluation in centipawns from the side to move's point of view
+        fn search_static_eval(search: &Search, board: &Board) -> i32;
+        /// First move of a forced mate in at most `moves` moves, proven within
+        /// `max_nodes` moves played; empty if none was found
+        fn engine_search_mate(board: &Board, moves: u32, max_nodes: u64) -> String;
+        /// Nodes visited by a fixed-depth search of `board`, for `bench`
+        fn engine_bench_search(board: &Board, depth: u32) -> u64;
+        /// Search a copy of `board` with the bridge's SearchEngine under the node
+        /// and depth limits set beforehand; blocks until a limit or
+        /// `engine_stop_search` ends it and returns the deepest completed iteration
+        fn engine_search(board: &Board, max_time_ms: u64) -> u32;
+        /// Ask the running `engine_search`, if any, to stop
+        fn engine_stop_search();
+        /// Counters of the most recent `engine_search`; waits for a running one
+        fn engine_get_search_stats() -> EngineSearchStats;
+        /// Deepest ply the completed iterations of the most recent `engine_search`
+        /// reached, extensions and quiescence included; waits for a running one
+        fn engine_seldepth() -> u32;
 
         // Engine configuration
         fn engine_set_hash_size(size_mb: u32) -> bool;
         fn engine_set_threads(thread_count: u32) -> bool;
         fn engine_clear_hash() -> bool;
+        /// Transposition table occupancy in per-mille, 0..=1000
+        fn engine_hashfull() -> u16;
+        /// Draw score offset in centipawns from the engine's side, -200..=200
+        fn engine_set_contempt(contempt_cp: i32) -> bool;
+        /// Touch every page of the transposition table so the first search avoids
+        /// page faults; entries are left as they are
+        fn engine_warmup_hash() -> bool;
+        /// Switch evaluation between the standard and Morphy-style evaluator
+        fn engine_set_morphy_style(enabled: bool) -> bool;
+        /// Most compensation (centipawns) the Morphy-style evaluator credits for
+        /// sacrificed material, 0..=1000
+        fn engine_set_sacrifice_threshold(threshold_cp: i32) -> bool;
+        /// Plies check extensions and quiescence search may add to a line beyond
+        /// the nominal depth of the next search, 0..=8
+        fn engine_set_tactical_depth(plies: u32) -> bool;
+        /// Node budget of the next search; `u64::MAX` searches without one
+        fn engine_set_node_limit(max_nodes: u64) -> bool;
+        /// Depth limit of the next search; 0 searches to the engine's maximum depth
+        fn engine_set_depth_limit(depth: u32) -> bool;
+        /// Root moves the next search tries first, space separated like
+        /// `searchmoves`; empty leaves ordering to the search
+        fn engine_set_root_move_order(moves: &str) -> bool;
+        /// Accept Shredder-FEN castling files (`UCI_Chess960`) in `board_set_fen`
+        fn engine_set_chess960(enabled: bool) -> bool;
+        /// Weaken play to about `elo` (1320..=3190); 0 restores full strength
+        fn engine_set_strength(elo: u32) -> bool;
     }
 
     // Rust functions that C++ can call (callbacks)
@@ -73,6 +165,11 @@ pub mod ffi {
     }
 }
 
+// SAFETY: the C++ Board owns all of its state and has no thread affinity, so it
+// may be moved between threads. It is deliberately not `Sync`; callers that
+// share a board must serialize access behind a lock.
+unsafe impl Send for ffi::Board {}
+
 // Rust implementations of callback functions
 /// Called by C++ engine during search to report progress
 pub fn on_search_progress(info: &ffi::SearchInfo) {
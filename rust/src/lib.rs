@@ -59,30 +59,70 @@
 //! ```
 
 use std::panic;
+use std::sync::{Arc, Weak};
 use tracing::{error, info, warn};
 
 #[cfg(feature = "ffi")]
 pub mod bridge;
+/// EPD test-suite records
+pub mod epd;
 pub mod error;
 #[cfg(feature = "ffi")]
 pub mod ffi;
 pub mod logging;
+/// PGN export of played games
+#[cfg(feature = "ffi")]
+pub mod pgn;
 pub mod runtime;
+/// Search time allocation policies
+pub mod time;
 pub mod uci;
 
-#[cfg(test)]
+/// Test helpers shared by unit and integration tests
 pub mod testing;
 
 // Re-export commonly used types
-pub use error::{ContextualError, ContextualResult, ErrorContext, ResultExt, UCIError, UCIResult};
+pub use error::{
+    ContextualError, ContextualResult, ErrorContext, ErrorToken, ResultExt, UCIError, UCIResult,
+};
 pub use uci::{
     run_uci_event_loop, BasicCommandHandler, BestMoveBuilder, ChessMove, EngineConfig,
     EngineIdentification, EngineState, EngineStatistics, EventLoopConfig, EventLoopStats,
     InfoBuilder, InputSanitizer, NewGameHandler, ParserStats, Position, PositionCommandHandler,
-    ResponseFormatter, SearchContext, StateChangeEvent, TimeControl, UCICommand, UCIEngine,
-    UCIEventLoop, UCIResponse, UCIState, ZeroCopyParser,
+    ResponseFormatter, ScoreBound, ScoreKind, SearchContext, StateChangeEvent, StopReason,
+    TimeControl, UCICommand, UCIEngine, UCIEventLoop, UCIResponse, UCIState, ZeroCopyParser,
 };
 
+/// Command log of the most recently created engine, dumped by the panic hook
+static PANIC_COMMAND_LOG: parking_lot::Mutex<Option<Weak<uci::engine::CommandLog>>> =
+    parking_lot::const_mutex(None);
+
+/// Make `log` the command history the panic hook reports
+pub(crate) fn register_command_log(log: &Arc<uci::engine::CommandLog>) {
+    *PANIC_COMMAND_LOG.lock() = Some(Arc::downgrade(log));
+}
+
+/// Log the registered engine's last commands, oldest first
+///
+/// Only tries the locks: the panicking thread may be the one holding them.
+fn dump_command_log() {
+    let Some(log) = PANIC_COMMAND_LOG
+        .try_lock()
+        .and_then(|registered| registered.as_ref().and_then(Weak::upgrade))
+    else {
+        return;
+    };
+    let Some(commands) = log.try_lock() else {
+        error!("Recent commands unavailable: command log is locked");
+        return;
+    };
+
+    error!("Last {} commands before the panic:", commands.len());
+    for command in commands.iter() {
+        error!("  {}", command);
+    }
+}
+
 /// Global panic hook setup for never-panic operation
 ///
 /// This function installs a custom panic hook that ensures the UCI engine
@@ -108,6 +148,7 @@ pub fn setup_panic_hook() {
         } else {
             error!("Panic message: <unknown>");
         }
+        dump_command_log();
 
         // Print UCI error message to stdout for GUI
         println!("info string CRITICAL ERROR: Engine panic - shutting down gracefully");
@@ -251,9 +292,14 @@ pub mod uci_options {
     pub const OPTIONS: &[(&str, &str, &str)] = &[
         ("Hash", "spin", "128"),
         ("Threads", "spin", "1"),
+        ("MultiPV", "spin", "1"),
         ("MorphyStyle", "check", "false"),
         ("SacrificeThreshold", "spin", "100"),
         ("TacticalDepth", "spin", "2"),
+        ("Contempt", "spin", "0"),
+        ("ResignThreshold", "spin", "0"),
+        ("DrawThreshold", "spin", "10"),
+        ("DrawMoveCount", "spin", "0"),
     ];
 }
 
@@ -270,7 +316,7 @@ mod tests {
 
     #[test]
     fn test_uci_options() {
-        assert_eq!(uci_options::OPTIONS.len(), 5);
+        assert_eq!(uci_options::OPTIONS.len(), 10);
 
         // Check that Hash option exists
         let hash_option = uci_options::OPTIONS
@@ -321,6 +367,7 @@ mod tests {
         let errors = vec![
             UCIError::Protocol {
                 message: "test".to_string(),
+                token: None,
             },
             UCIError::Engine {
                 message: "test".to_string(),
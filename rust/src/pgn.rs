@@ -0,0 +1,413 @@
+// Portable Game Notation (PGN) export and import
+//
+// Turns the game line tracked by `PositionCommandHandler` (a starting FEN plus
+// UCI moves) into a PGN game with SAN movetext, so played games can be saved,
+// and reads the main line of a PGN game back into UCI moves.
+
+use crate::bridge::Board;
+use crate::error::{UCIError, UCIResult};
+use crate::uci::PositionCommandHandler;
+
+/// Movetext lines are wrapped to stay below this many characters
+const MAX_LINE_LEN: usize = 79;
+
+/// Builds a PGN game from a starting position and UCI moves
+///
+/// Tags default to the seven tag roster with unknown values (`?`). `SetUp` and
+/// `FEN` tags are added when the game did not start from the initial position.
+#[derive(Debug, Clone)]
+pub struct PgnWriter {
+    tags: Vec<(String, String)>,
+    starting_fen: Option<String>,
+    moves: Vec<String>,
+}
+
+impl PgnWriter {
+    /// Start a game from `starting_fen` (`None` for the initial position)
+    pub fn new(starting_fen: Option<&str>, moves: &[String]) -> Self {
+        let tags = [
+            ("Event", "?"),
+            ("Site", "?"),
+            ("Date", "????.??.??"),
+            ("Round", "?"),
+            ("White", "?"),
+            ("Black", "?"),
+            ("Result", "*"),
+        ]
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+
+        Self {
+            tags,
+            starting_fen: starting_fen.map(str::to_string),
+            moves: moves.to_vec(),
+        }
+    }
+
+    /// The game line of the last `position` command
+    pub fn from_handler(handler: &PositionCommandHandler) -> Self {
+        Self::new(handler.starting_fen(), handler.get_move_history())
+    }
+
+    /// Set a tag, replacing any earlier value; `Result` also ends the movetext
+    pub fn tag(mut self, name: &str, value: &str) -> Self {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, existing)) => *existing = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+        self
+    }
+
+    /// Render the game as PGN text
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(String)` - Tag pairs, a blank line and the wrapped movetext
+    /// - `Err(UCIError::Position)` - The starting FEN is invalid
+    /// - `Err(UCIError::Move)` - A move is illegal where it is played
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::pgn::PgnWriter;
+    ///
+    /// let moves = ["e2e4".to_string(), "e7e5".to_string()];
+    /// let pgn = PgnWriter::new(None, &moves).write()?;
+    /// assert!(pgn.ends_with("\n1. e4 e5 *\n"));
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    pub fn write(&self) -> UCIResult<String> {
+        let mut board = Board::new()?;
+        if let Some(fen) = &self.starting_fen {
+            board.set_from_fen(fen)?;
+        }
+        let start = board.get_fen()?;
+        let mut fields = start.split_whitespace().skip(1);
+        let mut white_to_move = fields.next() != Some("b");
+        let mut move_number: u32 = fields.nth(3).and_then(|n| n.parse().ok()).unwrap_or(1);
+
+        let mut tokens = Vec::with_capacity(self.moves.len() * 3 / 2 + 1);
+        for (index, uci_move) in self.moves.iter().enumerate() {
+            if white_to_move {
+                tokens.push(format!("{}.", move_number));
+            } else if index == 0 {
+                tokens.push(format!("{}...", move_number));
+            }
+
+            tokens.push(board.uci_to_san(uci_move).map_err(|e| UCIError::Move {
+                message: format!("Cannot export move {} ({}): {}", index + 1, uci_move, e),
+            })?);
+            board.make_move(uci_move)?;
+
+            if !white_to_move {
+                move_number += 1;
+            }
+            white_to_move = !white_to_move;
+        }
+        tokens.push(self.result().to_string());
+
+        let mut pgn = String::new();
+        for (name, value) in &self.tags {
+            pgn.push_str(&tag_pair(name, value));
+        }
+        if self.starting_fen.is_some() {
+            pgn.push_str(&tag_pair("SetUp", "1"));
+            pgn.push_str(&tag_pair("FEN", &start));
+        }
+        pgn.push('\n');
+        pgn.push_str(&wrap(&tokens));
+        Ok(pgn)
+    }
+
+    fn result(&self) -> &str {
+        self.tags
+            .iter()
+            .find(|(name, _)| name == "Result")
+            .map_or("*", |(_, value)| value.as_str())
+    }
+}
+
+/// Reads the main line of a PGN game as UCI moves
+pub struct PgnReader;
+
+impl PgnReader {
+    /// Convert the movetext of `pgn` to UCI moves
+    ///
+    /// Move numbers, `{}` and `;` comments, variations, NAGs and the result are
+    /// skipped. Moves are replayed from the `FEN` tag if there is one, otherwise
+    /// from the initial position.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<String>)` - Main line moves in UCI notation
+    /// - `Err(UCIError::Move)` - A SAN move is illegal or ambiguous where it is played
+    /// - `Err(UCIError::Position)` - The `FEN` tag or the PGN text is malformed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opera_uci::pgn::PgnReader;
+    ///
+    /// let moves = PgnReader::parse_moves("1. e4 {best by test} e5 2. Nf3 $1 *")?;
+    /// assert_eq!(moves, ["e2e4", "e7e5", "g1f3"]);
+    /// # Ok::<(), opera_uci::UCIError>(())
+    /// ```
+    pub fn parse_moves(pgn: &str) -> UCIResult<Vec<String>> {
+        let mut board = Board::new()?;
+        if let Some(fen) = Self::starting_fen(pgn)? {
+            board.set_from_fen(&fen)?;
+        }
+
+        let mut moves = Vec::new();
+        for token in movetext_tokens(pgn)? {
+            let uci_move = board.san_to_uci(&token).map_err(|e| UCIError::Move {
+                message: format!("Cannot import move {} ({}): {}", moves.len() + 1, token, e),
+            })?;
+            board.make_move(&uci_move)?;
+            moves.push(uci_move);
+        }
+        Ok(moves)
+    }
+
+    /// Value of the `FEN` tag, if the game does not start from the initial position
+    pub fn starting_fen(pgn: &str) -> UCIResult<Option<String>> {
+        for line in pgn.lines().map(str::trim) {
+            let Some(tag) = line.strip_prefix("[FEN ") else {
+                continue;
+            };
+            return match tag
+                .trim()
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix("\"]"))
+            {
+                Some(fen) => Ok(Some(fen.to_string())),
+                None => Err(UCIError::Position {
+                    message: format!("Malformed FEN tag: {}", line),
+                }),
+            };
+        }
+        Ok(None)
+    }
+}
+
+/// SAN tokens of the main line
+fn movetext_tokens(pgn: &str) -> UCIResult<Vec<String>> {
+    let unterminated = |what: &str| UCIError::Position {
+        message: format!("Unterminated {} in PGN", what),
+    };
+
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut chars = pgn.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                chars
+                    .by_ref()
+                    .find(|&c| c == '}')
+                    .ok_or_else(|| unterminated("comment"))?;
+            }
+            ';' => {
+                chars.by_ref().find(|&c| c == '\n');
+            }
+            '[' => {
+                chars
+                    .by_ref()
+                    .find(|&c| c == ']')
+                    .ok_or_else(|| unterminated("tag"))?;
+            }
+            '(' => {
+                // Variations may nest
+                let mut depth = 1;
+                while depth > 0 {
+                    match chars.next().ok_or_else(|| unterminated("variation"))? {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
+                }
+            }
+            c if c.is_whitespace() => push_san(&mut tokens, &mut token),
+            c => token.push(c),
+        }
+    }
+    push_san(&mut tokens, &mut token);
+    Ok(tokens)
+}
+
+/// Keep `token` if it is a move, dropping move numbers, NAGs and results
+fn push_san(tokens: &mut Vec<String>, token: &mut String) {
+    let text = std::mem::take(token);
+
+    // `12.`, `12...` and `12.e4` all number a move
+    let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let san = if digits > 0 && text[digits..].starts_with('.') {
+        text[digits..].trim_start_matches('.')
+    } else {
+        text.as_str()
+    };
+
+    if san.is_empty() || san.starts_with('$') || matches!(san, "1-0" | "0-1" | "1/2-1/2" | "*") {
+        return;
+    }
+    tokens.push(san.to_string());
+}
+
+fn tag_pair(name: &str, value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("[{} \"{}\"]\n", name, escaped)
+}
+
+/// Join tokens with spaces, breaking lines before they exceed `MAX_LINE_LEN`
+fn wrap(tokens: &[String]) -> String {
+    let mut text = String::new();
+    let mut line_len = 0;
+    for token in tokens {
+        if line_len > 0 && line_len + 1 + token.len() > MAX_LINE_LEN {
+            text.push('\n');
+            line_len = 0;
+        } else if line_len > 0 {
+            text.push(' ');
+            line_len += 1;
+        }
+        text.push_str(token);
+        line_len += token.len();
+    }
+    text.push('\n');
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uci::{ChessMove, Position, UCICommand};
+
+    fn uci_moves(moves: &[&str]) -> Vec<String> {
+        moves.iter().map(|mv| mv.to_string()).collect()
+    }
+
+    #[test]
+    fn test_export_from_startpos() {
+        let pgn = PgnWriter::new(None, &uci_moves(&["e2e4", "e7e5", "g1f3", "b8c6"]))
+            .write()
+            .unwrap();
+
+        assert_eq!(
+            pgn,
+            "[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n\
+             [White \"?\"]\n[Black \"?\"]\n[Result \"*\"]\n\n1. e4 e5 2. Nf3 Nc6 *\n"
+        );
+    }
+
+    #[test]
+    fn test_export_from_handler_with_fen_and_tags() {
+        let mut handler = PositionCommandHandler::new().unwrap();
+        let fen = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3";
+        handler
+            .handle_position_command(&UCICommand::Position {
+                position: Position::Fen(fen.to_string()),
+                moves: vec![
+                    ChessMove::new("f1b5").unwrap(),
+                    ChessMove::new("a7a6").unwrap(),
+                ],
+            })
+            .unwrap();
+
+        let pgn = PgnWriter::from_handler(&handler)
+            .tag("White", "Opera \"dev\"")
+            .tag("Result", "1/2-1/2")
+            .write()
+            .unwrap();
+
+        assert!(pgn.contains("[White \"Opera \\\"dev\\\"\"]\n"), "{}", pgn);
+        assert!(
+            pgn.contains("[Result \"1/2-1/2\"]\n[SetUp \"1\"]\n"),
+            "{}",
+            pgn
+        );
+        assert!(pgn.contains(&format!("[FEN \"{}\"]\n", fen)), "{}", pgn);
+        assert!(pgn.ends_with("\n\n3. Bb5 a6 1/2-1/2\n"), "{}", pgn);
+    }
+
+    #[test]
+    fn test_export_black_to_move_and_wrapping() {
+        let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1";
+        let shuffle = ["g1f3", "g8f6", "f3g1", "f6g8"];
+        let mut moves = vec!["e7e5".to_string()];
+        moves.extend(shuffle.iter().cycle().take(40).map(|mv| mv.to_string()));
+
+        let pgn = PgnWriter::new(Some(fen), &moves).write().unwrap();
+        let movetext = pgn.split("\n\n").nth(1).unwrap();
+
+        assert!(
+            movetext.starts_with("1... e5 2. Nf3 Nf6 3. Ng1 Ng8"),
+            "{}",
+            movetext
+        );
+        assert!(movetext.lines().count() > 1);
+        assert!(movetext.lines().all(|line| line.len() <= MAX_LINE_LEN));
+    }
+
+    #[test]
+    fn test_import_ten_move_game() {
+        let pgn = "[Event \"Ruy Lopez\"]\n[Result \"*\"]\n\n\
+                   1. e4 e5 2. Nf3 Nc6 3. Bb5 a6 {Morphy} 4. Ba4 Nf6 5. O-O Be7\n\
+                   6. Re1 b5 7. Bb3 d6 8. c3 O-O 9. h3 $1 Nb8 (9... Na5 10. Bc2) \
+                   10. d4 Nbd7 *\n";
+
+        let moves = PgnReader::parse_moves(pgn).unwrap();
+        assert_eq!(moves.len(), 20);
+        assert_eq!(moves[8], "e1g1");
+        assert_eq!(moves[15], "e8g8");
+        assert_eq!(moves[19], "b8d7");
+
+        let mut handler = PositionCommandHandler::new().unwrap();
+        handler.load_pgn(pgn).unwrap();
+        let fen = handler.get_current_position().unwrap();
+        assert!(
+            fen.starts_with(
+                "r1bq1rk1/2pnbppp/p2p1n2/1p2p3/3PP3/1BP2N1P/PP3PP1/RNBQR1K1 w - - 1 11"
+            ),
+            "{}",
+            fen
+        );
+        assert_eq!(handler.get_move_history(), moves.as_slice());
+    }
+
+    #[test]
+    fn test_import_promotion_from_fen_tag() {
+        let pgn = "[SetUp \"1\"]\n[FEN \"k7/4P3/8/8/8/8/8/K7 w - - 0 1\"]\n\n\
+                   1. e8=Q+ Kb7 2. Qd7+ ; checks\n1-0";
+
+        assert_eq!(
+            PgnReader::parse_moves(pgn).unwrap(),
+            ["e7e8q", "a8b7", "e8d7"]
+        );
+
+        let mut handler = PositionCommandHandler::new().unwrap();
+        handler.load_pgn(pgn).unwrap();
+        assert_eq!(
+            handler.starting_fen(),
+            Some("k7/4P3/8/8/8/8/8/K7 w - - 0 1")
+        );
+    }
+
+    #[test]
+    fn test_import_rejects_bad_movetext() {
+        assert!(matches!(
+            PgnReader::parse_moves("1. e4 e4"),
+            Err(UCIError::Move { .. })
+        ));
+        assert!(PgnReader::parse_moves("1. e4 {unclosed").is_err());
+        assert!(PgnReader::parse_moves("1. e4 (1. d4").is_err());
+    }
+
+    #[test]
+    fn test_export_rejects_illegal_move() {
+        let error = PgnWriter::new(None, &uci_moves(&["e2e4", "e2e4"]))
+            .write()
+            .unwrap_err();
+        assert!(matches!(error, UCIError::Move { .. }), "{:?}", error);
+    }
+}
@@ -285,6 +285,26 @@ pub mod async_utils {
         }))
     }
 
+    /// Create a resource with `factory`, retrying transient failures with backoff
+    ///
+    /// The factory is tried up to `max_retries + 1` times; the last error is
+    /// returned if none succeeds.
+    pub async fn create_with_retry<F, T>(
+        mut factory: F,
+        max_retries: usize,
+        initial_delay_ms: u64,
+    ) -> UCIResult<T>
+    where
+        F: FnMut() -> UCIResult<T>,
+    {
+        retry_with_backoff(
+            || std::future::ready(factory()),
+            max_retries,
+            initial_delay_ms,
+        )
+        .await
+    }
+
     /// Task coordinator for managing multiple async operations
     pub struct TaskCoordinator {
         tasks: Vec<tokio::task::JoinHandle<UCIResult<()>>>,
@@ -576,6 +596,41 @@ mod tests {
         assert!(elapsed >= Duration::from_millis(10));
     }
 
+    #[tokio::test]
+    async fn test_create_with_retry_recovers_on_third_try() {
+        let mut attempts = 0;
+        let factory = || {
+            attempts += 1;
+            if attempts < 3 {
+                Err(UCIError::Ffi {
+                    message: format!("allocation failed on attempt {}", attempts),
+                })
+            } else {
+                Ok(attempts)
+            }
+        };
+
+        let created = async_utils::create_with_retry(factory, 2, 1).await.unwrap();
+        assert_eq!(created, 3);
+    }
+
+    #[tokio::test]
+    async fn test_create_with_retry_returns_last_error() {
+        let mut attempts = 0;
+        let factory = || -> UCIResult<()> {
+            attempts += 1;
+            Err(UCIError::Ffi {
+                message: format!("allocation failed on attempt {}", attempts),
+            })
+        };
+
+        let result = async_utils::create_with_retry(factory, 1, 1).await;
+        assert!(matches!(
+            result,
+            Err(UCIError::Ffi { message }) if message.ends_with("attempt 2")
+        ));
+    }
+
     #[tokio::test]
     async fn test_with_timeout_success() {
         let result =
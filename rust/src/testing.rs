@@ -1,12 +1,15 @@
 // Testing utilities and async test framework for Opera UCI Engine
 //
 // This module provides testing infrastructure optimized for async operations
-// and UCI protocol testing with comprehensive mocking and assertion utilities.
+// and UCI protocol testing with comprehensive mocking and assertion utilities,
+// plus a perft harness that checks move generation against reference counts.
 
 use std::time::{Duration, Instant};
 use tokio::time::timeout;
 use tracing::{error, info};
 
+#[cfg(feature = "ffi")]
+use crate::bridge::Board;
 use crate::error::{UCIError, UCIResult};
 use crate::logging::LoggingConfig;
 
@@ -245,6 +248,76 @@ impl Default for PerformanceMeasurer {
     }
 }
 
+/// Perft count of one suite position that differs from its reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerftResult {
+    /// Position the count was taken from
+    pub fen: String,
+    /// Plies expanded
+    pub depth: u32,
+    /// Reference leaf node count
+    pub expected: u64,
+    /// Leaf nodes the move generator produced; 0 if the FEN did not load
+    pub actual: u64,
+    /// Leaf nodes below each root move in UCI notation, to find the move the
+    /// generator gets wrong
+    pub divide: Vec<(String, u64)>,
+}
+
+/// Run perft on every `(fen, depth, expected)` entry and return the mismatches
+///
+/// An empty result means move generation matches every reference count. A FEN
+/// the board rejects is reported as a mismatch with no nodes.
+///
+/// # Examples
+///
+/// ```
+/// use opera_uci::testing::run_perft_suite;
+///
+/// let startpos = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+/// assert!(run_perft_suite(&[(startpos, 2, 400)]).is_empty());
+///
+/// let mismatches = run_perft_suite(&[(startpos, 1, 21)]);
+/// assert_eq!(mismatches[0].actual, 20);
+/// assert_eq!(mismatches[0].divide.len(), 20);
+/// ```
+#[cfg(feature = "ffi")]
+pub fn run_perft_suite(suite: &[(&str, u32, u64)]) -> Vec<PerftResult> {
+    suite
+        .iter()
+        .filter_map(|&(fen, depth, expected)| {
+            let board = Board::new().and_then(|mut board| {
+                board.set_from_fen(fen)?;
+                Ok(board)
+            });
+            let (actual, divide) = match board {
+                Ok(board) => {
+                    let actual = board.perft(depth).unwrap_or(0);
+                    // Only a mismatch is worth the second pass
+                    let divide = if actual == expected {
+                        Vec::new()
+                    } else {
+                        board.perft_divide(depth).unwrap_or_default()
+                    };
+                    (actual, divide)
+                }
+                Err(e) => {
+                    error!(fen, error = %e, "Perft position failed to load");
+                    (0, Vec::new())
+                }
+            };
+
+            (actual != expected).then(|| PerftResult {
+                fen: fen.to_string(),
+                depth,
+                expected,
+                actual,
+                divide,
+            })
+        })
+        .collect()
+}
+
 /// Assertion utilities for UCI testing
 pub mod assertions {
     use super::*;
@@ -267,6 +340,7 @@ pub mod assertions {
         if response.is_empty() {
             return Err(UCIError::Protocol {
                 message: "Empty UCI response".to_string(),
+                token: None,
             });
         }
 
@@ -279,6 +353,7 @@ pub mod assertions {
         {
             return Err(UCIError::Protocol {
                 message: format!("Invalid UCI response format: '{}'", response),
+                token: None,
             });
         }
 
@@ -331,6 +406,7 @@ macro_rules! async_test {
     };
 }
 
+/// Like `async_test!`, with test logging initialized first
 #[macro_export]
 macro_rules! async_test_with_logging {
     ($name:ident, $body:block) => {
@@ -347,6 +423,7 @@ macro_rules! async_test_with_logging {
     };
 }
 
+/// Async test that fails if its body runs longer than the given duration
 #[macro_export]
 macro_rules! benchmark_test {
     ($name:ident, $max_duration:expr, $body:block) => {
@@ -440,6 +517,7 @@ mod tests {
         // Test error type assertion
         let error = UCIError::Protocol {
             message: "test".to_string(),
+            token: None,
         };
         assert_error_type(&error, "Protocol")?;
 
@@ -0,0 +1,423 @@
+// Time Management for UCI Search
+//
+// This module turns the clock information from a `go` command into concrete
+// search time limits. Allocation strategies are pluggable through the
+// `TimePolicy` trait so the engine can swap policies without touching the
+// command handlers.
+
+use std::fmt::{self, Debug};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[cfg(feature = "ffi")]
+use crate::bridge::Board;
+use crate::error::UCIError;
+#[cfg(feature = "ffi")]
+use crate::error::UCIResult;
+use crate::uci::commands::TimeControl;
+
+/// Built-in time allocation policies
+pub mod policies;
+/// Per-search clock checked against the time limits
+pub mod timer;
+
+pub use policies::{
+    AdaptiveTimePolicy, ByoyomiTimePolicy, FixedTimePolicy, InfiniteTimePolicy,
+    NodeLimitedTimePolicy, StandardTimePolicy,
+};
+pub use timer::{NodeLimit, SearchTimer};
+
+/// Side to move, used to pick the right clock out of `wtime`/`btime`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Side {
+    /// White to move (`wtime`/`winc`)
+    #[default]
+    White,
+    /// Black to move (`btime`/`binc`)
+    Black,
+}
+
+/// Non-king material (centipawns, both sides) at or above which early moves count as opening
+const OPENING_MATERIAL: u32 = 7000;
+
+/// Last full move that can still be considered part of the opening
+const OPENING_MAX_MOVE: u32 = 15;
+
+/// Non-king material (centipawns, both sides) at or below which the game is an endgame
+const ENDGAME_MATERIAL: u32 = 2600;
+
+/// Position facts a time policy needs beyond the `go` parameters
+#[derive(Debug, Clone, Default)]
+pub struct PositionInfo {
+    /// Side whose clock is running
+    pub side_to_move: Side,
+    /// Number of legal moves for the side to move (0 when unknown)
+    pub legal_moves: usize,
+    /// Full move number from the FEN (0 when unknown)
+    pub move_number: u32,
+    /// Early in the game with nearly all material still on the board
+    pub is_opening: bool,
+    /// Little material left on the board
+    pub is_endgame: bool,
+}
+
+impl PositionInfo {
+    /// Extract side to move and move number from a FEN string
+    ///
+    /// Malformed fields default to White and move 0. Phase and mobility need
+    /// the board, see `from_board`.
+    pub fn from_fen(fen: &str) -> Self {
+        let mut fields = fen.split_whitespace().skip(1);
+        let side_to_move = match fields.next() {
+            Some("b") => Side::Black,
+            _ => Side::White,
+        };
+        let move_number = fields.nth(3).and_then(|n| n.parse().ok()).unwrap_or(0);
+
+        Self {
+            side_to_move,
+            move_number,
+            ..Self::default()
+        }
+    }
+
+    /// Derive all position facts from the current board
+    #[cfg(feature = "ffi")]
+    pub fn from_board(board: &Board) -> UCIResult<Self> {
+        let mut info = Self::from_fen(&board.get_fen()?);
+        let material = board.material_count()?;
+
+        info.move_number = board.fullmove_number()?;
+        info.legal_moves = board.legal_moves()?.len();
+        info.is_opening = material >= OPENING_MATERIAL && info.move_number <= OPENING_MAX_MOVE;
+        info.is_endgame = material <= ENDGAME_MATERIAL;
+        Ok(info)
+    }
+}
+
+/// Time budget for a single search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeLimits {
+    /// Target time; the search should not start a new iteration past this point
+    pub soft_limit: Duration,
+    /// Absolute limit; the search must stop once this is reached
+    pub hard_limit: Duration,
+}
+
+impl TimeLimits {
+    /// No time limit (`go infinite`, `go depth N`, pondering)
+    pub fn infinite() -> Self {
+        Self {
+            soft_limit: Duration::MAX,
+            hard_limit: Duration::MAX,
+        }
+    }
+
+    /// Whether these limits never expire
+    pub fn is_infinite(&self) -> bool {
+        self.hard_limit == Duration::MAX
+    }
+}
+
+/// Snapshot of a running search, checked between iterations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchProgress {
+    /// Time spent searching so far
+    pub elapsed: Duration,
+    /// Last fully completed iteration depth
+    pub depth: u32,
+    /// Nodes searched so far
+    pub nodes: u64,
+    /// Consecutive iterations that kept the same best move
+    pub best_move_stability: u32,
+    /// Best score of the last completed iteration, in centipawns
+    pub score: i32,
+    /// Best score of the iteration before it, in centipawns
+    pub prev_score: i32,
+}
+
+/// Strategy for converting `go` clock parameters into search time limits
+pub trait TimePolicy: Debug + Send + Sync {
+    /// Compute soft and hard limits for the side to move
+    fn calculate_time_limit(
+        &self,
+        search_params: &SearchParams,
+        position_info: &PositionInfo,
+    ) -> TimeLimits;
+
+    /// Whether to end the search before the limits expire
+    ///
+    /// Called between iterations; the default never stops early.
+    fn should_stop_early(&self, _progress: &SearchProgress, _limits: &TimeLimits) -> bool {
+        false
+    }
+
+    /// Short name reported in debug output
+    fn name(&self) -> &str {
+        "Custom"
+    }
+}
+
+/// Time policy selected by the `TimePolicy` option
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimePolicyKind {
+    /// Use the engine's configured policy (`StandardTimePolicy` unless replaced)
+    #[default]
+    Auto,
+    /// Force `StandardTimePolicy`
+    Standard,
+    /// Force `FixedTimePolicy`
+    Fixed,
+    /// Force `InfiniteTimePolicy`; every search runs until `stop`
+    Infinite,
+}
+
+impl TimePolicyKind {
+    /// Every kind, in the order the option lists them
+    pub const ALL: [TimePolicyKind; 4] = [
+        TimePolicyKind::Auto,
+        TimePolicyKind::Standard,
+        TimePolicyKind::Fixed,
+        TimePolicyKind::Infinite,
+    ];
+
+    /// The policy this kind forces, or `None` for `Auto`
+    pub fn forced_policy(&self) -> Option<Box<dyn TimePolicy>> {
+        match self {
+            TimePolicyKind::Auto => None,
+            TimePolicyKind::Standard => Some(Box::new(StandardTimePolicy::default())),
+            TimePolicyKind::Fixed => Some(Box::new(FixedTimePolicy::default())),
+            TimePolicyKind::Infinite => Some(Box::new(InfiniteTimePolicy)),
+        }
+    }
+}
+
+impl fmt::Display for TimePolicyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TimePolicyKind::Auto => "Auto",
+            TimePolicyKind::Standard => "Standard",
+            TimePolicyKind::Fixed => "Fixed",
+            TimePolicyKind::Infinite => "Infinite",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for TimePolicyKind {
+    type Err = UCIError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UCIError::Configuration {
+                message: format!("Unknown time policy: {}", s),
+            })
+    }
+}
+
+/// Search limits from a `go` command, detached from the command line
+///
+/// This is what time policies consume; the parser's `TimeControl` borrows from
+/// the input line and carries fields (like `searchmoves`) policies never need.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchParams {
+    /// White's remaining clock in milliseconds (`wtime`)
+    pub wtime: Option<u64>,
+    /// Black's remaining clock in milliseconds (`btime`)
+    pub btime: Option<u64>,
+    /// White's increment per move in milliseconds (`winc`)
+    pub winc: Option<u64>,
+    /// Black's increment per move in milliseconds (`binc`)
+    pub binc: Option<u64>,
+    /// Moves until the next time control (`movestogo`)
+    pub movestogo: Option<u32>,
+    /// Exact time for this move in milliseconds (`movetime`)
+    pub movetime: Option<u64>,
+    /// Maximum depth (`depth`)
+    pub depth: Option<u32>,
+    /// Maximum nodes (`nodes`)
+    pub nodes: Option<u64>,
+    /// Search for a mate in this many moves (`mate`)
+    pub mate: Option<u32>,
+    /// Search until `stop` (`infinite`)
+    pub infinite: bool,
+    /// Search on the opponent's time until `ponderhit` or `stop` (`ponder`)
+    pub ponder: bool,
+    /// Length of one byoyomi period in milliseconds, for Japanese time controls
+    pub byoyomi: Option<u64>,
+    /// Byoyomi periods left for the side to move
+    pub periods: Option<u32>,
+    /// Milliseconds held back from every budget for transmission lag (`MoveOverhead`)
+    pub move_overhead_ms: u64,
+}
+
+impl SearchParams {
+    /// Time budget for these parameters under `policy`
+    ///
+    /// Infinite and ponder searches never get a clock budget.
+    pub fn time_limits(&self, position_info: &PositionInfo, policy: &dyn TimePolicy) -> TimeLimits {
+        if self.infinite || self.ponder {
+            TimeLimits::infinite()
+        } else {
+            policy.calculate_time_limit(self, position_info)
+        }
+    }
+}
+
+impl From<&TimeControl<'_>> for SearchParams {
+    fn from(time_control: &TimeControl<'_>) -> Self {
+        let mut params = Self {
+            wtime: time_control.white_time_ms,
+            btime: time_control.black_time_ms,
+            winc: time_control.white_increment_ms,
+            binc: time_control.black_increment_ms,
+            movestogo: time_control.moves_to_go,
+            movetime: time_control.move_time_ms,
+            depth: time_control.depth,
+            nodes: time_control.nodes,
+            mate: time_control.mate,
+            infinite: time_control.infinite,
+            ponder: time_control.ponder,
+            // UCI `go` has no byoyomi fields; only policies configured for it set them
+            byoyomi: None,
+            periods: None,
+            // Engine configuration, not part of the command
+            move_overhead_ms: 0,
+        };
+
+        // `infinite` overrides any clock sent alongside it
+        if params.infinite {
+            params.wtime = None;
+            params.btime = None;
+            params.winc = None;
+            params.binc = None;
+            params.movestogo = None;
+            params.movetime = None;
+        }
+
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_policy_kind_from_str() {
+        assert_eq!(
+            "auto".parse::<TimePolicyKind>().unwrap(),
+            TimePolicyKind::Auto
+        );
+        assert_eq!(
+            "Infinite".parse::<TimePolicyKind>().unwrap(),
+            TimePolicyKind::Infinite
+        );
+        assert!("Sometimes".parse::<TimePolicyKind>().is_err());
+
+        assert!(TimePolicyKind::Auto.forced_policy().is_none());
+        assert_eq!(
+            TimePolicyKind::Fixed.forced_policy().unwrap().name(),
+            "Fixed"
+        );
+    }
+
+    #[test]
+    fn test_position_info_from_fen_move_number() {
+        let info = PositionInfo::from_fen("8/8/8/4k3/8/8/4P3/4K3 b - - 3 42");
+        assert_eq!(info.side_to_move, Side::Black);
+        assert_eq!(info.move_number, 42);
+        assert_eq!(PositionInfo::from_fen("garbage").move_number, 0);
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_position_info_from_board() {
+        let mut board = Board::new().unwrap();
+        let info = PositionInfo::from_board(&board).unwrap();
+        assert_eq!(info.legal_moves, 20);
+        assert_eq!(info.move_number, 1);
+        assert!(info.is_opening);
+        assert!(!info.is_endgame);
+
+        // King and pawn versus king
+        board
+            .set_from_fen("8/8/8/4k3/8/8/4P3/4K3 w - - 0 60")
+            .unwrap();
+        let info = PositionInfo::from_board(&board).unwrap();
+        assert_eq!(info.move_number, 60);
+        assert!(info.is_endgame);
+        assert!(!info.is_opening);
+    }
+
+    #[test]
+    fn test_search_params_field_mapping() {
+        let time_control = TimeControl {
+            white_time_ms: Some(60_000),
+            black_time_ms: Some(55_000),
+            white_increment_ms: Some(1000),
+            black_increment_ms: Some(2000),
+            moves_to_go: Some(20),
+            move_time_ms: Some(3000),
+            depth: Some(12),
+            nodes: Some(500_000),
+            ponder: true,
+            ..Default::default()
+        };
+
+        let params = SearchParams::from(&time_control);
+        assert_eq!(
+            params,
+            SearchParams {
+                wtime: Some(60_000),
+                btime: Some(55_000),
+                winc: Some(1000),
+                binc: Some(2000),
+                movestogo: Some(20),
+                movetime: Some(3000),
+                depth: Some(12),
+                nodes: Some(500_000),
+                mate: None,
+                infinite: false,
+                ponder: true,
+                byoyomi: None,
+                periods: None,
+                move_overhead_ms: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_infinite_wins_over_movetime() {
+        let time_control = TimeControl {
+            move_time_ms: Some(1000),
+            white_time_ms: Some(60_000),
+            infinite: true,
+            ..Default::default()
+        };
+
+        let params = SearchParams::from(&time_control);
+        assert!(params.infinite);
+        assert_eq!(params.movetime, None);
+        assert_eq!(params.wtime, None);
+        assert!(params
+            .time_limits(&PositionInfo::default(), &StandardTimePolicy::default())
+            .is_infinite());
+    }
+
+    #[test]
+    fn test_mate_search_has_no_clock() {
+        let time_control = TimeControl {
+            mate: Some(3),
+            ..Default::default()
+        };
+
+        let params = SearchParams::from(&time_control);
+        assert_eq!(params.mate, Some(3));
+        assert_eq!(params.movetime, None);
+        assert_eq!(params.wtime, None);
+        assert_eq!(params.btime, None);
+    }
+}
@@ -0,0 +1,789 @@
+// Standard Time Management Policies
+//
+// Classical clock division: spend an equal share of the remaining time on
+// each move until the next time control, plus the increment. Byoyomi covers
+// Japanese time controls, the adaptive wrapper thinks longer when the score
+// drops, and fixed, infinite and node-budget policies are also provided for
+// experiments, reproducible tests and benchmarks.
+
+use std::time::Duration;
+
+use crate::time::{PositionInfo, SearchParams, SearchProgress, Side, TimeLimits, TimePolicy};
+
+/// Moves assumed to remain at the start of a sudden-death game (no `movestogo`)
+const SUDDEN_DEATH_OPENING_MOVES: u64 = 50;
+
+/// Fewest moves ever assumed to remain in sudden death
+const SUDDEN_DEATH_MIN_MOVES: u64 = 20;
+
+/// Sudden-death allocations never exceed this share (1/N) of the remaining clock
+const SUDDEN_DEATH_MAX_SHARE: u64 = 20;
+
+/// Moves the remaining main time is divided over under byoyomi
+const BYOYOMI_MAIN_TIME_MOVES: u64 = 30;
+
+/// Time held back from the byoyomi clock to absorb GUI/OS latency
+const BYOYOMI_SAFETY_MARGIN_MS: u64 = 50;
+
+/// Time per move used by `FixedTimePolicy::default`
+const DEFAULT_FIXED_MOVE_TIME_MS: u64 = 1000;
+
+/// Classical time allocation with a safety margin for communication lag
+///
+/// The `MoveOverhead` carried in the search parameters is reserved on top of
+/// the safety margin.
+#[derive(Debug, Clone)]
+pub struct StandardTimePolicy {
+    /// Time held back from every budget to absorb GUI/OS latency
+    safety_margin_ms: u64,
+    /// Fraction of the per-move allocation used as the soft limit
+    time_factor: f64,
+}
+
+impl StandardTimePolicy {
+    /// Create a policy with the given safety margin and soft-limit factor
+    pub fn new(safety_margin_ms: u64, time_factor: f64) -> Self {
+        Self {
+            safety_margin_ms,
+            time_factor,
+        }
+    }
+
+    /// Moves the remaining clock must last when the GUI sends no `movestogo`
+    ///
+    /// Early in the game many moves are still to come, so the estimate starts
+    /// conservative and shrinks by three moves for every four played, down to
+    /// `SUDDEN_DEATH_MIN_MOVES`. An unknown move number counts as the opening.
+    fn estimate_moves_remaining(&self, position_info: &PositionInfo) -> u64 {
+        let played = u64::from(position_info.move_number) * 3 / 4;
+        SUDDEN_DEATH_OPENING_MOVES
+            .saturating_sub(played)
+            .max(SUDDEN_DEATH_MIN_MOVES)
+    }
+}
+
+/// Take the `MoveOverhead` off a budget, leaving the search at least 1ms
+///
+/// Time spent in transit to the GUI counts against our clock too.
+fn less_overhead(limit_ms: u64, search_params: &SearchParams) -> Duration {
+    Duration::from_millis(
+        limit_ms
+            .saturating_sub(search_params.move_overhead_ms)
+            .max(1),
+    )
+}
+
+impl Default for StandardTimePolicy {
+    fn default() -> Self {
+        Self::new(50, 1.0)
+    }
+}
+
+impl TimePolicy for StandardTimePolicy {
+    fn calculate_time_limit(
+        &self,
+        search_params: &SearchParams,
+        position_info: &PositionInfo,
+    ) -> TimeLimits {
+        if let Some(move_time) = search_params.movetime {
+            return TimeLimits {
+                soft_limit: less_overhead(
+                    move_time.saturating_sub(self.safety_margin_ms),
+                    search_params,
+                ),
+                hard_limit: less_overhead(move_time, search_params),
+            };
+        }
+
+        // Only the side to move's clock matters; a missing increment means none
+        let (our_time, our_increment) = match position_info.side_to_move {
+            Side::White => (search_params.wtime, search_params.winc),
+            Side::Black => (search_params.btime, search_params.binc),
+        };
+
+        let Some(our_time) = our_time else {
+            return TimeLimits::infinite();
+        };
+
+        let allocation = match search_params.movestogo.filter(|&moves| moves > 0) {
+            Some(moves_to_go) => our_time / u64::from(moves_to_go) + our_increment.unwrap_or(0),
+            None => {
+                let moves_remaining = self.estimate_moves_remaining(position_info);
+                (our_time / moves_remaining + our_increment.unwrap_or(0))
+                    .min(our_time / SUDDEN_DEATH_MAX_SHARE)
+            }
+        };
+
+        // Never plan to use more than what is left on the clock
+        let available = our_time.saturating_sub(self.safety_margin_ms).max(1);
+        let hard_limit = allocation.saturating_mul(3).clamp(1, available);
+        let soft_limit = ((allocation as f64 * self.time_factor) as u64).clamp(1, hard_limit);
+
+        TimeLimits {
+            soft_limit: less_overhead(soft_limit, search_params),
+            hard_limit: less_overhead(hard_limit, search_params),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Standard"
+    }
+}
+
+/// Japanese time control: a main time, then fixed-length byoyomi periods
+///
+/// Main time is spent first, divided evenly over the coming moves. Once it is
+/// used up every move gets one full byoyomi period, which is lost only if it is
+/// overrun. The side to move's `wtime`/`btime` is the main time left, and the
+/// `byoyomi`/`periods` search parameters override the configured values. A
+/// safety margin and the `MoveOverhead` are held back from every budget.
+#[derive(Debug, Clone)]
+pub struct ByoyomiTimePolicy {
+    /// Main time at the start of the game
+    main_time_ms: u64,
+    /// Length of one byoyomi period
+    byoyomi_ms: u64,
+    /// Byoyomi periods at the start of the game
+    periods: u32,
+}
+
+impl ByoyomiTimePolicy {
+    /// Create a policy for `main_time_ms` of main time followed by `periods`
+    /// periods of `byoyomi_ms` each
+    pub fn new(main_time_ms: u64, byoyomi_ms: u64, periods: u32) -> Self {
+        Self {
+            main_time_ms,
+            byoyomi_ms,
+            periods,
+        }
+    }
+}
+
+impl TimePolicy for ByoyomiTimePolicy {
+    fn calculate_time_limit(
+        &self,
+        search_params: &SearchParams,
+        position_info: &PositionInfo,
+    ) -> TimeLimits {
+        if let Some(move_time) = search_params.movetime {
+            return TimeLimits {
+                soft_limit: less_overhead(
+                    move_time.saturating_sub(BYOYOMI_SAFETY_MARGIN_MS),
+                    search_params,
+                ),
+                hard_limit: less_overhead(move_time, search_params),
+            };
+        }
+
+        let main_time = match position_info.side_to_move {
+            Side::White => search_params.wtime,
+            Side::Black => search_params.btime,
+        }
+        .unwrap_or(self.main_time_ms);
+        let byoyomi = search_params.byoyomi.unwrap_or(self.byoyomi_ms);
+        let periods = search_params.periods.unwrap_or(self.periods);
+
+        // A period is only available while one is left
+        let period = if periods > 0 { byoyomi } else { 0 };
+        if main_time == 0 {
+            let period = less_overhead(
+                period.saturating_sub(BYOYOMI_SAFETY_MARGIN_MS),
+                search_params,
+            );
+            return TimeLimits {
+                soft_limit: period,
+                hard_limit: period,
+            };
+        }
+
+        // Overrunning main time falls into the first period rather than losing
+        let available = main_time
+            .saturating_add(period)
+            .saturating_sub(BYOYOMI_SAFETY_MARGIN_MS)
+            .max(1);
+        let allocation = (main_time / BYOYOMI_MAIN_TIME_MOVES).max(1);
+        let hard_limit = allocation.saturating_mul(3).min(available);
+        TimeLimits {
+            soft_limit: less_overhead(allocation.min(hard_limit), search_params),
+            hard_limit: less_overhead(hard_limit, search_params),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Byoyomi"
+    }
+}
+
+/// Wraps another policy and keeps searching while the evaluation is falling
+///
+/// Searches stop once the soft limit is reached, or earlier if the base policy
+/// says so, unless the best score dropped by more than the threshold between
+/// the last two iterations. The search then continues towards the hard limit to
+/// look for a way out, as a strong player would think longer after spotting a
+/// problem.
+#[derive(Debug)]
+pub struct AdaptiveTimePolicy {
+    /// Policy that allocates the time and decides ordinary early stops
+    base: Box<dyn TimePolicy>,
+    /// Score drop in centipawns between iterations that prevents stopping
+    drop_threshold_cp: i32,
+}
+
+impl AdaptiveTimePolicy {
+    /// Wrap `base`, extending searches whose score fell by more than `drop_threshold_cp`
+    pub fn new(base: Box<dyn TimePolicy>, drop_threshold_cp: i32) -> Self {
+        Self {
+            base,
+            drop_threshold_cp,
+        }
+    }
+}
+
+impl TimePolicy for AdaptiveTimePolicy {
+    fn calculate_time_limit(
+        &self,
+        search_params: &SearchParams,
+        position_info: &PositionInfo,
+    ) -> TimeLimits {
+        self.base.calculate_time_limit(search_params, position_info)
+    }
+
+    fn should_stop_early(&self, progress: &SearchProgress, limits: &TimeLimits) -> bool {
+        let score_drop = i64::from(progress.prev_score) - i64::from(progress.score);
+        if score_drop > i64::from(self.drop_threshold_cp) {
+            return false;
+        }
+        progress.elapsed >= limits.soft_limit || self.base.should_stop_early(progress, limits)
+    }
+
+    fn name(&self) -> &str {
+        "Adaptive"
+    }
+}
+
+/// Spends the same time on every move, ignoring the clock
+///
+/// `movetime` still takes precedence so GUIs can pin a single search.
+#[derive(Debug, Clone)]
+pub struct FixedTimePolicy {
+    /// Time spent on each move
+    move_time_ms: u64,
+}
+
+impl FixedTimePolicy {
+    /// Create a policy that searches `move_time_ms` milliseconds per move
+    pub fn new(move_time_ms: u64) -> Self {
+        Self { move_time_ms }
+    }
+}
+
+impl Default for FixedTimePolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_FIXED_MOVE_TIME_MS)
+    }
+}
+
+impl TimePolicy for FixedTimePolicy {
+    fn calculate_time_limit(
+        &self,
+        search_params: &SearchParams,
+        _position_info: &PositionInfo,
+    ) -> TimeLimits {
+        let move_time = Duration::from_millis(search_params.movetime.unwrap_or(self.move_time_ms));
+        TimeLimits {
+            soft_limit: move_time,
+            hard_limit: move_time,
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Fixed"
+    }
+}
+
+/// Never limits the search; it runs until `stop`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InfiniteTimePolicy;
+
+impl TimePolicy for InfiniteTimePolicy {
+    fn calculate_time_limit(
+        &self,
+        _search_params: &SearchParams,
+        _position_info: &PositionInfo,
+    ) -> TimeLimits {
+        TimeLimits::infinite()
+    }
+
+    fn name(&self) -> &str {
+        "Infinite"
+    }
+}
+
+/// Clock-independent policy that stops after a fixed number of nodes
+///
+/// Searches are reproducible regardless of machine speed, which makes this
+/// the policy of choice for regression tests and node-limited benchmarks.
+#[derive(Debug, Clone)]
+pub struct NodeLimitedTimePolicy {
+    /// Nodes after which the search stops
+    max_nodes: u64,
+}
+
+impl NodeLimitedTimePolicy {
+    /// Create a policy that stops once `max_nodes` nodes have been searched
+    pub fn new(max_nodes: u64) -> Self {
+        Self { max_nodes }
+    }
+}
+
+impl TimePolicy for NodeLimitedTimePolicy {
+    fn calculate_time_limit(
+        &self,
+        _search_params: &SearchParams,
+        _position_info: &PositionInfo,
+    ) -> TimeLimits {
+        TimeLimits::infinite()
+    }
+
+    fn should_stop_early(&self, progress: &SearchProgress, _limits: &TimeLimits) -> bool {
+        progress.nodes >= self.max_nodes
+    }
+
+    fn name(&self) -> &str {
+        "NodeLimited"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn black_to_move() -> PositionInfo {
+        PositionInfo {
+            side_to_move: Side::Black,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_movestogo_without_increment_uses_black_clock() {
+        let policy = StandardTimePolicy::default();
+        let search_params = SearchParams {
+            wtime: Some(10_000),
+            btime: Some(120_000),
+            movestogo: Some(40),
+            ..Default::default()
+        };
+
+        let limits = policy.calculate_time_limit(&search_params, &black_to_move());
+
+        // 120000ms over 40 moves, no increment
+        assert_eq!(limits.soft_limit, Duration::from_millis(3000));
+        assert_eq!(limits.hard_limit, Duration::from_millis(9000));
+    }
+
+    #[test]
+    fn test_white_increment_not_applied_to_black() {
+        let policy = StandardTimePolicy::default();
+        let search_params = SearchParams {
+            btime: Some(120_000),
+            winc: Some(2000),
+            movestogo: Some(40),
+            ..Default::default()
+        };
+
+        let limits = policy.calculate_time_limit(&search_params, &black_to_move());
+        assert_eq!(limits.soft_limit, Duration::from_millis(3000));
+    }
+
+    #[test]
+    fn test_increment_and_time_factor() {
+        let policy = StandardTimePolicy::new(50, 0.3);
+        let search_params = SearchParams {
+            wtime: Some(60_000),
+            winc: Some(1000),
+            ..Default::default()
+        };
+
+        let limits = policy.calculate_time_limit(&search_params, &PositionInfo::default());
+
+        // 60000 / 50 + 1000 = 2200ms allocation at the start of the game
+        assert_eq!(limits.soft_limit, Duration::from_millis(660));
+        assert_eq!(limits.hard_limit, Duration::from_millis(6600));
+    }
+
+    #[test]
+    fn test_move_overhead_reduces_both_limits() {
+        let policy = StandardTimePolicy::default();
+        let mut search_params = SearchParams {
+            btime: Some(120_000),
+            movestogo: Some(40),
+            ..Default::default()
+        };
+        let base = policy.calculate_time_limit(&search_params, &black_to_move());
+
+        search_params.move_overhead_ms = 500;
+        let limits = policy.calculate_time_limit(&search_params, &black_to_move());
+        assert_eq!(
+            limits.soft_limit,
+            base.soft_limit - Duration::from_millis(500)
+        );
+        assert_eq!(
+            limits.hard_limit,
+            base.hard_limit - Duration::from_millis(500)
+        );
+
+        // movetime loses the overhead on top of the safety margin
+        let search_params = SearchParams {
+            movetime: Some(1000),
+            move_overhead_ms: 100,
+            ..Default::default()
+        };
+        let limits = policy.calculate_time_limit(&search_params, &PositionInfo::default());
+        assert_eq!(limits.soft_limit, Duration::from_millis(850));
+        assert_eq!(limits.hard_limit, Duration::from_millis(900));
+
+        // An overhead larger than the budget still leaves the search a moment
+        let search_params = SearchParams {
+            movetime: Some(20),
+            move_overhead_ms: 5000,
+            ..Default::default()
+        };
+        let limits = policy.calculate_time_limit(&search_params, &PositionInfo::default());
+        assert_eq!(limits.hard_limit, Duration::from_millis(1));
+    }
+
+    fn at_move(move_number: u32) -> PositionInfo {
+        PositionInfo {
+            move_number,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sudden_death_spends_more_later_in_the_game() {
+        let policy = StandardTimePolicy::default();
+        let search_params = SearchParams {
+            wtime: Some(300_000),
+            ..Default::default()
+        };
+
+        let early = policy.calculate_time_limit(&search_params, &at_move(5));
+        let late = policy.calculate_time_limit(&search_params, &at_move(40));
+
+        // 300000 / 47 at move 5, 300000 / 20 at move 40
+        assert_eq!(early.soft_limit, Duration::from_millis(6382));
+        assert_eq!(late.soft_limit, Duration::from_millis(15_000));
+        assert!(late.hard_limit > early.hard_limit);
+    }
+
+    #[test]
+    fn test_sudden_death_caps_allocation_at_share_of_clock() {
+        let policy = StandardTimePolicy::default();
+        let search_params = SearchParams {
+            wtime: Some(10_000),
+            winc: Some(5000),
+            ..Default::default()
+        };
+
+        // 10000 / 20 + 5000 would spend more than 1/20 of the clock
+        let limits = policy.calculate_time_limit(&search_params, &at_move(40));
+        assert_eq!(limits.soft_limit, Duration::from_millis(500));
+        assert_eq!(limits.hard_limit, Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn test_movestogo_ignores_move_number() {
+        let policy = StandardTimePolicy::default();
+        let search_params = SearchParams {
+            wtime: Some(120_000),
+            winc: Some(2000),
+            movestogo: Some(40),
+            ..Default::default()
+        };
+
+        let early = policy.calculate_time_limit(&search_params, &at_move(5));
+        let late = policy.calculate_time_limit(&search_params, &at_move(40));
+
+        // 120000 / 40 + 2000, uncapped
+        assert_eq!(early.soft_limit, Duration::from_millis(5000));
+        assert_eq!(early, late);
+    }
+
+    #[test]
+    fn test_low_clock_never_exceeds_remaining_time() {
+        let policy = StandardTimePolicy::default();
+        let search_params = SearchParams {
+            wtime: Some(200),
+            winc: Some(5000),
+            ..Default::default()
+        };
+
+        let limits = policy.calculate_time_limit(&search_params, &PositionInfo::default());
+        assert!(limits.hard_limit <= Duration::from_millis(150));
+        assert!(limits.soft_limit <= limits.hard_limit);
+        assert!(limits.soft_limit.as_millis() > 0);
+    }
+
+    #[test]
+    fn test_movetime_and_infinite() {
+        let policy = StandardTimePolicy::default();
+
+        let movetime = SearchParams {
+            movetime: Some(1000),
+            ..Default::default()
+        };
+        let limits = policy.calculate_time_limit(&movetime, &PositionInfo::default());
+        assert_eq!(limits.soft_limit, Duration::from_millis(950));
+        assert_eq!(limits.hard_limit, Duration::from_millis(1000));
+
+        // Missing clock for the side to move means no time limit
+        let white_only = SearchParams {
+            wtime: Some(60_000),
+            ..Default::default()
+        };
+        assert!(policy
+            .calculate_time_limit(&white_only, &black_to_move())
+            .is_infinite());
+    }
+
+    #[test]
+    fn test_node_limited_stops_at_budget() {
+        let policy = NodeLimitedTimePolicy::new(10_000);
+        let search_params = SearchParams {
+            wtime: Some(100),
+            movetime: Some(5),
+            ..Default::default()
+        };
+
+        let limits = policy.calculate_time_limit(&search_params, &PositionInfo::default());
+        assert!(limits.is_infinite());
+
+        let at = |nodes| SearchProgress {
+            nodes,
+            ..Default::default()
+        };
+        assert!(!policy.should_stop_early(&at(0), &limits));
+        assert!(!policy.should_stop_early(&at(9_999), &limits));
+        assert!(policy.should_stop_early(&at(10_000), &limits));
+        assert!(policy.should_stop_early(&at(10_001), &limits));
+    }
+
+    #[test]
+    fn test_node_limited_ignores_depth_and_stability() {
+        let policy = NodeLimitedTimePolicy::new(10_000);
+        let limits = TimeLimits::infinite();
+
+        let deep_and_stable = SearchProgress {
+            elapsed: Duration::from_secs(3600),
+            depth: 99,
+            nodes: 500,
+            best_move_stability: 50,
+            ..Default::default()
+        };
+        assert!(!policy.should_stop_early(&deep_and_stable, &limits));
+
+        let shallow = SearchProgress {
+            nodes: 10_000,
+            ..Default::default()
+        };
+        assert!(policy.should_stop_early(&shallow, &limits));
+    }
+
+    #[test]
+    fn test_byoyomi_spends_main_time_first() {
+        // 10 minutes main time, then 3 periods of 30 seconds
+        let policy = ByoyomiTimePolicy::new(600_000, 30_000, 3);
+        let search_params = SearchParams {
+            wtime: Some(300_000),
+            ..Default::default()
+        };
+
+        let limits = policy.calculate_time_limit(&search_params, &PositionInfo::default());
+        assert_eq!(limits.soft_limit, Duration::from_millis(10_000));
+        assert_eq!(limits.hard_limit, Duration::from_millis(30_000));
+
+        // Without a clock the full main time is assumed
+        let limits =
+            policy.calculate_time_limit(&SearchParams::default(), &PositionInfo::default());
+        assert_eq!(limits.soft_limit, Duration::from_millis(20_000));
+    }
+
+    #[test]
+    fn test_byoyomi_allocates_one_period_after_main_time() {
+        let policy = ByoyomiTimePolicy::new(600_000, 30_000, 3);
+        let search_params = SearchParams {
+            btime: Some(0),
+            ..Default::default()
+        };
+
+        let limits = policy.calculate_time_limit(&search_params, &black_to_move());
+        assert_eq!(limits.soft_limit, Duration::from_millis(29_950));
+        assert_eq!(limits.hard_limit, Duration::from_millis(29_950));
+
+        // Period length from the GUI wins over the configured one
+        let override_params = SearchParams {
+            byoyomi: Some(10_000),
+            ..search_params
+        };
+        let limits = policy.calculate_time_limit(&override_params, &black_to_move());
+        assert_eq!(limits.hard_limit, Duration::from_millis(9_950));
+    }
+
+    #[test]
+    fn test_byoyomi_main_time_overrun_limited_to_next_period() {
+        let policy = ByoyomiTimePolicy::new(600_000, 5_000, 1);
+        let search_params = SearchParams {
+            wtime: Some(3_000),
+            ..Default::default()
+        };
+
+        let limits = policy.calculate_time_limit(&search_params, &PositionInfo::default());
+        assert_eq!(limits.soft_limit, Duration::from_millis(100));
+        assert_eq!(limits.hard_limit, Duration::from_millis(300));
+
+        // With no periods left the main time, less the margin, is all there is
+        let last = SearchParams {
+            wtime: Some(80),
+            periods: Some(0),
+            ..search_params
+        };
+        let limits = policy.calculate_time_limit(&last, &PositionInfo::default());
+        assert_eq!(limits.soft_limit, Duration::from_millis(2));
+        assert_eq!(limits.hard_limit, Duration::from_millis(6));
+
+        let last = SearchParams {
+            wtime: Some(20),
+            ..last
+        };
+        let limits = policy.calculate_time_limit(&last, &PositionInfo::default());
+        assert_eq!(limits.hard_limit, Duration::from_millis(1));
+    }
+
+    #[test]
+    fn test_byoyomi_move_overhead_reduces_both_limits() {
+        let policy = ByoyomiTimePolicy::new(600_000, 30_000, 3);
+        let in_main_time = SearchParams {
+            wtime: Some(300_000),
+            move_overhead_ms: 200,
+            ..Default::default()
+        };
+        let limits = policy.calculate_time_limit(&in_main_time, &PositionInfo::default());
+        assert_eq!(limits.soft_limit, Duration::from_millis(9_800));
+        assert_eq!(limits.hard_limit, Duration::from_millis(29_800));
+
+        let in_byoyomi = SearchParams {
+            wtime: Some(0),
+            ..in_main_time
+        };
+        let limits = policy.calculate_time_limit(&in_byoyomi, &PositionInfo::default());
+        assert_eq!(limits.hard_limit, Duration::from_millis(29_750));
+
+        let movetime = SearchParams {
+            movetime: Some(1000),
+            ..in_main_time
+        };
+        let limits = policy.calculate_time_limit(&movetime, &PositionInfo::default());
+        assert_eq!(limits.soft_limit, Duration::from_millis(750));
+        assert_eq!(limits.hard_limit, Duration::from_millis(800));
+    }
+
+    fn adaptive_progress(elapsed_ms: u64, prev_score: i32, score: i32) -> SearchProgress {
+        SearchProgress {
+            elapsed: Duration::from_millis(elapsed_ms),
+            depth: 8,
+            score,
+            prev_score,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_adaptive_stable_score_stops_at_soft_limit() {
+        let policy = AdaptiveTimePolicy::new(Box::new(StandardTimePolicy::default()), 100);
+        let limits = TimeLimits {
+            soft_limit: Duration::from_millis(1000),
+            hard_limit: Duration::from_millis(3000),
+        };
+
+        assert!(!policy.should_stop_early(&adaptive_progress(900, 40, 35), &limits));
+        assert!(policy.should_stop_early(&adaptive_progress(1000, 40, 35), &limits));
+
+        // Rising scores are no reason to keep thinking
+        assert!(policy.should_stop_early(&adaptive_progress(1200, 40, 300), &limits));
+    }
+
+    #[test]
+    fn test_adaptive_score_drop_prevents_early_stop() {
+        let policy = AdaptiveTimePolicy::new(Box::new(NodeLimitedTimePolicy::new(1000)), 100);
+        let limits = TimeLimits {
+            soft_limit: Duration::from_millis(1000),
+            hard_limit: Duration::from_millis(3000),
+        };
+
+        let dropped = SearchProgress {
+            nodes: 5000,
+            ..adaptive_progress(2000, 50, -100)
+        };
+        assert!(!policy.should_stop_early(&dropped, &limits));
+
+        // A drop at the threshold is still ordinary noise
+        let noise = SearchProgress {
+            nodes: 5000,
+            ..adaptive_progress(2000, 50, -50)
+        };
+        assert!(policy.should_stop_early(&noise, &limits));
+        assert_eq!(policy.name(), "Adaptive");
+    }
+
+    #[test]
+    fn test_fixed_policy_ignores_clock() {
+        let policy = FixedTimePolicy::new(250);
+        let clock = SearchParams {
+            wtime: Some(600_000),
+            btime: Some(600_000),
+            ..Default::default()
+        };
+
+        let limits = policy.calculate_time_limit(&clock, &PositionInfo::default());
+        assert_eq!(limits.hard_limit, Duration::from_millis(250));
+        assert_eq!(limits.soft_limit, Duration::from_millis(250));
+
+        let pinned = SearchParams {
+            movetime: Some(40),
+            ..clock
+        };
+        let limits = policy.calculate_time_limit(&pinned, &PositionInfo::default());
+        assert_eq!(limits.hard_limit, Duration::from_millis(40));
+    }
+
+    #[test]
+    fn test_infinite_policy_never_limits() {
+        let movetime = SearchParams {
+            movetime: Some(1000),
+            ..Default::default()
+        };
+        assert!(InfiniteTimePolicy
+            .calculate_time_limit(&movetime, &PositionInfo::default())
+            .is_infinite());
+    }
+
+    #[test]
+    fn test_standard_policy_never_stops_early() {
+        let policy = StandardTimePolicy::default();
+        let progress = SearchProgress {
+            nodes: u64::MAX,
+            depth: 99,
+            ..Default::default()
+        };
+        assert!(!policy.should_stop_early(&progress, &TimeLimits::infinite()));
+    }
+
+    #[test]
+    fn test_position_info_from_fen() {
+        let info =
+            PositionInfo::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+        assert_eq!(info.side_to_move, Side::Black);
+        assert_eq!(PositionInfo::from_fen("garbage").side_to_move, Side::White);
+    }
+}
@@ -0,0 +1,157 @@
+// Search Clock
+//
+// A `SearchTimer` is started when a search begins and checked between
+// iterations, so the hard limit is enforced by the search itself rather than
+// trusted to whatever schedules the iterations. A `NodeLimit` does the same
+// for the `go nodes` budget.
+
+use std::time::{Duration, Instant};
+
+use crate::time::TimeLimits;
+
+/// Wall clock of one search measured against its time limits
+#[derive(Debug, Clone, Copy)]
+pub struct SearchTimer {
+    /// When the search started
+    start: Instant,
+    /// Budget the search runs under
+    limits: TimeLimits,
+}
+
+impl SearchTimer {
+    /// Start timing a search with the given limits
+    pub fn new(limits: TimeLimits) -> Self {
+        Self {
+            start: Instant::now(),
+            limits,
+        }
+    }
+
+    /// Time since the search started
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Limits the timer was started with
+    pub fn limits(&self) -> TimeLimits {
+        self.limits
+    }
+
+    /// Whether the soft limit has passed; no new iteration should start
+    pub fn soft_limit_reached(&self) -> bool {
+        self.elapsed() >= self.limits.soft_limit
+    }
+
+    /// Whether the hard limit has passed and the search must stop now
+    ///
+    /// Never true for infinite limits.
+    pub fn should_stop(&self) -> bool {
+        !self.limits.is_infinite() && self.elapsed() >= self.limits.hard_limit
+    }
+}
+
+/// Node budget of one search from `go nodes`
+///
+/// The search checks the budget before each batch of nodes it visits, so it
+/// stops at the budget or overshoots it by less than one batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NodeLimit {
+    /// Nodes after which no new batch starts; `None` searches without a budget
+    max_nodes: Option<u64>,
+}
+
+impl NodeLimit {
+    /// Budget of `max_nodes` nodes, or none
+    pub fn new(max_nodes: Option<u64>) -> Self {
+        Self { max_nodes }
+    }
+
+    /// The budget, if there is one
+    pub fn max_nodes(&self) -> Option<u64> {
+        self.max_nodes
+    }
+
+    /// Whether a search that has visited `nodes` nodes must stop
+    ///
+    /// A budget of 0 is reached before the search visits anything.
+    pub fn reached(&self, nodes: u64) -> bool {
+        self.max_nodes.is_some_and(|max_nodes| nodes >= max_nodes)
+    }
+
+    /// Batches of `batch_nodes` nodes it takes to spend the whole budget
+    ///
+    /// `None` without a budget; 0 for a budget of 0.
+    pub fn batches(&self, batch_nodes: u64) -> Option<u64> {
+        self.max_nodes
+            .map(|max_nodes| max_nodes.div_ceil(batch_nodes.max(1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timer_stops_after_hard_limit() {
+        let timer = SearchTimer::new(TimeLimits {
+            soft_limit: Duration::ZERO,
+            hard_limit: Duration::from_millis(20),
+        });
+        assert!(timer.soft_limit_reached());
+        assert!(!timer.should_stop());
+
+        std::thread::sleep(Duration::from_millis(25));
+        assert!(timer.should_stop());
+        assert!(timer.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_infinite_timer_never_stops() {
+        let timer = SearchTimer::new(TimeLimits::infinite());
+        assert!(!timer.soft_limit_reached());
+        assert!(!timer.should_stop());
+        assert!(timer.limits().is_infinite());
+    }
+
+    /// Stand-in for the search: visits `batch_nodes` nodes at a time until the
+    /// limit says stop, returning how many it visited
+    fn mock_search(limit: NodeLimit, batch_nodes: u64) -> u64 {
+        let mut nodes = 0;
+        while !limit.reached(nodes) {
+            nodes += batch_nodes;
+        }
+        nodes
+    }
+
+    #[test]
+    fn test_node_limit_stops_within_one_batch() {
+        let limit = NodeLimit::new(Some(100));
+        for batch_nodes in [1, 7, 20, 100] {
+            let nodes = mock_search(limit, batch_nodes);
+            assert!(
+                nodes >= 100,
+                "{} nodes in batches of {}",
+                nodes,
+                batch_nodes
+            );
+            assert!(
+                nodes < 100 + batch_nodes,
+                "{} nodes in batches of {}",
+                nodes,
+                batch_nodes
+            );
+            assert_eq!(limit.batches(batch_nodes), Some(nodes / batch_nodes));
+        }
+    }
+
+    #[test]
+    fn test_zero_node_limit_searches_nothing() {
+        let limit = NodeLimit::new(Some(0));
+        assert_eq!(mock_search(limit, 20), 0);
+        assert_eq!(limit.batches(20), Some(0));
+
+        let unlimited = NodeLimit::default();
+        assert!(!unlimited.reached(u64::MAX));
+        assert_eq!(unlimited.batches(20), None);
+    }
+}
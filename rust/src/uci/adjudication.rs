@@ -0,0 +1,170 @@
+// Self-Play Adjudication
+//
+// Games played between engine instances for training data are cut short once
+// the outcome is clear. The engine keeps the best score of each search and,
+// when `ResignThreshold` or `DrawMoveCount` are enabled, signals the match
+// runner with a non-standard `info string resign` / `info string offerdraw`.
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::uci::state::EngineConfig;
+
+/// Consecutive searches at or below `-ResignThreshold` before the engine resigns
+pub const RESIGN_MOVE_COUNT: usize = 3;
+
+/// Scores kept per game; enough for the largest `DrawMoveCount`
+const MAX_SCORE_HISTORY: usize = 256;
+
+/// Outcome signalled to a self-play match runner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjudication {
+    /// The position is hopelessly lost
+    Resign,
+    /// The position has been level for many moves
+    OfferDraw,
+}
+
+impl fmt::Display for Adjudication {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Adjudication::Resign => f.write_str("resign"),
+            Adjudication::OfferDraw => f.write_str("offerdraw"),
+        }
+    }
+}
+
+/// Best score of each search in the current game, from the engine's side
+#[derive(Debug, Default, Clone)]
+pub struct ScoreHistory {
+    scores: VecDeque<i32>,
+}
+
+impl ScoreHistory {
+    /// Create an empty history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the best score (centipawns) of a finished search
+    pub fn record(&mut self, score_cp: i32) {
+        if self.scores.len() == MAX_SCORE_HISTORY {
+            self.scores.pop_front();
+        }
+        self.scores.push_back(score_cp);
+    }
+
+    /// Forget all scores, e.g. on `ucinewgame`
+    pub fn clear(&mut self) {
+        self.scores.clear();
+    }
+
+    /// Number of recorded scores
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    /// Whether no scores have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    /// Decide whether to resign or offer a draw under the configured thresholds
+    ///
+    /// Resignation takes priority. Both signals are off while their option is 0.
+    pub fn adjudicate(&self, config: &EngineConfig) -> Option<Adjudication> {
+        if config.resign_threshold > 0
+            && self.last_n_all(RESIGN_MOVE_COUNT, |score| score <= -config.resign_threshold)
+        {
+            return Some(Adjudication::Resign);
+        }
+
+        if config.draw_move_count > 0
+            && self.last_n_all(config.draw_move_count as usize, |score| {
+                score.abs() <= config.draw_threshold
+            })
+        {
+            return Some(Adjudication::OfferDraw);
+        }
+
+        None
+    }
+
+    /// Whether at least `n` scores exist and the last `n` all satisfy `predicate`
+    fn last_n_all(&self, n: usize, predicate: impl Fn(i32) -> bool) -> bool {
+        self.scores.len() >= n && self.scores.iter().rev().take(n).all(|&s| predicate(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(resign_threshold: i32, draw_threshold: i32, draw_move_count: u32) -> EngineConfig {
+        EngineConfig {
+            resign_threshold,
+            draw_threshold,
+            draw_move_count,
+            ..EngineConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        let mut history = ScoreHistory::new();
+        for _ in 0..50 {
+            history.record(-5000);
+        }
+        assert_eq!(history.adjudicate(&EngineConfig::default()), None);
+
+        history.clear();
+        for _ in 0..50 {
+            history.record(0);
+        }
+        assert_eq!(history.adjudicate(&EngineConfig::default()), None);
+    }
+
+    #[test]
+    fn test_sustained_losing_score_resigns() {
+        let config = config(700, 10, 0);
+        let mut history = ScoreHistory::new();
+
+        history.record(-900);
+        history.record(-900);
+        assert_eq!(history.adjudicate(&config), None);
+
+        history.record(-900);
+        assert_eq!(history.adjudicate(&config), Some(Adjudication::Resign));
+
+        // A single recovery resets the streak
+        history.record(-100);
+        assert_eq!(history.adjudicate(&config), None);
+    }
+
+    #[test]
+    fn test_sustained_level_score_offers_draw() {
+        let config = config(0, 10, 4);
+        let mut history = ScoreHistory::new();
+
+        for score in [5, -10, 0] {
+            history.record(score);
+        }
+        assert_eq!(history.adjudicate(&config), None);
+
+        history.record(8);
+        assert_eq!(history.adjudicate(&config), Some(Adjudication::OfferDraw));
+
+        history.record(40);
+        assert_eq!(history.adjudicate(&config), None);
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut history = ScoreHistory::new();
+        for score in 0..1000 {
+            history.record(score);
+        }
+        assert_eq!(history.len(), MAX_SCORE_HISTORY);
+        assert_eq!(Adjudication::OfferDraw.to_string(), "offerdraw");
+    }
+}
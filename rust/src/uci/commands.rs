@@ -87,11 +87,17 @@ impl<'a> ChessMove<'a> {
     }
 }
 
+/// Formats the move in canonical UCI notation: lowercase files and promotion piece,
+/// whatever casing the GUI sent
 impl<'a> fmt::Display for ChessMove<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{}", self.from_square, self.to_square)?;
-        if let Some(promo) = self.promotion {
-            write!(f, "{}", promo)?;
+        let parts = [
+            self.from_square,
+            self.to_square,
+            self.promotion.unwrap_or(""),
+        ];
+        for c in parts.iter().flat_map(|part| part.chars()) {
+            write!(f, "{}", c.to_ascii_lowercase())?;
         }
         Ok(())
     }
@@ -99,7 +105,7 @@ impl<'a> fmt::Display for ChessMove<'a> {
 
 /// Time control parameters for search commands
 #[derive(Debug, Clone, PartialEq)]
-pub struct TimeControl {
+pub struct TimeControl<'a> {
     pub white_time_ms: Option<u64>,
     pub black_time_ms: Option<u64>,
     pub white_increment_ms: Option<u64>,
@@ -111,9 +117,11 @@ pub struct TimeControl {
     pub depth: Option<u32>,
     pub nodes: Option<u64>,
     pub mate: Option<u32>,
+    /// Root moves the search is restricted to (`go searchmoves ...`)
+    pub search_moves: Vec<ChessMove<'a>>,
 }
 
-impl Default for TimeControl {
+impl<'a> Default for TimeControl<'a> {
     fn default() -> Self {
         Self {
             white_time_ms: None,
@@ -127,6 +135,43 @@ impl Default for TimeControl {
             depth: None,
             nodes: None,
             mate: None,
+            search_moves: Vec::new(),
+        }
+    }
+}
+
+impl<'a> TimeControl<'a> {
+    /// Whether any clock, depth, node or mate limit was given
+    pub fn has_limits(&self) -> bool {
+        self.white_time_ms.is_some()
+            || self.black_time_ms.is_some()
+            || self.white_increment_ms.is_some()
+            || self.black_increment_ms.is_some()
+            || self.moves_to_go.is_some()
+            || self.move_time_ms.is_some()
+            || self.depth.is_some()
+            || self.nodes.is_some()
+            || self.mate.is_some()
+    }
+
+    /// Copy the search limits without the borrowed `searchmoves` list
+    ///
+    /// Used when the time control has to outlive the command line it was
+    /// parsed from (e.g. when stored in the search context).
+    pub fn detach_limits(&self) -> TimeControl<'static> {
+        TimeControl {
+            white_time_ms: self.white_time_ms,
+            black_time_ms: self.black_time_ms,
+            white_increment_ms: self.white_increment_ms,
+            black_increment_ms: self.black_increment_ms,
+            moves_to_go: self.moves_to_go,
+            move_time_ms: self.move_time_ms,
+            infinite: self.infinite,
+            ponder: self.ponder,
+            depth: self.depth,
+            nodes: self.nodes,
+            mate: self.mate,
+            search_moves: Vec::new(),
         }
     }
 }
@@ -163,13 +208,40 @@ pub enum UCICommand<'a> {
     /// Set board position
     Position {
         /// Starting position: "startpos" or FEN string
-        position: Position<'a>,
+        position: Position,
         /// List of moves to apply
         moves: Vec<ChessMove<'a>>,
     },
 
     /// Start searching
-    Go(TimeControl),
+    Go(TimeControl<'a>),
+
+    /// Count move-generation leaf nodes to the given depth (`go perft N`)
+    Perft(u32),
+
+    /// List the current position's legal moves (`legalmoves [san]`, non-standard)
+    LegalMoves {
+        /// Report moves in SAN instead of UCI notation
+        san: bool,
+    },
+
+    /// Report the static evaluation of the current position (`eval`, non-standard)
+    Eval,
+
+    /// Report per-game and cumulative search statistics (`stats`, non-standard)
+    Stats,
+
+    /// Search the benchmark suite to a fixed depth and report nodes and NPS
+    /// (`bench [depth] [count]`, non-standard)
+    Bench {
+        /// Depth searched in every position
+        depth: u32,
+        /// Number of suite positions to search, from the start of the suite
+        positions: usize,
+    },
+
+    /// Print the board, FEN and side to move (`d`, non-standard, debug mode only)
+    Display,
 
     /// Stop current search
     Stop,
@@ -199,14 +271,14 @@ pub enum UCICommand<'a> {
 
 /// Position specification for position command
 #[derive(Debug, Clone, PartialEq)]
-pub enum Position<'a> {
+pub enum Position {
     /// Starting position
     StartPos,
-    /// Position from FEN string
-    Fen(&'a str),
+    /// Position from FEN string (all six fields, reconstructed from the command tokens)
+    Fen(String),
 }
 
-impl<'a> Position<'a> {
+impl Position {
     /// Validate FEN string format (basic validation)
     pub fn validate_fen(fen: &str) -> UCIResult<()> {
         let parts: Vec<&str> = fen.split_whitespace().collect();
@@ -295,6 +367,7 @@ impl<'a> RawCommand<'a> {
         if trimmed.is_empty() {
             return Err(UCIError::Protocol {
                 message: "Empty command line".to_string(),
+                token: None,
             });
         }
 
@@ -302,6 +375,7 @@ impl<'a> RawCommand<'a> {
         if trimmed.len() > 4096 {
             return Err(UCIError::Protocol {
                 message: format!("Command too long: {} chars (max 4096)", trimmed.len()),
+                token: None,
             });
         }
 
@@ -311,6 +385,7 @@ impl<'a> RawCommand<'a> {
         if tokens.is_empty() {
             return Err(UCIError::Protocol {
                 message: "No command tokens found".to_string(),
+                token: None,
             });
         }
 
@@ -384,6 +459,7 @@ impl SafeParse<u32> for u32 {
     fn safe_parse(s: &str, context: &str) -> UCIResult<u32> {
         s.parse().map_err(|_| UCIError::Protocol {
             message: format!("Invalid {} number: '{}'", context, s),
+            token: None,
         })
     }
 }
@@ -392,6 +468,7 @@ impl SafeParse<u64> for u64 {
     fn safe_parse(s: &str, context: &str) -> UCIResult<u64> {
         s.parse().map_err(|_| UCIError::Protocol {
             message: format!("Invalid {} number: '{}'", context, s),
+            token: None,
         })
     }
 }
@@ -400,6 +477,7 @@ impl SafeParse<i32> for i32 {
     fn safe_parse(s: &str, context: &str) -> UCIResult<i32> {
         s.parse().map_err(|_| UCIError::Protocol {
             message: format!("Invalid {} number: '{}'", context, s),
+            token: None,
         })
     }
 }
@@ -411,6 +489,7 @@ impl SafeParse<bool> for bool {
             "false" | "0" | "no" | "off" => Ok(false),
             _ => Err(UCIError::Protocol {
                 message: format!("Invalid {} boolean: '{}' (expected true/false)", context, s),
+                token: None,
             }),
         }
     }
@@ -433,6 +512,13 @@ mod tests {
         assert_eq!(mv.promotion, Some("q"));
     }
 
+    #[test]
+    fn test_chess_move_display_is_canonical() {
+        assert_eq!(ChessMove::new("e7e8Q").unwrap().to_string(), "e7e8q");
+        assert_eq!(ChessMove::new("e7e8q").unwrap().to_string(), "e7e8q");
+        assert_eq!(ChessMove::new("E2E4").unwrap().to_string(), "e2e4");
+    }
+
     #[test]
     fn test_chess_move_validation() {
         // Valid moves
@@ -3,15 +3,40 @@
 // This module provides the main UCIEngine struct that coordinates all UCI protocol
 // operations with thread-safe state management and async command processing.
 
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::sync::{broadcast, mpsc, oneshot, Notify};
+use tokio::task::AbortHandle;
 use tokio::time::{Duration, Instant};
 use tracing::{debug, error, info, instrument, warn};
 
-use crate::error::{UCIError, UCIResult};
+use crate::bridge::{Board, SearchEngine};
+use crate::error::{ContextualError, ResultExt, UCIError, UCIResult};
+use crate::ffi::ffi;
+use crate::runtime::async_utils;
+use crate::time::{
+    NodeLimit, PositionInfo, SearchParams, SearchProgress, SearchTimer, StandardTimePolicy,
+    TimePolicy, TimePolicyKind,
+};
+use crate::uci::adjudication::ScoreHistory;
+use crate::uci::bench::BENCH_POSITIONS;
 use crate::uci::commands::{TimeControl, UCICommand};
+use crate::uci::handlers::position::PositionCommandHandler;
+use crate::uci::hash_table::{EngineHashAllocator, HashAllocator, HashTable};
+use crate::uci::mate_search::{EngineMateSearcher, MateSearcher};
+use crate::uci::move_ordering::RootMoveOrdering;
+use crate::uci::options::{self, OptionKind, OPTION_SPECS};
 use crate::uci::parser::ZeroCopyParser;
-use crate::uci::state::{EngineConfig, EngineState, SearchContext, StateChangeEvent, UCIState};
+use crate::uci::response::ScoreKind;
+use crate::uci::response::UCIResponse;
+use crate::uci::sanitizer::{InputLimits, InputSanitizer};
+use crate::uci::search_threads::{worker_count, EngineSearchThreads, SearchThreads};
+use crate::uci::state::{
+    EngineConfig, EngineState, PlayingStyle, SearchContext, StateChangeEvent, StopReason, UCIState,
+};
+use crate::uci::telemetry::{TelemetryFrame, TelemetryLine, TELEMETRY_PREFIX};
+use crate::uci::warmup::{EngineHashWarmup, HashWarmup};
 
 /// Main UCI engine coordinator with async command processing
 pub struct UCIEngine {
@@ -21,12 +46,49 @@ pub struct UCIEngine {
     /// Command parser for UCI protocol (mutable for statistics tracking)
     parser: parking_lot::Mutex<ZeroCopyParser>,
 
+    /// Board position tracking for `position` commands
+    position_handler: parking_lot::Mutex<PositionCommandHandler>,
+
+    /// Transposition table sized by the `Hash` option
+    hash_table: Arc<HashTable>,
+
+    /// Hash table warmup run after allocation when `WarmupHash` is enabled
+    hash_warmup: Arc<dyn HashWarmup>,
+
+    /// Sizes the worker set of each search from the `Threads` option
+    search_threads: Arc<dyn SearchThreads>,
+
+    /// Background work started by `setoption`, awaited by the next `isready`
+    pending_config: parking_lot::Mutex<Vec<tokio::task::JoinHandle<()>>>,
+
+    /// Blocking search behind `go mate`
+    mate_searcher: Arc<dyn MateSearcher>,
+
+    /// Optional bias on the order root moves are searched in
+    root_move_ordering: Option<Box<dyn RootMoveOrdering>>,
+
+    /// Converts `go` clock parameters into search deadlines
+    time_policy: parking_lot::Mutex<Box<dyn TimePolicy>>,
+
+    /// Score of each move played this game, for resign/draw adjudication
+    score_history: Arc<parking_lot::Mutex<ScoreHistory>>,
+
+    /// `bestmove` line of the current search, sent by whichever of the search
+    /// task and `stop` finishes the search first
+    pending_bestmove: Arc<parking_lot::Mutex<Option<String>>>,
+
+    /// Spawned task of the current search, cancelled by `stop`
+    active_search: parking_lot::Mutex<Option<ActiveSearch>>,
+
+    /// Last raw command lines, dumped by the panic hook
+    command_log: Arc<CommandLog>,
+
     /// Command processing channel
     command_tx: mpsc::UnboundedSender<EngineCommand>,
     command_rx: Option<mpsc::UnboundedReceiver<EngineCommand>>,
 
     /// Response channel for sending UCI responses
-    response_tx: broadcast::Sender<String>,
+    response_tx: ResponseSender,
 
     /// Engine identification information
     id_info: EngineIdentification,
@@ -35,6 +97,210 @@ pub struct UCIEngine {
     startup_time: Instant,
 }
 
+/// Raw command lines kept for post-mortem debugging
+const COMMAND_LOG_CAPACITY: usize = 256;
+
+/// Most recent raw command lines, oldest first
+pub(crate) type CommandLog = parking_lot::Mutex<VecDeque<String>>;
+
+/// Handle on the task running the current search
+struct ActiveSearch {
+    /// Set by `stop`; the task checks it before reporting and never answers once set
+    stopped: Arc<AtomicBool>,
+    /// Nodes searched so far, kept up to date by the task
+    nodes: Arc<AtomicU64>,
+    /// Cancels the task at its next await point
+    task: AbortHandle,
+    /// Clock started by `ponderhit`, which answers for the still-running task
+    clock: Option<AbortHandle>,
+}
+
+impl ActiveSearch {
+    /// Spawn a search task that receives its own stop flag and adds to `nodes`
+    fn spawn<F, Fut>(nodes: Arc<AtomicU64>, search: F) -> Self
+    where
+        F: FnOnce(Arc<AtomicBool>, Arc<AtomicU64>) -> Fut,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let task = tokio::spawn(search(Arc::clone(&stopped), Arc::clone(&nodes))).abort_handle();
+        Self {
+            stopped,
+            nodes,
+            task,
+            clock: None,
+        }
+    }
+
+    /// Spawn the clock that answers for this search, sharing its stop flag and nodes
+    fn start_clock<F, Fut>(&mut self, clock: F)
+    where
+        F: FnOnce(Arc<AtomicBool>, Arc<AtomicU64>) -> Fut,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let clock = clock(Arc::clone(&self.stopped), Arc::clone(&self.nodes));
+        self.clock = Some(tokio::spawn(clock).abort_handle());
+    }
+
+    /// Whether the task is still searching
+    fn is_running(&self) -> bool {
+        !self.task.is_finished()
+    }
+
+    /// Nodes the task has searched so far
+    fn nodes(&self) -> u64 {
+        self.nodes.load(Ordering::Relaxed)
+    }
+
+    /// Tell the task to stop reporting and cancel it, along with its clock
+    fn stop(&self) {
+        self.stopped.store(true, Ordering::Release);
+        self.task.abort();
+        if let Some(clock) = &self.clock {
+            clock.abort();
+        }
+    }
+}
+
+/// Lines the output stream queues before it starts shedding search progress
+const OUTPUT_CAPACITY: usize = 1024;
+
+/// Whether `line` only reports search progress that a later line supersedes
+fn is_progress_line(line: &str) -> bool {
+    line.starts_with("info ")
+        && (!line.starts_with("info string ") || line.starts_with(TELEMETRY_PREFIX))
+}
+
+/// Queue behind [`OutputReceiver`], shared with the engine's senders
+struct OutputQueue {
+    lines: parking_lot::Mutex<VecDeque<String>>,
+    closed: AtomicBool,
+    ready: Notify,
+    capacity: usize,
+}
+
+impl OutputQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            lines: parking_lot::Mutex::new(VecDeque::with_capacity(capacity)),
+            closed: AtomicBool::new(false),
+            ready: Notify::new(),
+            capacity,
+        }
+    }
+
+    /// Queue `line`; `false` once the stream is closed
+    ///
+    /// A full queue makes room for progress by dropping its oldest progress
+    /// line, and drops new progress when there is none to replace. Every other
+    /// line is queued regardless, so `bestmove` and `readyok` are never lost.
+    fn push(&self, line: String) -> bool {
+        if self.closed.load(Ordering::Acquire) {
+            return false;
+        }
+
+        let mut lines = self.lines.lock();
+        if lines.len() >= self.capacity && is_progress_line(&line) {
+            match lines.iter().position(|queued| is_progress_line(queued)) {
+                Some(oldest) => {
+                    lines.remove(oldest);
+                }
+                None => return true,
+            }
+        }
+        lines.push_back(line);
+        drop(lines);
+
+        self.ready.notify_one();
+        true
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.ready.notify_one();
+    }
+}
+
+/// Receiving end of the engine's output stream
+///
+/// Lines arrive in send order. The queue holds at most [`OUTPUT_CAPACITY`]
+/// lines of search progress; a reader that falls behind misses the oldest
+/// `info` updates but still gets every `bestmove`, `readyok` and `info string`.
+pub struct OutputReceiver {
+    queue: Arc<OutputQueue>,
+}
+
+impl OutputReceiver {
+    /// Wait for the next line; `None` once the stream is closed and drained
+    ///
+    /// Cancel safe: a line is only taken from the queue when it is returned.
+    pub async fn recv(&mut self) -> Option<String> {
+        let queue = Arc::clone(&self.queue);
+        loop {
+            let ready = queue.ready.notified();
+            if let Some(line) = queue.lines.lock().pop_front() {
+                return Some(line);
+            }
+            if queue.closed.load(Ordering::Acquire) {
+                return None;
+            }
+            ready.await;
+        }
+    }
+
+    /// Take the next queued line without waiting
+    pub fn try_recv(&mut self) -> Option<String> {
+        self.queue.lines.lock().pop_front()
+    }
+}
+
+/// Sending end of an [`OutputReceiver`], which it closes when dropped
+struct OutputSender(Arc<OutputQueue>);
+
+impl Drop for OutputSender {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+/// Delivers engine output to the output stream and to broadcast subscribers
+///
+/// Both receive responses in the order they were sent. The output stream only
+/// sheds superseded search progress, so a slow writer delays the essential
+/// responses but never loses them; broadcast subscribers that fall more than
+/// its capacity behind skip ahead instead.
+#[derive(Clone)]
+struct ResponseSender {
+    broadcast: broadcast::Sender<String>,
+    output: Arc<parking_lot::Mutex<Option<OutputSender>>>,
+}
+
+impl ResponseSender {
+    fn new(capacity: usize) -> Self {
+        let (broadcast, _) = broadcast::channel(capacity);
+        Self {
+            broadcast,
+            output: Arc::new(parking_lot::Mutex::new(None)),
+        }
+    }
+
+    /// Send `response`; `false` if nobody is listening
+    fn send(&self, response: String) -> bool {
+        // Holding the lock keeps concurrent senders in the same order on both channels
+        let output = self.output.lock();
+        let delivered = output
+            .as_ref()
+            .is_some_and(|output_tx| output_tx.0.push(response.clone()));
+        self.broadcast.send(response).is_ok() || delivered
+    }
+
+    fn open_output(&self) -> OutputReceiver {
+        let queue = Arc::new(OutputQueue::new(OUTPUT_CAPACITY));
+        *self.output.lock() = Some(OutputSender(Arc::clone(&queue)));
+        OutputReceiver { queue }
+    }
+}
+
 /// Engine identification information for UCI protocol
 #[derive(Debug, Clone)]
 pub struct EngineIdentification {
@@ -78,16 +344,49 @@ pub enum EngineCommand {
 /// Search result information
 #[derive(Debug, Clone)]
 pub struct SearchResult {
+    /// Move to play; `0000` when there is none
     pub best_move: String,
+    /// Expected reply to ponder on, already checked to be legal
     pub ponder_move: Option<String>,
+    /// Depth of the last completed iteration
     pub depth: u32,
+    /// Score in centipawns from the side to move's point of view
     pub score: i32,
+    /// Nodes searched
     pub nodes: u64,
+    /// Time spent searching in milliseconds
     pub time_ms: u64,
+    /// Nodes per second
     pub nps: u64,
+    /// Principal variation, starting with the best move
     pub principal_variation: Vec<String>,
 }
 
+impl SearchResult {
+    /// Final `info` summary line followed by the `bestmove` line
+    pub fn to_responses(&self) -> Vec<UCIResponse> {
+        let info = UCIResponse::info()
+            .depth(u8::try_from(self.depth).unwrap_or(u8::MAX))
+            .score(self.score)
+            .time(Duration::from_millis(self.time_ms))
+            .nodes(self.nodes)
+            .nps(self.nps)
+            .pv(self.principal_variation.clone())
+            .build();
+
+        vec![info, self.best_move_response()]
+    }
+
+    /// The `bestmove` line, with the ponder move if there is one
+    pub fn best_move_response(&self) -> UCIResponse {
+        let response = UCIResponse::best_move(self.best_move.as_str());
+        match &self.ponder_move {
+            Some(ponder_move) => response.ponder(ponder_move.as_str()).build(),
+            None => response.build(),
+        }
+    }
+}
+
 impl UCIEngine {
     /// Create a new UCI engine with default configuration
     pub fn new() -> Self {
@@ -97,6 +396,7 @@ impl UCIEngine {
     /// Create a new UCI engine with custom configuration
     pub fn with_config(config: EngineConfig) -> Self {
         let state = Arc::new(UCIState::new());
+        let input_limits = Self::limits_for(&config);
 
         // Initialize state with provided configuration
         state
@@ -104,11 +404,31 @@ impl UCIEngine {
             .expect("Failed to set initial config");
 
         let (command_tx, command_rx) = mpsc::unbounded_channel();
-        let (response_tx, _) = broadcast::channel(64);
+        let response_tx = ResponseSender::new(64);
+        let command_log = Arc::new(parking_lot::Mutex::new(VecDeque::with_capacity(
+            COMMAND_LOG_CAPACITY,
+        )));
+        crate::register_command_log(&command_log);
 
         Self {
             state,
-            parser: parking_lot::Mutex::new(ZeroCopyParser::new()),
+            parser: parking_lot::Mutex::new(ZeroCopyParser::with_sanitizer(InputSanitizer::new(
+                input_limits,
+            ))),
+            position_handler: parking_lot::Mutex::new(
+                PositionCommandHandler::new().expect("Failed to create position handler"),
+            ),
+            hash_table: Arc::new(HashTable::new(Arc::new(EngineHashAllocator))),
+            hash_warmup: Arc::new(EngineHashWarmup),
+            search_threads: Arc::new(EngineSearchThreads),
+            pending_config: parking_lot::Mutex::new(Vec::new()),
+            mate_searcher: Arc::new(EngineMateSearcher),
+            root_move_ordering: None,
+            time_policy: parking_lot::Mutex::new(Box::new(StandardTimePolicy::default())),
+            score_history: Arc::new(parking_lot::Mutex::new(ScoreHistory::new())),
+            pending_bestmove: Arc::new(parking_lot::Mutex::new(None)),
+            active_search: parking_lot::Mutex::new(None),
+            command_log,
             command_tx,
             command_rx: Some(command_rx),
             response_tx,
@@ -117,6 +437,55 @@ impl UCIEngine {
         }
     }
 
+    /// Replace the hash allocator (used by tests to simulate running out of memory)
+    pub fn with_hash_allocator(mut self, hash_allocator: Arc<dyn HashAllocator>) -> Self {
+        self.hash_table = Arc::new(HashTable::new(hash_allocator));
+        self
+    }
+
+    /// Reorder root moves before each search, e.g. with `MorphyAggressiveOrdering`
+    pub fn with_root_move_ordering(mut self, ordering: Box<dyn RootMoveOrdering>) -> Self {
+        self.root_move_ordering = Some(ordering);
+        self
+    }
+
+    /// Replace the search thread sizing (used by tests to observe the worker count)
+    pub fn with_search_threads(mut self, search_threads: Arc<dyn SearchThreads>) -> Self {
+        self.search_threads = search_threads;
+        self
+    }
+
+    /// Replace the hash warmup implementation (used by tests to observe warmups)
+    pub fn with_hash_warmup(mut self, hash_warmup: Arc<dyn HashWarmup>) -> Self {
+        self.hash_warmup = hash_warmup;
+        self
+    }
+
+    /// Replace the `go mate` search (used by tests to simulate a stuck search)
+    pub fn with_mate_searcher(mut self, mate_searcher: Arc<dyn MateSearcher>) -> Self {
+        self.mate_searcher = mate_searcher;
+        self
+    }
+
+    /// Input limits derived from the engine configuration
+    ///
+    /// Front ends that validate input before handing it to the engine should
+    /// use these so they accept the same commands the engine does.
+    pub fn input_limits(&self) -> InputLimits {
+        Self::limits_for(&self.state.config())
+    }
+
+    fn limits_for(config: &EngineConfig) -> InputLimits {
+        InputLimits::default()
+            .with_max_moves_per_command(config.max_moves_per_command)
+            .with_chess960(config.chess960)
+    }
+
+    /// Replace the time allocation policy used by subsequent `go` commands
+    pub fn set_time_policy(&self, time_policy: Box<dyn TimePolicy>) {
+        *self.time_policy.lock() = time_policy;
+    }
+
     /// Initialize the engine and transition to ready state
     #[instrument(skip(self))]
     pub async fn initialize(&self) -> UCIResult<()> {
@@ -188,10 +557,16 @@ impl UCIEngine {
     #[instrument(skip(self, command_str))]
     async fn process_uci_command(&self, command_str: &str) -> UCIResult<()> {
         debug!(command = command_str, "Processing UCI command");
+        self.log_command(command_str);
 
         // Parse the command (need mutable lock for statistics)
         let command = self.parser.lock().parse_command(command_str)?;
 
+        // Loopback echo for test rigs, sent as a regular info string ahead of any reply
+        if self.state.config().echo_commands {
+            self.send_response(&format!("info string cmd: {}", command_str.trim()))?;
+        }
+
         // Dispatch to appropriate handler
         match command {
             UCICommand::Uci => self.handle_uci_command().await,
@@ -208,12 +583,36 @@ impl UCIEngine {
                 self.handle_position_command(position, moves).await
             }
             UCICommand::Go(time_control) => self.handle_go_command(time_control).await,
+            UCICommand::Perft(depth) => self.handle_perft_command(depth).await,
+            UCICommand::LegalMoves { san } => self.handle_legalmoves_command(san).await,
+            UCICommand::Eval => self.handle_eval_command().await,
+            UCICommand::Stats => self.handle_stats_command().await,
+            UCICommand::Bench { depth, positions } => {
+                self.handle_bench_command(depth, positions).await
+            }
+            UCICommand::Display => self.handle_display_command().await,
             UCICommand::Stop => self.handle_stop_command().await,
             UCICommand::PonderHit => self.handle_ponderhit_command().await,
             UCICommand::Quit => self.handle_quit_command().await,
         }
     }
 
+    /// Remember a raw command line, dropping the oldest once the log is full
+    fn log_command(&self, command_str: &str) {
+        let mut log = self.command_log.lock();
+        if log.len() == COMMAND_LOG_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(command_str.trim_end().to_string());
+    }
+
+    /// Get the last raw command lines processed this game, oldest first
+    ///
+    /// Holds at most 256 lines and starts over at `ucinewgame`.
+    pub fn recent_commands(&self) -> Vec<String> {
+        self.command_log.lock().iter().cloned().collect()
+    }
+
     /// Handle UCI identification command
     async fn handle_uci_command(&self) -> UCIResult<()> {
         self.send_response(&format!("id name {}", self.id_info.name))?;
@@ -235,6 +634,21 @@ impl UCIEngine {
 
     /// Handle engine ready query
     async fn handle_isready_command(&self) -> UCIResult<()> {
+        // GUIs sync on `isready` after `setoption`, so the options sent so far
+        // must be fully applied before answering; a running search is not waited on
+        let pending_config = std::mem::take(&mut *self.pending_config.lock());
+        if !pending_config.is_empty() {
+            debug!(
+                tasks = pending_config.len(),
+                "Waiting for option changes to finish"
+            );
+        }
+        for task in pending_config {
+            if let Err(e) = task.await {
+                warn!(error = %e, "Option change failed in the background");
+            }
+        }
+
         let current_state = self.state.current_state();
 
         if current_state.can_accept_commands() {
@@ -256,53 +670,305 @@ impl UCIEngine {
     async fn handle_setoption_command(&self, name: &str, value: Option<&str>) -> UCIResult<()> {
         debug!(name, value, "Setting UCI option");
 
-        match name.to_lowercase().as_str() {
-            "hash" => {
-                if let Some(value_str) = value {
-                    let hash_size: u32 = value_str.parse().map_err(|_| UCIError::Protocol {
-                        message: format!("Invalid hash size: {}", value_str),
-                    })?;
+        let name = match name.to_lowercase().as_str() {
+            // The parser splits the button name, leaving `Hash` as a flag
+            "clear" if value.is_none_or(|v| v.eq_ignore_ascii_case("hash")) => "Clear Hash",
+            _ => name,
+        };
+        let spec = match options::find_option(name) {
+            Some(spec) => spec,
+            // Debug-only options exist just for engines told `debug on`
+            None => match options::find_debug_option(name) {
+                Some(spec) if self.state.is_debug_mode() => spec,
+                Some(spec) => {
+                    return Err(UCIError::Configuration {
+                        message: format!("{} is only available in debug mode", spec.name),
+                    })
+                }
+                None => {
+                    return Err(UCIError::Configuration {
+                        message: format!("Unknown option: {}", name),
+                    })
+                }
+            },
+        };
+        let value = spec.validate(value.unwrap_or(""))?;
+
+        match spec.name {
+            "Hash" => {
+                let hash_size: u32 = value.as_spin();
+                let previous = self.state.config().hash_size_mb;
+                // The running search still probes the current table
+                if self.state.current_state().is_computing() {
+                    self.hash_table.defer_resize(previous, hash_size);
+                } else if hash_size != previous {
+                    self.hash_table.resize(hash_size)?;
+                }
+
+                self.state
+                    .update_config(|cfg| cfg.hash_size_mb = hash_size)?;
+
+                info!(hash_size_mb = hash_size, "Hash size updated");
+                if !self.state.current_state().is_computing() {
+                    self.spawn_hash_warmup();
+                }
+            }
+            "Threads" => {
+                let thread_count: u32 = value.as_spin();
+                self.state.update_config(|cfg| {
+                    cfg.thread_count = thread_count;
+                    cfg.multithread_enabled = thread_count > 1;
+                })?;
+
+                info!(thread_count, "Thread count updated");
+            }
+            "Ponder" => {
+                self.state
+                    .update_config(|cfg| cfg.ponder_enabled = value.as_bool())?;
+
+                info!(ponder_enabled = value.as_bool(), "Ponder setting updated");
+            }
+            "UCI_AnalyseMode" => {
+                self.state
+                    .update_config(|cfg| cfg.analysis_mode = value.as_bool())?;
 
-                    self.state.update_config(|cfg| {
-                        cfg.hash_size_mb = hash_size.clamp(1, 2048);
-                    })?;
+                info!(analysis_mode = value.as_bool(), "Analysis mode updated");
+            }
+            "UCI_Chess960" => {
+                let chess960 = value.as_bool();
+                self.state.update_config(|cfg| cfg.chess960 = chess960)?;
 
-                    info!(hash_size_mb = hash_size, "Hash size updated");
+                if !ffi::engine_set_chess960(chess960) {
+                    warn!(chess960, "C++ engine rejected UCI_Chess960 setting");
                 }
+                self.sync_fen_validation();
+
+                info!(chess960, "Chess960 mode updated");
+            }
+            "UCI_LimitStrength" => {
+                self.state
+                    .update_config(|cfg| cfg.limit_strength = value.as_bool())?;
+                self.sync_strength();
+
+                info!(limit_strength = value.as_bool(), "Strength limit updated");
+            }
+            "UCI_Elo" => {
+                self.state
+                    .update_config(|cfg| cfg.uci_elo = value.as_spin())?;
+                self.sync_strength();
+
+                info!(elo = value.as_spin::<u32>(), "Target Elo updated");
+            }
+            "TimePolicy" => {
+                let time_policy: TimePolicyKind = value.as_str().parse()?;
+                self.state
+                    .update_config(|cfg| cfg.time_policy = time_policy)?;
+
+                info!(%time_policy, "Time policy updated");
             }
-            "threads" => {
-                if let Some(value_str) = value {
-                    let thread_count: u32 = value_str.parse().map_err(|_| UCIError::Protocol {
-                        message: format!("Invalid thread count: {}", value_str),
-                    })?;
+            "MoveOverhead" => {
+                self.state
+                    .update_config(|cfg| cfg.move_overhead_ms = value.as_spin())?;
+
+                info!(
+                    move_overhead_ms = value.as_spin::<u64>(),
+                    "Move overhead updated"
+                );
+            }
+            "EchoCommands" => {
+                self.state
+                    .update_config(|cfg| cfg.echo_commands = value.as_bool())?;
+
+                info!(
+                    echo_commands = value.as_bool(),
+                    "Command echo setting updated"
+                );
+            }
+            "MoveSummary" => {
+                self.state
+                    .update_config(|cfg| cfg.move_summary = value.as_bool())?;
+
+                info!(
+                    move_summary = value.as_bool(),
+                    "Move summary setting updated"
+                );
+            }
+            "MorphyStyle" => {
+                let morphy_style = value.as_bool();
+                self.state
+                    .update_config(|cfg| cfg.morphy_style = morphy_style)?;
+
+                if !ffi::engine_set_morphy_style(morphy_style) {
+                    warn!(morphy_style, "C++ engine rejected MorphyStyle setting");
+                }
 
-                    self.state.update_config(|cfg| {
-                        cfg.thread_count = thread_count.clamp(1, 64);
-                        cfg.multithread_enabled = thread_count > 1;
-                    })?;
+                info!(morphy_style, "Morphy style updated");
+            }
+            "SacrificeThreshold" => {
+                let threshold: i32 = value.as_spin();
+                self.state
+                    .update_config(|cfg| cfg.sacrifice_threshold = threshold)?;
 
-                    info!(thread_count, "Thread count updated");
+                if !ffi::engine_set_sacrifice_threshold(threshold) {
+                    warn!(threshold, "C++ engine rejected SacrificeThreshold setting");
                 }
+
+                info!(
+                    sacrifice_threshold = threshold,
+                    "Sacrifice threshold updated"
+                );
+            }
+            "TacticalDepth" => {
+                let tactical_depth: u32 = value.as_spin();
+                self.state
+                    .update_config(|cfg| cfg.tactical_depth = tactical_depth)?;
+
+                info!(tactical_depth, "Tactical depth updated");
             }
-            "ponder" => {
-                if let Some(value_str) = value {
-                    let ponder_enabled = matches!(value_str.to_lowercase().as_str(), "true" | "1");
+            "Clear Hash" => self.clear_hash()?,
+            "Style" => {
+                let style: PlayingStyle = value.as_str().parse()?;
+                self.state.update_config(|cfg| style.apply(cfg))?;
+
+                let config = self.state.config();
+                let accepted = ffi::engine_set_morphy_style(config.morphy_style)
+                    && ffi::engine_set_sacrifice_threshold(config.sacrifice_threshold)
+                    && ffi::engine_set_tactical_depth(config.tactical_depth)
+                    && ffi::engine_set_contempt(config.contempt_factor);
+                if !accepted {
+                    warn!(%style, "C++ engine rejected Style preset");
+                }
 
-                    self.state.update_config(|cfg| {
-                        cfg.ponder_enabled = ponder_enabled;
-                    })?;
+                info!(%style, "Playing style updated");
+            }
+            "Contempt" => {
+                let contempt: i32 = value.as_spin();
+                self.state
+                    .update_config(|cfg| cfg.contempt_factor = contempt)?;
 
-                    info!(ponder_enabled, "Ponder setting updated");
+                if !ffi::engine_set_contempt(contempt) {
+                    warn!(contempt, "C++ engine rejected Contempt setting");
                 }
+
+                info!(contempt, "Contempt updated");
+            }
+            "ResignThreshold" => {
+                self.state
+                    .update_config(|cfg| cfg.resign_threshold = value.as_spin())?;
+
+                info!(
+                    resign_threshold = value.as_spin::<i32>(),
+                    "Resign threshold updated"
+                );
+            }
+            "DrawThreshold" => {
+                self.state
+                    .update_config(|cfg| cfg.draw_threshold = value.as_spin())?;
+
+                info!(
+                    draw_threshold = value.as_spin::<i32>(),
+                    "Draw threshold updated"
+                );
+            }
+            "DrawMoveCount" => {
+                self.state
+                    .update_config(|cfg| cfg.draw_move_count = value.as_spin())?;
+
+                info!(
+                    draw_move_count = value.as_spin::<u32>(),
+                    "Draw move count updated"
+                );
+            }
+            "SearchNps" => {
+                self.state
+                    .update_config(|cfg| cfg.search_nps = value.as_spin())?;
+
+                info!(
+                    search_nps = value.as_spin::<u64>(),
+                    "Simulated search speed updated"
+                );
+            }
+            "MaxMoveList" => {
+                self.state
+                    .update_config(|cfg| cfg.max_moves_per_command = value.as_spin())?;
+                self.sync_fen_validation();
+
+                info!(
+                    max_moves = value.as_spin::<usize>(),
+                    "Move list limit updated"
+                );
+            }
+            "MultiPV" => {
+                self.state
+                    .update_config(|cfg| cfg.multi_pv = value.as_spin())?;
+
+                info!(multi_pv = value.as_spin::<u32>(), "MultiPV updated");
+            }
+            "WarmupHash" => {
+                self.state
+                    .update_config(|cfg| cfg.warmup_hash = value.as_bool())?;
+
+                info!(warmup_hash = value.as_bool(), "Hash warmup setting updated");
+                self.spawn_hash_warmup();
             }
             _ => {
-                warn!(name, "Unknown UCI option");
+                warn!(name = spec.name, "Declared UCI option has no handler");
             }
         }
 
         Ok(())
     }
 
+    /// Apply input limits and FEN settings to the command parser and tracked board
+    fn sync_fen_validation(&self) {
+        let config = self.state.config();
+        self.parser
+            .lock()
+            .set_input_limits(Self::limits_for(&config));
+        self.position_handler
+            .lock()
+            .board_mut()
+            .set_chess960(config.chess960);
+    }
+
+    /// Forward the playing strength to the C++ search; `UCI_Elo` only applies while limiting
+    fn sync_strength(&self) {
+        let elo = self.state.config().strength_elo().unwrap_or(0);
+        if !ffi::engine_set_strength(elo) {
+            warn!(elo, "C++ engine rejected strength setting");
+        }
+    }
+
+    /// Wipe the C++ transposition table for the `Clear Hash` button
+    fn clear_hash(&self) -> UCIResult<()> {
+        if !ffi::engine_clear_hash() {
+            return Err(UCIError::Engine {
+                message: "Failed to clear C++ engine hash tables".to_string(),
+            });
+        }
+
+        self.state.record_hash_clear();
+        info!("Hash tables cleared");
+        Ok(())
+    }
+
+    /// Warm up the hash table on a blocking thread if `WarmupHash` is enabled
+    ///
+    /// Returns immediately; the warmup runs off the event loop so `isready` and
+    /// other commands stay responsive while pages are being touched.
+    fn spawn_hash_warmup(&self) {
+        let config = self.state.config();
+        if !config.warmup_hash {
+            return;
+        }
+
+        let hash_warmup = Arc::clone(&self.hash_warmup);
+        let size_mb = config.hash_size_mb;
+        debug!(size_mb, "Scheduling hash warmup");
+        let task = tokio::task::spawn_blocking(move || hash_warmup.warm_up(size_mb));
+        self.pending_config.lock().push(task);
+    }
+
     /// Handle registration command (no-op for open source engine)
     async fn handle_register_command(
         &self,
@@ -319,11 +985,20 @@ impl UCIEngine {
     async fn handle_ucinewgame_command(&self) -> UCIResult<()> {
         info!("Starting new game");
 
+        // A search of the previous game must not report into the new one
+        if self.state.current_state().is_computing() {
+            self.stop_search().await?;
+        }
+
         // Reset engine state but keep configuration
         self.state.reset()?;
+        self.state.start_new_game();
+        self.score_history.lock().clear();
+        self.command_log.lock().clear();
 
-        // TODO: Clear hash tables and reset position
-        // This will be implemented when we integrate with the C++ engine
+        // Stale transposition entries and repetition keys belong to the old game
+        self.clear_hash()?;
+        self.position_handler.lock().new_game()?;
 
         Ok(())
     }
@@ -331,175 +1006,1012 @@ impl UCIEngine {
     /// Handle position command
     async fn handle_position_command(
         &self,
-        _position: crate::uci::commands::Position<'_>,
-        _moves: Vec<crate::uci::commands::ChessMove<'_>>,
+        position: crate::uci::commands::Position,
+        moves: Vec<crate::uci::commands::ChessMove<'_>>,
     ) -> UCIResult<()> {
         debug!("Setting board position");
 
-        // TODO: Set board position and apply moves
-        // This will be implemented when we integrate with the C++ engine
+        let command = UCICommand::Position { position, moves };
+        let result = self
+            .position_handler
+            .lock()
+            .handle_position_command_with_context(&command)
+            .with_operation("position command");
+
+        result.map_err(|e| {
+            // Reporting is best effort; the original error is what matters
+            let _ = self.report_error(&e);
+            e.error
+        })
+    }
 
+    /// Report a failed command to the GUI as `info string` lines
+    ///
+    /// Only the error itself is sent normally; debug mode adds the full context
+    /// chain so bug reports include where the failure originated.
+    fn report_error(&self, error: &ContextualError) -> UCIResult<()> {
+        for line in error.report_lines(self.state.is_debug_mode()) {
+            self.send_response(&format!("info string {}", line))?;
+        }
         Ok(())
     }
 
-    /// Handle go command to start search
-    async fn handle_go_command(&self, time_control: TimeControl) -> UCIResult<()> {
-        info!(time_control = ?time_control, "Starting search");
-
-        let search_context = SearchContext {
-            start_time: std::time::Instant::now(),
-            time_control,
-            max_depth: None,
-            max_nodes: None,
-            is_infinite: false,
-            is_ponder: false,
-        };
-
-        // Start search
-        self.state.start_search(search_context)?;
-
-        // TODO: Actually perform the search
-        // For now, simulate a quick search and return a dummy move
-        tokio::spawn({
-            let state = Arc::clone(&self.state);
-            let response_tx = self.response_tx.clone();
+    /// Handle `go perft N`: report leaf node counts per root move for the current position
+    ///
+    /// The count runs on a blocking thread over a copy of the position, so
+    /// the position lock is not held while it walks the tree.
+    async fn handle_perft_command(&self, depth: u32) -> UCIResult<()> {
+        info!(depth, "Running perft");
 
-            async move {
-                // Simulate search time
-                tokio::time::sleep(Duration::from_millis(100)).await;
+        let mut board = Board::new()?;
+        board.set_from_fen(&self.current_fen()?)?;
 
-                // Complete search
-                if let Err(e) = state.complete_search(1000) {
-                    error!(error = ?e, "Failed to complete search");
-                    return;
-                }
+        let divide = tokio::task::spawn_blocking(move || board.perft_divide(depth))
+            .await
+            .map_err(|e| UCIError::Internal {
+                message: format!("Perft task failed: {}", e),
+            })??;
+
+        let mut total: u64 = 0;
+        for (uci_move, nodes) in &divide {
+            self.send_response(&format!("{}: {}", uci_move, nodes))?;
+            total += nodes;
+        }
 
-                // Send best move (dummy for now)
-                let _ = response_tx.send("bestmove e2e4".to_string());
-            }
-        });
+        if depth == 0 {
+            total = 1;
+        }
 
+        self.send_response("")?;
+        self.send_response(&format!("Nodes searched: {}", total))?;
         Ok(())
     }
 
-    /// Handle stop command
-    async fn handle_stop_command(&self) -> UCIResult<()> {
-        self.stop_search().await
-    }
-
-    /// Handle ponder hit command
-    async fn handle_ponderhit_command(&self) -> UCIResult<()> {
-        debug!("Ponder hit received");
-
-        let current_state = self.state.current_state();
-        if current_state == EngineState::Pondering {
-            self.state
-                .transition_to(EngineState::Searching, "Ponder hit - converting to search")?;
+    /// Handle `bench`: search the first `positions` suite positions to `depth`
+    ///
+    /// Runs on a scratch board, so the tracked position is left untouched.
+    async fn handle_bench_command(&self, depth: u32, positions: usize) -> UCIResult<()> {
+        info!(depth, positions, "Running bench");
+
+        let suite = &BENCH_POSITIONS[..positions.min(BENCH_POSITIONS.len())];
+        let mut board = Board::new()?;
+        let start_time = Instant::now();
+        let mut total: u64 = 0;
+        for (index, fen) in suite.iter().enumerate() {
+            board.set_from_fen(fen)?;
+            let nodes = SearchEngine::bench_search(&board, depth)?;
+            self.send_response(&format!(
+                "info string bench position {}/{} nodes {} fen {}",
+                index + 1,
+                suite.len(),
+                nodes,
+                fen
+            ))?;
+            total += nodes;
         }
 
+        let elapsed_ms = (start_time.elapsed().as_millis() as u64).max(1);
+        self.send_response("")?;
+        self.send_response(&format!("Total time (ms): {}", elapsed_ms))?;
+        self.send_response(&format!("Nodes searched: {}", total))?;
+        self.send_response(&format!("Nodes/second: {}", total * 1000 / elapsed_ms))?;
         Ok(())
     }
 
-    /// Handle quit command
-    async fn handle_quit_command(&self) -> UCIResult<()> {
-        info!("Quit command received");
-        self.shutdown().await
+    /// Handle `eval`: report the static evaluation of the current position
+    async fn handle_eval_command(&self) -> UCIResult<()> {
+        let score = {
+            let handler = self.position_handler.lock();
+            SearchEngine::new()?.static_eval(handler.board())?
+        };
+
+        self.send_response(&format!("info string eval cp {}", score))
     }
 
-    /// Stop current search operation
-    async fn stop_search(&self) -> UCIResult<()> {
-        let current_state = self.state.current_state();
+    /// Handle `stats`: report search totals for this game and all games
+    async fn handle_stats_command(&self) -> UCIResult<()> {
+        let stats = self.state.statistics();
 
-        if current_state.is_computing() {
-            info!("Stopping current search");
+        self.send_response(&format!(
+            "info string stats game {} {}",
+            stats.games_played, stats.game_totals
+        ))?;
+        self.send_response(&format!(
+            "info string stats total games {} {}",
+            stats.games_played, stats.cumulative_totals
+        ))
+    }
 
-            // TODO: Signal C++ engine to stop search
+    /// Handle `d`: print the board, FEN and side to move as info strings
+    async fn handle_display_command(&self) -> UCIResult<()> {
+        // Non-standard output; keep it out of normal GUI sessions
+        if !self.state.is_debug_mode() {
+            return Err(UCIError::Protocol {
+                message: "d command is only available in debug mode".to_string(),
+                token: None,
+            });
+        }
 
-            // Complete search with current results
-            self.state.complete_search(0)?;
+        let (art, fen) = {
+            let handler = self.position_handler.lock();
+            let board = handler.board();
+            (board.to_ascii()?, board.get_fen()?)
+        };
 
-            // Send stop confirmation (best move should have been sent already)
-            self.send_response("bestmove (none)")?;
-        } else {
-            debug!(state = ?current_state, "Stop command received but not searching");
+        for line in art.lines() {
+            self.send_response(&format!("info string {}", line.trim_end()))?;
         }
+        self.send_response(&format!("info string Fen: {}", fen))?;
 
-        Ok(())
+        let side_to_move = if fen.split_whitespace().nth(1) == Some("b") {
+            "black"
+        } else {
+            "white"
+        };
+        self.send_response(&format!("info string Side to move: {}", side_to_move))
     }
 
-    /// Shutdown the engine gracefully
-    async fn shutdown(&self) -> UCIResult<()> {
-        info!("Shutting down UCI engine");
-
-        self.state
-            .transition_to(EngineState::Stopping, "Engine shutdown requested")?;
+    /// Handle `legalmoves [san]`: list the current position's legal moves for UI display
+    async fn handle_legalmoves_command(&self, san: bool) -> UCIResult<()> {
+        let moves = {
+            let handler = self.position_handler.lock();
+            let board = handler.board();
+            let uci_moves = board.legal_moves()?;
+
+            if san {
+                uci_moves
+                    .iter()
+                    .map(|mv| board.uci_to_san(mv))
+                    .collect::<UCIResult<Vec<_>>>()?
+            } else {
+                uci_moves
+            }
+        };
 
-        // Stop any ongoing search
-        if self.state.current_state().is_computing() {
-            let _ = self.stop_search().await;
+        if moves.is_empty() {
+            self.send_response("info string legalmoves: none")
+        } else {
+            self.send_response(&format!("info string legalmoves: {}", moves.join(",")))
         }
-
-        Ok(())
     }
 
-    /// Reset engine to clean state
-    async fn reset(&self) -> UCIResult<()> {
-        info!("Resetting UCI engine");
+    /// Handle go command to start search
+    async fn handle_go_command(&self, time_control: TimeControl<'_>) -> UCIResult<()> {
+        info!(time_control = ?time_control, "Starting search");
 
-        // Stop any ongoing search
-        if self.state.current_state().is_computing() {
-            let _ = self.stop_search().await;
+        // Caps how far the C++ search extends and resolves captures past the depth
+        let tactical_depth = self.state.config().tactical_depth;
+        if !ffi::engine_set_tactical_depth(tactical_depth) {
+            warn!(tactical_depth, "C++ engine rejected TacticalDepth setting");
         }
 
-        // Reset state
-        self.state.reset()?;
+        // `Threads` changed during the previous search only takes effect here
+        let thread_count = worker_count(self.state.config().thread_count);
+        if !self.search_threads.set_threads(thread_count) {
+            warn!(thread_count, "C++ engine rejected thread count");
+        }
 
-        Ok(())
-    }
+        // Restricted root moves must outlive the command line they were parsed from
+        let search_moves: Vec<String> = time_control
+            .search_moves
+            .iter()
+            .map(|chess_move| chess_move.to_string())
+            .collect();
 
-    /// Send UCI options for the uci command
-    fn send_uci_options(&self) -> UCIResult<()> {
-        let config = self.state.config();
+        let position_info = PositionInfo::from_board(self.position_handler.lock().board())?;
+        let mut params = SearchParams::from(&time_control);
+        params.move_overhead_ms = self.state.config().move_overhead_ms;
 
-        // Hash size option
-        self.send_response(&format!(
-            "option name Hash type spin default {} min 1 max 2048",
-            config.hash_size_mb
-        ))?;
+        let node_limit = NodeLimit::new(params.nodes);
+        if !ffi::engine_set_node_limit(node_limit.max_nodes().unwrap_or(u64::MAX)) {
+            warn!(?node_limit, "C++ engine rejected node limit");
+        }
 
-        // Thread count option
-        self.send_response(&format!(
-            "option name Threads type spin default {} min 1 max 64",
-            config.thread_count
-        ))?;
+        // The TimePolicy option overrides the engine's own policy
+        let policy_kind = self.state.config().time_policy;
+        let forced_policy = policy_kind.forced_policy();
+        let time_limits = {
+            let auto_policy = self.time_policy.lock();
+            let policy = forced_policy.as_deref().unwrap_or(auto_policy.as_ref());
+            if self.state.is_debug_mode() {
+                self.send_response(&format!(
+                    "info string timepolicy {} ({})",
+                    policy.name(),
+                    policy_kind
+                ))?;
+            }
 
-        // Ponder option
-        self.send_response(&format!(
-            "option name Ponder type check default {}",
-            config.ponder_enabled
-        ))?;
+            // A forced infinite policy turns every search into analysis until `stop`
+            if policy_kind == TimePolicyKind::Infinite {
+                params.infinite = true;
+            }
+            params.time_limits(&position_info, policy)
+        };
 
-        // Analysis mode option
-        self.send_response(&format!(
-            "option name UCI_AnalyseMode type check default {}",
-            config.analysis_mode
-        ))?;
+        // Searches without a clock (depth/nodes/mate) keep the short simulated search
+        let deadline = if time_limits.is_infinite() {
+            Duration::from_millis(100)
+        } else {
+            time_limits.hard_limit
+        };
+        debug!(?params, ?deadline, "Search parameters resolved");
+
+        let start_time = Instant::now();
+        let timer = SearchTimer::new(time_limits);
+        let search_context = SearchContext {
+            start_time: start_time.into_std(),
+            time_control: time_control.detach_limits(),
+            search_moves: search_moves.clone(),
+            max_depth: params.depth,
+            max_nodes: params.nodes,
+            is_infinite: params.infinite,
+            is_ponder: params.ponder,
+        };
+
+        // One line per requested principal variation, best first
+        let multi_pv = self.state.config().multi_pv as usize;
+        let mut lines = self.rank_root_moves(&search_moves)?;
+
+        // `go mate N` moves a proven mate to the front and reports it as a mate
+        // score; without one the search reports its best move as usual
+        let mate_plies = match params.mate {
+            Some(max_moves) => {
+                let max_nodes = params.nodes.unwrap_or(MATE_SEARCH_NODES);
+                match self
+                    .run_mate_search(max_moves, max_nodes, &time_limits)
+                    .await
+                {
+                    Ok(found) => found,
+                    Err(e @ UCIError::Timeout { .. }) => {
+                        // Abandon the stuck search; the GUI still needs an answer
+                        warn!(error = %e, "Mate search did not return in time");
+                        self.send_response("bestmove (none)")?;
+                        return Err(e);
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            None => None,
+        }
+        .and_then(|(moves, mating_move)| {
+            // searchmoves may exclude the mating move
+            promote_root_move(&mut lines, &mating_move).then_some(2 * moves - 1)
+        });
+
+        let root_score = mate_plies.map(|plies| ScoreKind::mate_in_plies(plies as i32));
+
+        // Static ranking never changes its best move, so every simulated iteration
+        // is stable. Analysis mode ignores the policy and runs to the depth limit.
+        let analysis_mode = self.state.config().analysis_mode;
+        let auto_policy = self.time_policy.lock();
+        let policy = forced_policy.as_deref().unwrap_or(auto_policy.as_ref());
+        let root_moves = lines.len() as u64;
+        let target_depth = match params.depth.or(mate_plies) {
+            Some(depth) => depth.max(1),
+            // Each iteration visits one node per root move, so a node budget
+            // alone searches as deep as it reaches; `go nodes 0` not at all
+            None => node_limit
+                .batches(root_moves)
+                .map_or(1, |batches| u32::try_from(batches).unwrap_or(u32::MAX)),
+        };
+        let best_score = lines.first().map_or(0, |(_, score)| *score);
+        let mut depth = target_depth.min(1);
+        let mut stopped_early = false;
+        while depth < target_depth {
+            let progress = SearchProgress {
+                elapsed: start_time.elapsed(),
+                depth,
+                nodes: lines.len() as u64 * u64::from(depth),
+                best_move_stability: depth - 1,
+                score: best_score,
+                prev_score: best_score,
+            };
+            if !analysis_mode && policy.should_stop_early(&progress, &time_limits) {
+                stopped_early = true;
+                break;
+            }
+            depth += 1;
+        }
+        let depth = u8::try_from(depth).unwrap_or(u8::MAX);
+        drop(auto_policy);
+
+        // The C++ search runs alongside the simulated one until its last depth is
+        // reported, measuring the counters added to the statistics
+        if !ffi::engine_set_depth_limit(u32::from(depth)) {
+            warn!(depth, "C++ engine rejected depth limit");
+        }
+        // Without a clock it runs until stopped; the simulated deadline is not a
+        // budget it could plan its iterations around
+        let engine_time = (!time_limits.is_infinite()).then_some(time_limits.hard_limit);
+        let mut engine_board = Board::new()?;
+        engine_board.set_from_fen(&self.current_fen()?)?;
+        // Root moves in the order the search visits them, for `currmove` reports
+        let search_order = self.order_root_moves(&lines);
+        lines.truncate(multi_pv);
+        let currmove_min_depth = self.state.config().currmove_min_depth;
+        let info_interval = Duration::from_millis(self.state.config().info_interval_ms);
+        let move_summary = self.state.config().move_summary;
+        let telemetry_json = self.state.config().telemetry_json;
+
+        // The simulated search always runs into its first limit
+        let stop_reason = if mate_plies.is_some() {
+            StopReason::Mate
+        } else if stopped_early {
+            StopReason::SoftLimit
+        } else if node_limit.reached(root_moves * u64::from(depth)) {
+            StopReason::Nodes
+        } else {
+            limit_stop_reason(&params, &time_limits)
+        };
+
+        let best_pv: Vec<String> = lines
+            .first()
+            .map(|(mv, _)| vec![mv.clone()])
+            .unwrap_or_default();
+        let mut result = self.search_result(
+            &best_pv,
+            u32::from(depth),
+            best_score,
+            root_moves * u64::from(depth),
+        )?;
+        let bestmove = result.best_move_response().to_string();
+        let pv_fen = self.position_handler.lock().board().pv_end_fen(&best_pv)?;
+
+        // Only game moves count towards adjudication, not analysis or ponder searches
+        let game_score = lines
+            .first()
+            .filter(|_| !params.infinite && !params.ponder)
+            .map(|(_, score)| *score);
+
+        // Start search
+        self.state.start_search(search_context)?;
+        let analyzing = self.state.current_state() == EngineState::Analyzing;
+        let pondering = self.state.current_state() == EngineState::Pondering;
+
+        // Static ranking has found the best move before the first iteration, so
+        // a `stop` arriving at any point from here on can answer with it
+        *self.pending_bestmove.lock() = Some(bestmove);
+
+        // In debug mode `SearchNps` makes the node count follow the clock, so tests
+        // can pin down how node and time limits interact
+        let search_nps = if self.state.is_debug_mode() {
+            self.state.config().search_nps
+        } else {
+            0
+        };
+        let count_nodes = move |iteration: u8, elapsed: Duration| match search_nps {
+            0 => root_moves * u64::from(iteration),
+            nps => {
+                u64::try_from(elapsed.as_micros() * u128::from(nps) / 1_000_000).unwrap_or(u64::MAX)
+            }
+        };
+
+        // TODO: Report the moves and scores of the C++ search
+        // For now, simulate a quick search over the statically ranked root moves
+        let search = ActiveSearch::spawn(Arc::new(AtomicU64::new(0)), {
+            let state = Arc::clone(&self.state);
+            let response_tx = self.response_tx.clone();
+            let pending_bestmove = Arc::clone(&self.pending_bestmove);
+            let hash_table = Arc::clone(&self.hash_table);
+            let score_history = Arc::clone(&self.score_history);
+
+            move |stopped: Arc<AtomicBool>, nodes_counter: Arc<AtomicU64>| async move {
+                let mut engine_search = Some(tokio::task::spawn_blocking(move || {
+                    SearchEngine::search(&engine_board, engine_time)
+                }));
+
+                // Simulate search time, spreading the iterations up to the policy's
+                // hard limit and reporting each one no more often than the interval
+                let mut last_report: Option<Instant> = None;
+                let mut searched_nodes = 0;
+                let mut stop_reason = stop_reason;
+                let hard_deadline =
+                    (!time_limits.is_infinite()).then(|| start_time + time_limits.hard_limit);
+                for iteration in 1..=depth {
+                    // The hard limit ends the search no matter how far it planned to go;
+                    // past the soft limit the iteration in progress finishes but no new
+                    // one starts, and neither does one once the node budget is spent
+                    if node_limit.reached(count_nodes(iteration - 1, start_time.elapsed())) {
+                        stop_reason = StopReason::Nodes;
+                        break;
+                    }
+                    if iteration > 1 && timer.should_stop() {
+                        stop_reason = StopReason::HardLimit;
+                        break;
+                    }
+                    if iteration > 1 && timer.soft_limit_reached() {
+                        stop_reason = StopReason::SoftLimit;
+                        break;
+                    }
+
+                    let started_at =
+                        start_time + deadline * u32::from(iteration - 1) / u32::from(depth);
+                    let completed_at =
+                        start_time + deadline * u32::from(iteration) / u32::from(depth);
+
+                    // Deep iterations take long enough that GUIs show which root
+                    // move is being searched
+                    if iteration >= currmove_min_depth {
+                        let slice = (completed_at - started_at) / search_order.len().max(1) as u32;
+                        for (index, root_move) in search_order.iter().enumerate() {
+                            tokio::time::sleep_until(started_at + slice * index as u32).await;
+                            if stopped.load(Ordering::Acquire) {
+                                return;
+                            }
+                            let info = UCIResponse::info()
+                                .depth(iteration)
+                                .currmove(root_move.clone())
+                                .currmovenumber((index + 1) as u16)
+                                .build();
+                            let _ = response_tx.send(info.to_string());
+                        }
+                    }
+                    // The hard limit aborts the iteration at once, discarding it
+                    if let Some(hard_deadline) = hard_deadline.filter(|hard| *hard < completed_at) {
+                        tokio::time::sleep_until(hard_deadline).await;
+                        if stopped.load(Ordering::Acquire) {
+                            return;
+                        }
+                        stop_reason = StopReason::HardLimit;
+                        break;
+                    }
+                    tokio::time::sleep_until(completed_at).await;
+                    if stopped.load(Ordering::Acquire) {
+                        return;
+                    }
+
+                    let now = Instant::now();
+                    searched_nodes = count_nodes(iteration, now - start_time);
+                    nodes_counter.store(searched_nodes, Ordering::Relaxed);
+                    let throttled = last_report.is_some_and(|last| now - last < info_interval);
+                    if iteration < depth && throttled {
+                        continue;
+                    }
+                    last_report = Some(now);
+
+                    // The last depth ends the C++ search, whose selective depth is
+                    // reported only if it completed that depth too
+                    let mut seldepth = None;
+                    if let Some(search) = engine_search.take_if(|_| iteration == depth) {
+                        if finish_engine_search(search).await >= u32::from(iteration) {
+                            seldepth = u8::try_from(SearchEngine::seldepth()).ok();
+                        }
+                        if stopped.load(Ordering::Acquire) {
+                            return;
+                        }
+                    }
+
+                    let elapsed = now - start_time;
+                    let nodes = searched_nodes;
+                    let nps = nodes * 1000 / (elapsed.as_millis() as u64).max(1);
+                    let hashfull = SearchEngine::hashfull();
+                    let mut telemetry_lines = Vec::new();
+                    for (index, (root_move, score)) in lines.iter().enumerate() {
+                        let score = match root_score {
+                            Some(root_score) if index == 0 => root_score,
+                            _ => ScoreKind::Cp(*score),
+                        };
+                        let mut info = UCIResponse::info()
+                            .depth(iteration)
+                            .multipv((index + 1) as u16)
+                            .score_kind(score)
+                            .time(elapsed)
+                            .nodes(nodes)
+                            .nps(nps)
+                            .hashfull(hashfull)
+                            .pv(vec![root_move.clone()]);
+                        if let Some(seldepth) = seldepth {
+                            info = info.seldepth(seldepth);
+                        }
+                        let _ = response_tx.send(info.build().to_string());
+                        if telemetry_json {
+                            telemetry_lines
+                                .push(TelemetryLine::new(vec![root_move.clone()], score));
+                        }
+                    }
+
+                    if telemetry_json {
+                        let frame = TelemetryFrame {
+                            depth: iteration,
+                            seldepth,
+                            nodes,
+                            nps,
+                            hashfull,
+                            multipv: telemetry_lines,
+                        };
+                        match frame.to_info_string() {
+                            Ok(line) => {
+                                let _ = response_tx.send(line);
+                            }
+                            Err(e) => warn!(error = %e, "Dropped telemetry frame"),
+                        }
+                    }
+                }
+                if let Some(search) = engine_search {
+                    finish_engine_search(search).await;
+                }
+                state.set_pv_fen(pv_fen);
+
+                let elapsed_ms = start_time.elapsed().as_millis() as u64;
+                result.nodes = searched_nodes;
+                result.time_ms = elapsed_ms;
+                result.nps = result.nodes * 1000 / elapsed_ms.max(1);
+                debug!(?result, "Search finished");
+
+                // Analysis has no time watchdog; it runs until `stop`, as does
+                // pondering unless `ponderhit` starts the clock
+                if analyzing || pondering {
+                    return;
+                }
+
+                // Holding the pending move keeps a concurrent `stop` from also answering
+                let mut pending = pending_bestmove.lock();
+                if stopped.load(Ordering::Acquire) {
+                    return;
+                }
+                let Some(bestmove) = pending.take() else {
+                    return;
+                };
+
+                // Complete search
+                if let Err(e) = complete_search(&state, &hash_table, searched_nodes) {
+                    error!(error = ?e, "Failed to complete search");
+                    return;
+                }
+
+                if state.is_debug_mode() {
+                    let _ = response_tx.send(format!("info string stopreason {}", stop_reason));
+                }
+
+                // The score joins the history only once its move is played
+                let adjudication = game_score.and_then(|score| {
+                    let mut history = score_history.lock();
+                    history.record(score);
+                    history.adjudicate(&state.config())
+                });
+                if let Some(adjudication) = adjudication {
+                    let _ = response_tx.send(format!("info string {}", adjudication));
+                }
+
+                let _ = response_tx.send(bestmove);
+                drop(pending);
+
+                // One greppable line per move for logs, after the bestmove itself
+                if move_summary {
+                    if let Some((best_move, score)) = lines.first() {
+                        let _ = response_tx.send(format!(
+                            "info string summary depth {} score cp {} nodes {} time {} move {}",
+                            depth,
+                            score,
+                            result.nodes,
+                            start_time.elapsed().as_millis(),
+                            best_move
+                        ));
+                    }
+                }
+            }
+        });
+        *self.active_search.lock() = Some(search);
+
+        Ok(())
+    }
+
+    /// Run the `go mate` search on a blocking thread
+    ///
+    /// With a clock the search is abandoned once the hard limit plus a grace
+    /// margin has passed, returning `UCIError::Timeout`; the blocking thread
+    /// finishes on its own on a copy of the position. Without a clock only the
+    /// node budget bounds the search.
+    async fn run_mate_search(
+        &self,
+        max_moves: u32,
+        max_nodes: u64,
+        time_limits: &crate::time::TimeLimits,
+    ) -> UCIResult<Option<(u32, String)>> {
+        let mut board = Board::new()?;
+        board.set_from_fen(&self.current_fen()?)?;
+
+        let mate_searcher = Arc::clone(&self.mate_searcher);
+        let search = tokio::task::spawn_blocking(move || {
+            mate_searcher.search_mate(&board, max_moves, max_nodes)
+        });
+
+        let joined = if time_limits.is_infinite() {
+            search.await
+        } else {
+            let timeout = time_limits.hard_limit.saturating_add(SEARCH_TIMEOUT_GRACE);
+            async_utils::with_timeout(search, timeout.as_millis() as u64).await?
+        };
+        joined.map_err(|e| UCIError::Internal {
+            message: format!("Mate search task failed: {}", e),
+        })?
+    }
+
+    /// Build the result of a search for a principal variation
+    ///
+    /// The second PV move becomes the ponder move, but only if it is legal once
+    /// the best move has been played; GUIs start a broken ponder search otherwise.
+    /// An empty PV (mate, stalemate or no legal searchmoves) reports a null move.
+    fn search_result(
+        &self,
+        pv: &[String],
+        depth: u32,
+        score: i32,
+        nodes: u64,
+    ) -> UCIResult<SearchResult> {
+        let best_move = pv.first().map_or("0000", String::as_str);
+
+        let ponder_move = match pv.get(1) {
+            Some(ponder_move)
+                if !self.state.config().check_ponder_move
+                    || self.is_legal_reply(best_move, ponder_move)? =>
+            {
+                Some(ponder_move.clone())
+            }
+            Some(ponder_move) => {
+                debug!(best_move = %best_move, ponder_move = %ponder_move, "Dropping illegal ponder move");
+                None
+            }
+            None => None,
+        };
+
+        Ok(SearchResult {
+            best_move: best_move.to_string(),
+            ponder_move,
+            depth,
+            score,
+            nodes,
+            time_ms: 0,
+            nps: 0,
+            principal_variation: pv.to_vec(),
+        })
+    }
+
+    /// Whether `reply` is legal after `best_move` is played in the current position
+    fn is_legal_reply(&self, best_move: &str, reply: &str) -> UCIResult<bool> {
+        // Work on a copy so the tracked position is never disturbed
+        let mut scratch = Board::new()?;
+        scratch.set_from_fen(&self.current_fen()?)?;
+        if scratch.make_move(best_move).is_err() {
+            return Ok(false);
+        }
+
+        // Check against generated moves; is_valid_move only checks the move can be applied
+        Ok(scratch.legal_moves()?.iter().any(|mv| mv == reply))
+    }
+
+    /// Rank legal root moves by material balance one ply deep (placeholder for real search)
+    ///
+    /// `searchmoves` restricts and orders the candidates; ties keep candidate order
+    /// so repeated searches report the same lines.
+    fn rank_root_moves(&self, search_moves: &[String]) -> UCIResult<Vec<(String, i32)>> {
+        let handler = self.position_handler.lock();
+        let board = handler.board();
+        let fen = board.get_fen()?;
+        let legal_moves = board.legal_moves()?;
+
+        let candidates: Vec<String> = if search_moves.is_empty() {
+            legal_moves
+        } else {
+            search_moves
+                .iter()
+                .filter(|mv| legal_moves.contains(mv))
+                .cloned()
+                .collect()
+        };
+
+        let mut scratch = Board::new()?;
+        let mut lines = Vec::with_capacity(candidates.len());
+        for root_move in candidates {
+            scratch.set_from_fen(&fen)?;
+            scratch.make_move(&root_move)?;
+            // Material is from the opponent's view after our move
+            let score = -material_balance(&scratch.get_fen()?);
+            lines.push((root_move, score));
+        }
+
+        lines.sort_by_key(|line| std::cmp::Reverse(line.1));
+        Ok(lines)
+    }
+
+    /// Root moves of `lines` in the order the search should visit them
+    ///
+    /// The `RootMoveOrdering` hook, if set, reorders them and the C++ search gets
+    /// the result as an ordering hint; without one the hint is cleared.
+    fn order_root_moves(&self, lines: &[(String, i32)]) -> Vec<String> {
+        let mut moves: Vec<String> = lines.iter().map(|(mv, _)| mv.clone()).collect();
+        let hint = match &self.root_move_ordering {
+            Some(ordering) => {
+                ordering.order(self.position_handler.lock().board(), &mut moves);
+                moves.join(" ")
+            }
+            None => String::new(),
+        };
+        if !ffi::engine_set_root_move_order(&hint) {
+            warn!(hint = %hint, "C++ engine rejected root move order");
+        }
+        moves
+    }
+
+    /// Handle stop command
+    async fn handle_stop_command(&self) -> UCIResult<()> {
+        self.stop_search().await
+    }
+
+    /// Handle ponder hit command
+    async fn handle_ponderhit_command(&self) -> UCIResult<()> {
+        debug!("Ponder hit received");
+
+        let current_state = self.state.current_state();
+        if current_state != EngineState::Pondering {
+            debug!(state = ?current_state, "Ponder hit received but not pondering");
+            return Ok(());
+        }
+
+        // Keep the in-flight search; only its clock starts now
+        let (ponder_running, ponder_nodes) = match self.active_search.lock().as_ref() {
+            Some(search) => (search.is_running(), Arc::clone(&search.nodes)),
+            None => (false, Arc::new(AtomicU64::new(0))),
+        };
+        let context = self.state.ponder_hit()?;
+        if context.is_infinite {
+            return Ok(());
+        }
+
+        // A ponder search that already ran its course has nothing left to search,
+        // so its move is committed at once instead of starting a clock
+        if !ponder_running {
+            let mut pending = self.pending_bestmove.lock();
+            let Some(bestmove) = pending.take() else {
+                debug!("Ponder hit after the ponder move was already sent");
+                return Ok(());
+            };
+            let nodes = ponder_nodes.load(Ordering::Relaxed);
+            complete_search(&self.state, &self.hash_table, nodes)?;
+            if self.state.is_debug_mode() {
+                self.send_response(&format!("info string stopreason {}", StopReason::Completed))?;
+            }
+            return self.send_response(&bestmove);
+        }
+
+        let mut params = SearchParams::from(&context.time_control);
+        params.ponder = false;
+        params.move_overhead_ms = self.state.config().move_overhead_ms;
+        let position_info = PositionInfo::from_board(self.position_handler.lock().board())?;
+        let time_limits = {
+            let forced_policy = self.state.config().time_policy.forced_policy();
+            let auto_policy = self.time_policy.lock();
+            let policy = forced_policy.as_deref().unwrap_or(auto_policy.as_ref());
+            params.time_limits(&position_info, policy)
+        };
+        let deadline = if time_limits.is_infinite() {
+            Duration::from_millis(100)
+        } else {
+            time_limits.hard_limit
+        };
+        let stop_reason = limit_stop_reason(&params, &time_limits);
+        debug!(?params, ?deadline, "Ponder hit - search clock started");
+
+        // The ponder search keeps running and counting nodes; the clock answers
+        // for it and stops it once the move is sent
+        let mut active_search = self.active_search.lock();
+        let Some(search) = active_search.as_mut() else {
+            return Ok(());
+        };
+        search.start_clock({
+            let state = Arc::clone(&self.state);
+            let response_tx = self.response_tx.clone();
+            let pending_bestmove = Arc::clone(&self.pending_bestmove);
+            let hash_table = Arc::clone(&self.hash_table);
+
+            move |stopped: Arc<AtomicBool>, nodes: Arc<AtomicU64>| async move {
+                tokio::time::sleep(deadline).await;
+
+                // `stop` may have answered in the meantime
+                let mut pending = pending_bestmove.lock();
+                if stopped.load(Ordering::Acquire)
+                    || state.current_state() != EngineState::Searching
+                {
+                    return;
+                }
+                let Some(bestmove) = pending.take() else {
+                    return;
+                };
+
+                let nodes = nodes.load(Ordering::Relaxed);
+                if let Err(e) = complete_search(&state, &hash_table, nodes) {
+                    error!(error = ?e, "Failed to complete search");
+                    return;
+                }
+
+                if state.is_debug_mode() {
+                    let _ = response_tx.send(format!("info string stopreason {}", stop_reason));
+                }
+                let _ = response_tx.send(bestmove);
+                stopped.store(true, Ordering::Release);
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Handle quit command
+    async fn handle_quit_command(&self) -> UCIResult<()> {
+        info!("Quit command received");
+        self.shutdown().await
+    }
+
+    /// Stop current search operation
+    async fn stop_search(&self) -> UCIResult<()> {
+        // The search task only answers while holding the pending move, so once it
+        // is held here exactly one of the two sends `bestmove`
+        let mut pending = self.pending_bestmove.lock();
+        let mut nodes = 0;
+        if let Some(search) = self.active_search.lock().take() {
+            search.stop();
+            nodes = search.nodes();
+        }
+        let current_state = self.state.current_state();
+
+        if current_state.is_computing() {
+            info!("Stopping current search");
+
+            // Complete search with current results; this also stops the C++ search
+            complete_search(&self.state, &self.hash_table, nodes)?;
+
+            if self.state.is_debug_mode() {
+                self.send_response(&format!("info string stopreason {}", StopReason::Stop))?;
+            }
+
+            // Answer with the best move found so far
+            let bestmove = pending.take();
+            self.send_response(bestmove.as_deref().unwrap_or("bestmove (none)"))?;
+        } else {
+            debug!(state = ?current_state, "Stop command received but not searching");
+        }
+
+        Ok(())
+    }
+
+    /// Shutdown the engine gracefully
+    async fn shutdown(&self) -> UCIResult<()> {
+        info!("Shutting down UCI engine");
+
+        self.state
+            .transition_to(EngineState::Stopping, "Engine shutdown requested")?;
+
+        // Stop any ongoing search
+        if self.state.current_state().is_computing() {
+            let _ = self.stop_search().await;
+        }
+
+        Ok(())
+    }
+
+    /// Reset engine to clean state
+    async fn reset(&self) -> UCIResult<()> {
+        info!("Resetting UCI engine");
+
+        // Stop any ongoing search
+        if self.state.current_state().is_computing() {
+            let _ = self.stop_search().await;
+        }
+
+        // Reset state
+        self.state.reset()?;
+
+        Ok(())
+    }
 
+    /// Send UCI options for the uci command
+    ///
+    /// Each declared option is announced with its current value as the default,
+    /// so a GUI connecting after a profile was applied sees the active settings.
+    fn send_uci_options(&self) -> UCIResult<()> {
+        let config = self.state.config();
+
+        for spec in OPTION_SPECS.iter() {
+            let current = (spec.current)(&config);
+            self.send_response(&spec.to_response(&current).to_uci_string()?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace the whole engine configuration at once (e.g. from a saved profile)
+    ///
+    /// The profile is validated and pushed to the C++ engine before it becomes
+    /// visible; if any part is invalid, the engine is busy searching, or the
+    /// engine rejects a setting, the previous configuration stays in effect.
+    pub fn apply_config(&self, mut config: EngineConfig) -> UCIResult<()> {
+        config.validate()?;
+
+        let current_state = self.state.current_state();
+        if current_state.is_computing() {
+            return Err(UCIError::Configuration {
+                message: format!("Cannot apply configuration while {:?}", current_state),
+            });
+        }
+
+        config.multithread_enabled = config.thread_count > 1;
+
+        let previous = self.state.config();
+        if let Err(e) = Self::push_config_to_engine(&config) {
+            // Best effort: leave the C++ side matching the config we keep
+            let _ = Self::push_config_to_engine(&previous);
+            return Err(e);
+        }
+
+        let hash_changed = previous.hash_size_mb != config.hash_size_mb;
+        self.state.update_config(|cfg| *cfg = config)?;
+        self.sync_fen_validation();
+        info!("Engine configuration applied");
+
+        if hash_changed {
+            self.spawn_hash_warmup();
+        }
         Ok(())
     }
 
+    /// Forward every FFI-backed setting in `config` to the C++ engine
+    fn push_config_to_engine(config: &EngineConfig) -> UCIResult<()> {
+        let results = [
+            ("Hash", ffi::engine_set_hash_size(config.hash_size_mb)),
+            ("Threads", ffi::engine_set_threads(config.thread_count)),
+            ("Contempt", ffi::engine_set_contempt(config.contempt_factor)),
+            (
+                "MorphyStyle",
+                ffi::engine_set_morphy_style(config.morphy_style),
+            ),
+            (
+                "SacrificeThreshold",
+                ffi::engine_set_sacrifice_threshold(config.sacrifice_threshold),
+            ),
+            (
+                "TacticalDepth",
+                ffi::engine_set_tactical_depth(config.tactical_depth),
+            ),
+            ("UCI_Chess960", ffi::engine_set_chess960(config.chess960)),
+            (
+                "UCI_Elo",
+                ffi::engine_set_strength(config.strength_elo().unwrap_or(0)),
+            ),
+        ];
+
+        match results.iter().find(|(_, accepted)| !accepted) {
+            Some((option, _)) => Err(UCIError::Ffi {
+                message: format!("C++ engine rejected {} setting", option),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Get the current value of every registered UCI option
+    ///
+    /// Returns `(option name, current value)` pairs in the same order the
+    /// options are announced in response to `uci`.
+    pub fn current_option_values(&self) -> Vec<(String, String)> {
+        let config = self.state.config();
+
+        OPTION_SPECS
+            .iter()
+            .filter(|spec| !matches!(spec.kind, OptionKind::Button))
+            .map(|spec| (spec.name.to_string(), (spec.current)(&config)))
+            .collect()
+    }
+
+    /// Get the FEN of the board as set by the last `position` command
+    pub fn current_fen(&self) -> UCIResult<String> {
+        self.position_handler.lock().get_current_position()
+    }
+
     /// Send a response to the UCI interface
     fn send_response(&self, response: &str) -> UCIResult<()> {
         debug!(response, "Sending UCI response");
 
-        // Send via broadcast channel (non-blocking)
-        match self.response_tx.send(response.to_string()) {
-            Ok(_) => Ok(()),
-            Err(_) => {
-                // No active receivers is OK - might happen during shutdown
-                Ok(())
-            }
-        }
+        // Non-blocking; no active receivers is OK - might happen during shutdown
+        self.response_tx.send(response.to_string());
+        Ok(())
     }
 
     /// Get current engine state
@@ -513,8 +2025,27 @@ impl UCIEngine {
     }
 
     /// Subscribe to engine responses
+    ///
+    /// A subscriber more than 64 responses behind loses the oldest ones; the GUI
+    /// connection reads [`Self::open_output`] instead.
     pub fn subscribe_responses(&self) -> broadcast::Receiver<String> {
-        self.response_tx.subscribe()
+        self.response_tx.broadcast.subscribe()
+    }
+
+    /// Open the output stream that feeds the GUI
+    ///
+    /// Every response sent after this call is queued in send order until read;
+    /// only search progress is shed if the reader falls far behind. Opening a
+    /// new stream closes the previous one.
+    pub fn open_output(&self) -> OutputReceiver {
+        self.response_tx.open_output()
+    }
+
+    /// Follow each search `info` update with a JSON telemetry frame
+    pub fn set_telemetry_json(&self, enabled: bool) -> UCIResult<()> {
+        self.state.update_config(|cfg| {
+            cfg.telemetry_json = enabled;
+        })
     }
 
     /// Subscribe to state changes
@@ -531,6 +2062,107 @@ impl UCIEngine {
     pub async fn process_command(&self, command: &str) -> UCIResult<()> {
         self.process_uci_command(command).await
     }
+
+    /// Process command lines in order, as if read one after another from stdin
+    ///
+    /// Stops after a `quit`; lines following it are not processed and have no
+    /// result. Useful for replaying a recorded session.
+    pub async fn process_batch(&self, lines: &[String]) -> Vec<UCIResult<()>> {
+        let mut results = Vec::with_capacity(lines.len());
+
+        for line in lines {
+            results.push(self.process_uci_command(line).await);
+            if self.state.current_state() == EngineState::Stopping {
+                debug!(processed = results.len(), "Batch stopped at quit");
+                break;
+            }
+        }
+
+        results
+    }
+}
+
+/// Moves a `go mate` search may play per depth when `go` sets no node limit
+const MATE_SEARCH_NODES: u64 = 1_000_000;
+
+/// Time a blocking search may overrun its hard limit before it is abandoned
+const SEARCH_TIMEOUT_GRACE: Duration = Duration::from_millis(250);
+
+/// Stop the C++ search running alongside a simulated one and wait for it
+///
+/// Returns the deepest iteration it completed.
+async fn finish_engine_search(search: tokio::task::JoinHandle<u32>) -> u32 {
+    SearchEngine::stop();
+    search.await.unwrap_or_else(|e| {
+        warn!(error = %e, "C++ search task failed");
+        0
+    })
+}
+
+/// Complete the current search, adding the C++ search counters to the statistics
+/// and applying a hash resize that was waiting for it
+fn complete_search(state: &UCIState, hash_table: &HashTable, nodes_searched: u64) -> UCIResult<()> {
+    // Stopping first keeps the read from waiting out a C++ search still running
+    SearchEngine::stop();
+    state.record_search_counters(SearchEngine::last_search_counters());
+    state.complete_search(nodes_searched)?;
+    hash_table.apply_deferred(state);
+    Ok(())
+}
+
+/// Which limit a search that ran its full course stopped at
+fn limit_stop_reason(params: &SearchParams, time_limits: &crate::time::TimeLimits) -> StopReason {
+    if !time_limits.is_infinite() {
+        StopReason::HardLimit
+    } else if params.depth.is_some() {
+        StopReason::Depth
+    } else if params.nodes.is_some() {
+        StopReason::Nodes
+    } else {
+        StopReason::Completed
+    }
+}
+
+/// Move `root_move` to the front of the ranked lines; false if it is not among them
+fn promote_root_move(lines: &mut Vec<(String, i32)>, root_move: &str) -> bool {
+    let Some(index) = lines.iter().position(|(mv, _)| mv == root_move) else {
+        return false;
+    };
+    let line = lines.remove(index);
+    lines.insert(0, line);
+    true
+}
+
+/// Material balance in centipawns from the side to move's point of view
+fn material_balance(fen: &str) -> i32 {
+    let mut fields = fen.split_whitespace();
+    let placement = fields.next().unwrap_or("");
+    let white_to_move = fields.next() != Some("b");
+
+    let balance: i32 = placement
+        .chars()
+        .map(|piece| {
+            let value = match piece.to_ascii_lowercase() {
+                'p' => 100,
+                'n' => 320,
+                'b' => 330,
+                'r' => 500,
+                'q' => 900,
+                _ => 0,
+            };
+            if piece.is_ascii_uppercase() {
+                value
+            } else {
+                -value
+            }
+        })
+        .sum();
+
+    if white_to_move {
+        balance
+    } else {
+        -balance
+    }
 }
 
 impl Default for UCIEngine {
@@ -542,132 +2174,2258 @@ impl Default for UCIEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::time::TimeLimits;
+    use crate::uci::state::SearchTotals;
+    use std::sync::atomic::{AtomicU32, Ordering};
     use tokio::time::Duration;
 
-    #[tokio::test]
-    async fn test_engine_initialization() {
-        let engine = UCIEngine::new();
+    /// Warmup that records its calls and takes long enough to expose blocking
+    #[derive(Default)]
+    struct RecordingWarmup {
+        calls: AtomicU32,
+        last_size_mb: AtomicU32,
+    }
 
-        assert_eq!(engine.state(), EngineState::Initializing);
+    /// Allocator that records resizes and runs out of memory above `max_mb`
+    struct LimitedAllocator {
+        max_mb: u32,
+        sizes: parking_lot::Mutex<Vec<u32>>,
+    }
 
-        engine.initialize().await.unwrap();
-        assert_eq!(engine.state(), EngineState::Ready);
+    impl HashAllocator for LimitedAllocator {
+        fn resize(&self, size_mb: u32) -> bool {
+            if size_mb > self.max_mb {
+                return false;
+            }
+            self.sizes.lock().push(size_mb);
+            true
+        }
+    }
+
+    impl LimitedAllocator {
+        fn new(max_mb: u32) -> Arc<Self> {
+            Arc::new(Self {
+                max_mb,
+                sizes: parking_lot::Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    /// Records every worker count a search is sized to
+    #[derive(Default)]
+    struct RecordingThreads {
+        counts: parking_lot::Mutex<Vec<u32>>,
+    }
+
+    impl SearchThreads for RecordingThreads {
+        fn set_threads(&self, thread_count: u32) -> bool {
+            self.counts.lock().push(thread_count);
+            true
+        }
+    }
+
+    /// Skip search info lines and return the bestmove response
+    async fn recv_bestmove(responses: &mut broadcast::Receiver<String>) -> String {
+        loop {
+            let response = tokio::time::timeout(Duration::from_millis(1500), responses.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            if !response.starts_with("info") {
+                return response;
+            }
+        }
+    }
+
+    /// Collect every line up to and including the next `bestmove`
+    async fn recv_search_output(responses: &mut broadcast::Receiver<String>) -> Vec<String> {
+        let mut lines = Vec::new();
+        loop {
+            let response = tokio::time::timeout(Duration::from_millis(1500), responses.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            let done = response.starts_with("bestmove");
+            lines.push(response);
+            if done {
+                return lines;
+            }
+        }
+    }
+
+    impl HashWarmup for RecordingWarmup {
+        fn warm_up(&self, size_mb: u32) {
+            std::thread::sleep(std::time::Duration::from_millis(300));
+            self.last_size_mb.store(size_mb, Ordering::SeqCst);
+            self.calls.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_engine_initialization() {
+        let engine = UCIEngine::new();
+
+        assert_eq!(engine.state(), EngineState::Initializing);
+
+        engine.initialize().await.unwrap();
+        assert_eq!(engine.state(), EngineState::Ready);
+    }
+
+    #[tokio::test]
+    async fn test_uci_command() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("uci").await.unwrap();
+
+        // Should receive identification and options
+        let response1 = responses.recv().await.unwrap();
+        assert!(response1.starts_with("id name"));
+
+        let response2 = responses.recv().await.unwrap();
+        assert!(response2.starts_with("id author"));
+
+        // Skip option responses
+        while let Ok(response) =
+            tokio::time::timeout(Duration::from_millis(10), responses.recv()).await
+        {
+            if response.is_ok() && response.unwrap() == "uciok" {
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_debug_command() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        assert!(!engine.state.is_debug_mode());
+
+        engine.process_command("debug on").await.unwrap();
+        assert!(engine.state.is_debug_mode());
+
+        engine.process_command("debug off").await.unwrap();
+        assert!(!engine.state.is_debug_mode());
+    }
+
+    #[tokio::test]
+    async fn test_isready_command() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("isready").await.unwrap();
+
+        let response = responses.recv().await.unwrap();
+        assert_eq!(response, "readyok");
+    }
+
+    #[tokio::test]
+    async fn test_go_perft_from_startpos() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine.process_command("position startpos").await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("go perft 2").await.unwrap();
+
+        let mut lines = Vec::new();
+        while let Ok(line) = responses.try_recv() {
+            lines.push(line);
+        }
+
+        let move_lines: Vec<_> = lines.iter().filter(|l| l.ends_with(": 20")).collect();
+        assert_eq!(move_lines.len(), 20);
+        assert!(lines.contains(&"e2e4: 20".to_string()));
+        assert_eq!(lines.last().unwrap(), "Nodes searched: 400");
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("go perft 1").await.unwrap();
+        let mut last = String::new();
+        while let Ok(line) = responses.try_recv() {
+            last = line;
+        }
+        assert_eq!(last, "Nodes searched: 20");
+    }
+
+    #[tokio::test]
+    async fn test_bench_reports_node_total() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine
+            .process_command("position startpos moves e2e4")
+            .await
+            .unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("bench 4 3").await.unwrap();
+
+        let mut lines = Vec::new();
+        while let Ok(line) = responses.try_recv() {
+            lines.push(line);
+        }
+        let position_lines = lines
+            .iter()
+            .filter(|line| line.starts_with("info string bench position"))
+            .count();
+        assert_eq!(position_lines, 3);
+
+        let total: u64 = lines
+            .iter()
+            .find_map(|line| line.strip_prefix("Nodes searched: "))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(total > 0);
+        let nps: u64 = lines
+            .iter()
+            .find_map(|line| line.strip_prefix("Nodes/second: "))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(nps > 0);
+
+        // The same suite and depth always visit the same nodes
+        engine.process_command("bench 4 3").await.unwrap();
+        let mut repeat = None;
+        while let Ok(line) = responses.try_recv() {
+            if let Some(nodes) = line.strip_prefix("Nodes searched: ") {
+                repeat = Some(nodes.parse::<u64>().unwrap());
+            }
+        }
+        assert_eq!(repeat, Some(total));
+
+        // The game position is untouched
+        assert_eq!(
+            engine.current_fen().unwrap(),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_eval_command_from_startpos() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine.process_command("position startpos").await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("eval").await.unwrap();
+
+        let response = responses.try_recv().unwrap();
+        let score: i32 = response
+            .strip_prefix("info string eval cp ")
+            .expect("Should be an eval info string")
+            .parse()
+            .unwrap();
+        assert!(
+            score.abs() < 50,
+            "Start position should be level: {}",
+            score
+        );
+    }
+
+    #[tokio::test]
+    async fn test_game_node_total_adds_searched_nodes() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("position startpos").await.unwrap();
+        engine.process_command("go depth 2").await.unwrap();
+        let mut expected = final_nodes(&recv_search_output(&mut responses).await);
+
+        // A ponder search that finished before ponderhit still counts its nodes
+        engine
+            .process_command("position startpos moves e2e4 e7e5")
+            .await
+            .unwrap();
+        engine
+            .process_command("go ponder wtime 10000 btime 10000 depth 2")
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        engine.process_command("ponderhit").await.unwrap();
+        let mut output = Vec::new();
+        while let Ok(line) = responses.try_recv() {
+            output.push(line);
+        }
+        assert!(output.last().unwrap().starts_with("bestmove "));
+        expected += final_nodes(&output);
+
+        let totals = engine.statistics().game_totals;
+        assert_eq!(totals.moves, 2);
+        assert_eq!(totals.nodes, expected);
+    }
+
+    #[tokio::test]
+    async fn test_stats_per_game_and_cumulative() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        // The ucinewgame before the first game does not start a second one
+        engine.process_command("ucinewgame").await.unwrap();
+        engine.process_command("position startpos").await.unwrap();
+        engine.process_command("go depth 1").await.unwrap();
+        recv_bestmove(&mut responses).await;
+        engine
+            .process_command("position startpos moves e2e4 e7e5")
+            .await
+            .unwrap();
+        engine.process_command("go depth 1").await.unwrap();
+        recv_bestmove(&mut responses).await;
+
+        let stats = engine.statistics();
+        assert_eq!(stats.games_played, 1);
+        assert_eq!(stats.game_totals.moves, 2);
+        assert_eq!(stats.game_totals, stats.cumulative_totals);
+
+        let first_game_nodes = stats.game_totals.nodes;
+
+        engine.process_command("ucinewgame").await.unwrap();
+        assert_eq!(engine.statistics().game_totals, SearchTotals::default());
+        engine.process_command("position startpos").await.unwrap();
+        engine.process_command("go depth 1").await.unwrap();
+        let nodes = final_nodes(&recv_search_output(&mut responses).await);
+
+        let stats = engine.statistics();
+        assert_eq!(stats.games_played, 2);
+        assert_eq!(stats.game_totals.moves, 1);
+        assert_eq!(stats.game_totals.nodes, nodes);
+        assert_eq!(stats.cumulative_totals.moves, 3);
+        assert_eq!(stats.cumulative_totals.nodes, first_game_nodes + nodes);
+        assert!(stats.cumulative_totals.time_ms >= stats.game_totals.time_ms);
+
+        engine.process_command("stats").await.unwrap();
+        assert_eq!(
+            responses.try_recv().unwrap(),
+            format!("info string stats game 2 {}", stats.game_totals)
+        );
+        assert_eq!(
+            responses.try_recv().unwrap(),
+            format!(
+                "info string stats total games 2 {}",
+                stats.cumulative_totals
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_display_command_requires_debug() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine
+            .process_command("position startpos moves e2e4")
+            .await
+            .unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        assert!(engine.process_command("d").await.is_err());
+        assert!(responses.try_recv().is_err());
+
+        engine.process_command("debug on").await.unwrap();
+        engine.process_command("d").await.unwrap();
+
+        let mut lines = Vec::new();
+        while let Ok(line) = responses.try_recv() {
+            lines.push(line);
+        }
+        assert_eq!(lines[0], "info string 8 r n b q k b n r");
+        assert!(lines.contains(&format!(
+            "info string Fen: {}",
+            engine.current_fen().unwrap()
+        )));
+        assert_eq!(lines.last().unwrap(), "info string Side to move: black");
+    }
+
+    #[tokio::test]
+    async fn test_legalmoves_san_from_startpos() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine.process_command("position startpos").await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("legalmoves san").await.unwrap();
+
+        let response = responses.recv().await.unwrap();
+        let list = response
+            .strip_prefix("info string legalmoves: ")
+            .expect("Should be a legalmoves info string");
+        let moves: Vec<&str> = list.split(',').collect();
+
+        assert_eq!(moves.len(), 20);
+        assert!(moves.contains(&"Nf3"));
+        assert!(moves.contains(&"e4"));
+    }
+
+    #[tokio::test]
+    async fn test_legalmoves_in_checkmate() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine
+            .process_command("position startpos moves f2f3 e7e5 g2g4 d8h4")
+            .await
+            .unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("legalmoves").await.unwrap();
+        assert_eq!(
+            responses.recv().await.unwrap(),
+            "info string legalmoves: none"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_echo_commands() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        engine
+            .process_command("setoption name EchoCommands value true")
+            .await
+            .unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("isready").await.unwrap();
+
+        assert_eq!(responses.recv().await.unwrap(), "info string cmd: isready");
+        assert_eq!(responses.recv().await.unwrap(), "readyok");
+    }
+
+    #[tokio::test]
+    async fn test_setoption_commands() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        // Test hash size option
+        engine
+            .process_command("setoption name Hash value 64")
+            .await
+            .unwrap();
+        let config = engine.state.config();
+        assert_eq!(config.hash_size_mb, 64);
+
+        // Test threads option
+        engine
+            .process_command("setoption name Threads value 4")
+            .await
+            .unwrap();
+        let config = engine.state.config();
+        assert_eq!(config.thread_count, 4);
+        assert!(config.multithread_enabled);
+
+        // Test ponder option
+        engine
+            .process_command("setoption name Ponder value true")
+            .await
+            .unwrap();
+        let config = engine.state.config();
+        assert!(config.ponder_enabled);
+    }
+
+    #[tokio::test]
+    async fn test_clear_hash_button() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("uci").await.unwrap();
+        let mut advertised = false;
+        while let Ok(response) = responses.try_recv() {
+            advertised |= response == "option name Clear Hash type button";
+        }
+        assert!(advertised);
+
+        engine
+            .process_command("setoption name Clear Hash")
+            .await
+            .unwrap();
+        assert_eq!(engine.statistics().hash_clears, 1);
+
+        // Other options named Clear are not the button
+        assert!(engine
+            .process_command("setoption name Clear value Book")
+            .await
+            .is_err());
+        assert_eq!(engine.statistics().hash_clears, 1);
+    }
+
+    #[tokio::test]
+    async fn test_full_output_queue_keeps_essential_lines() {
+        let queue = Arc::new(OutputQueue::new(4));
+        let mut output = OutputReceiver {
+            queue: Arc::clone(&queue),
+        };
+
+        // Progress beyond the capacity replaces the oldest progress
+        for depth in 1..=10 {
+            assert!(queue.push(format!("info depth {} nodes 100", depth)));
+        }
+        assert!(queue.push("info string stopreason depth".to_string()));
+        assert!(queue.push("bestmove e2e4".to_string()));
+        assert!(queue.push("readyok".to_string()));
+        assert!(queue.push("info depth 11 nodes 100".to_string()));
+        queue.close();
+
+        let mut lines = Vec::new();
+        while let Some(line) = output.recv().await {
+            lines.push(line);
+        }
+        assert_eq!(
+            lines,
+            [
+                "info depth 8 nodes 100",
+                "info depth 9 nodes 100",
+                "info depth 10 nodes 100",
+                "info string stopreason depth",
+                "bestmove e2e4",
+                "readyok",
+                "info depth 11 nodes 100",
+            ]
+        );
+        assert!(!queue.push("readyok".to_string()));
+
+        // A queue full of essential lines drops new progress instead
+        let queue = OutputQueue::new(2);
+        assert!(queue.push("bestmove e2e4".to_string()));
+        assert!(queue.push("readyok".to_string()));
+        assert!(queue.push("info depth 1 nodes 20".to_string()));
+        assert!(queue.push("readyok".to_string()));
+        assert_eq!(
+            Vec::from(queue.lines.lock().clone()),
+            ["bestmove e2e4", "readyok", "readyok"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_current_option_values() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        engine
+            .process_command("setoption name Hash value 256")
+            .await
+            .unwrap();
+        engine
+            .process_command("setoption name Ponder value true")
+            .await
+            .unwrap();
+
+        let values = engine.current_option_values();
+        let value_of = |name: &str| {
+            values
+                .iter()
+                .find(|(option, _)| option == name)
+                .map(|(_, value)| value.clone())
+        };
+
+        assert_eq!(value_of("Hash"), Some("256".to_string()));
+        assert_eq!(value_of("Ponder"), Some("true".to_string()));
+        assert_eq!(value_of("Threads"), Some("1".to_string()));
+        assert_eq!(value_of("UCI_AnalyseMode"), Some("false".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_chess960_accepts_shredder_fen_only_when_enabled() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let command = "position fen bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w HAha - 0 1";
+
+        assert!(engine.process_command(command).await.is_err());
+
+        engine
+            .process_command("setoption name UCI_Chess960 value true")
+            .await
+            .unwrap();
+        assert!(engine.state.config().chess960);
+        assert!(engine.input_limits().chess960);
+
+        engine.process_command(command).await.unwrap();
+        assert_eq!(
+            engine.current_fen().unwrap(),
+            "bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w KQkq - 0 1"
+        );
+
+        engine
+            .process_command("setoption name UCI_Chess960 value false")
+            .await
+            .unwrap();
+        assert!(engine.process_command(command).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sustained_losing_score_resigns() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine
+            .process_command("setoption name ResignThreshold value 500")
+            .await
+            .unwrap();
+        // White is a queen down
+        engine
+            .process_command("position fen q3k3/8/8/8/8/8/8/4K3 w - - 0 1")
+            .await
+            .unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        for search in 1..=3 {
+            engine.process_command("go depth 1").await.unwrap();
+            let output = recv_search_output(&mut responses).await;
+            let resigned = output.iter().any(|line| line == "info string resign");
+            assert_eq!(resigned, search == 3, "search {}: {:?}", search, output);
+        }
+
+        // A new game starts with a clean history
+        engine.process_command("ucinewgame").await.unwrap();
+        engine.process_command("go depth 1").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+        assert!(!output.iter().any(|line| line == "info string resign"));
+    }
+
+    #[tokio::test]
+    async fn test_only_played_moves_count_towards_resigning() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine
+            .process_command("setoption name ResignThreshold value 500")
+            .await
+            .unwrap();
+        engine
+            .process_command("position fen q3k3/8/8/8/8/8/8/4K3 w - - 0 1")
+            .await
+            .unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        // A go refused because a search is already running plays no move
+        engine.process_command("go infinite").await.unwrap();
+        assert!(engine.process_command("go depth 1").await.is_err());
+        engine.process_command("stop").await.unwrap();
+        recv_bestmove(&mut responses).await;
+
+        for search in 1..=3 {
+            engine.process_command("go depth 1").await.unwrap();
+            let output = recv_search_output(&mut responses).await;
+            let resigned = output.iter().any(|line| line == "info string resign");
+            assert_eq!(resigned, search == 3, "search {}: {:?}", search, output);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sustained_level_score_offers_draw() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine.process_command("position startpos").await.unwrap();
+
+        // Off by default
+        let mut responses = engine.subscribe_responses();
+        for _ in 0..3 {
+            engine.process_command("go depth 1").await.unwrap();
+            let output = recv_search_output(&mut responses).await;
+            assert!(!output
+                .iter()
+                .any(|line| line.starts_with("info string offerdraw")));
+        }
+
+        engine.process_command("ucinewgame").await.unwrap();
+        engine
+            .process_command("setoption name DrawMoveCount value 2")
+            .await
+            .unwrap();
+        for search in 1..=2 {
+            engine.process_command("go depth 1").await.unwrap();
+            let output = recv_search_output(&mut responses).await;
+            let offered = output.iter().any(|line| line == "info string offerdraw");
+            assert_eq!(offered, search == 2, "search {}: {:?}", search, output);
+            assert!(output.last().unwrap().starts_with("bestmove"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_setoption_morphy_style() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        assert!(!engine.state.config().morphy_style);
+
+        engine
+            .process_command("setoption name MorphyStyle value true")
+            .await
+            .unwrap();
+        assert!(engine.state.config().morphy_style);
+
+        engine
+            .process_command("setoption name MorphyStyle value false")
+            .await
+            .unwrap();
+        assert!(!engine.state.config().morphy_style);
+    }
+
+    #[tokio::test]
+    async fn test_setoption_limit_strength_and_elo() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        assert_eq!(engine.state.config().strength_elo(), None);
+
+        // UCI_Elo is kept while limiting is off, but play stays at full strength
+        engine
+            .process_command("setoption name UCI_Elo value 1800")
+            .await
+            .unwrap();
+        assert_eq!(engine.state.config().uci_elo, 1800);
+        assert_eq!(engine.state.config().strength_elo(), None);
+
+        engine
+            .process_command("setoption name UCI_LimitStrength value true")
+            .await
+            .unwrap();
+        assert!(engine.state.config().limit_strength);
+        assert_eq!(engine.state.config().strength_elo(), Some(1800));
+
+        engine
+            .process_command("setoption name UCI_LimitStrength value false")
+            .await
+            .unwrap();
+        assert_eq!(engine.state.config().strength_elo(), None);
+        assert_eq!(engine.state.config().uci_elo, 1800);
+    }
+
+    #[tokio::test]
+    async fn test_setoption_uci_elo_range() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        engine
+            .process_command("setoption name UCI_Elo value 3190")
+            .await
+            .unwrap();
+        assert_eq!(engine.state.config().uci_elo, 3190);
+
+        for value in ["500", "4000", "strong"] {
+            assert!(engine
+                .process_command(&format!("setoption name UCI_Elo value {}", value))
+                .await
+                .is_err());
+        }
+        assert_eq!(engine.state.config().uci_elo, 3190);
+    }
+
+    #[tokio::test]
+    async fn test_setoption_style_applies_preset() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        assert_eq!(engine.state.config().style, PlayingStyle::Balanced);
+
+        engine
+            .process_command("setoption name Style value Morphy")
+            .await
+            .unwrap();
+        let config = engine.state.config();
+        assert_eq!(config.style, PlayingStyle::Morphy);
+        assert!(config.morphy_style);
+        assert_eq!(config.sacrifice_threshold, 300);
+        assert_eq!(config.tactical_depth, 4);
+
+        engine
+            .process_command("setoption name Style value defensive")
+            .await
+            .unwrap();
+        let config = engine.state.config();
+        assert_eq!(config.style, PlayingStyle::Defensive);
+        assert!(!config.morphy_style);
+        assert_eq!(config.contempt_factor, -20);
+    }
+
+    #[tokio::test]
+    async fn test_setoption_style_rejects_undeclared_value() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        assert!(engine
+            .process_command("setoption name Style value Aggressive")
+            .await
+            .is_err());
+        assert_eq!(engine.state.config().style, PlayingStyle::Balanced);
+        assert_eq!(engine.state.config().sacrifice_threshold, 100);
+    }
+
+    #[tokio::test]
+    async fn test_style_option_declared_as_combo() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("uci").await.unwrap();
+        let mut declaration = None;
+        while let Ok(line) = responses.try_recv() {
+            if line.starts_with("option name Style ") {
+                declaration = Some(line);
+            }
+        }
+        assert_eq!(
+            declaration.unwrap(),
+            "option name Style type combo default Balanced var Morphy var Positional \
+             var Balanced var Defensive"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setoption_sacrifice_threshold_range() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        assert_eq!(engine.state.config().sacrifice_threshold, 100);
+
+        engine
+            .process_command("setoption name SacrificeThreshold value 250")
+            .await
+            .unwrap();
+        assert_eq!(engine.state.config().sacrifice_threshold, 250);
+
+        for value in ["5000", "-20", "lots"] {
+            assert!(engine
+                .process_command(&format!(
+                    "setoption name SacrificeThreshold value {}",
+                    value
+                ))
+                .await
+                .is_err());
+        }
+        assert_eq!(engine.state.config().sacrifice_threshold, 250);
+    }
+
+    #[tokio::test]
+    async fn test_setoption_contempt_range() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        engine
+            .process_command("setoption name Contempt value -35")
+            .await
+            .unwrap();
+        assert_eq!(engine.state.config().contempt_factor, -35);
+
+        engine
+            .process_command("setoption name Contempt value -200")
+            .await
+            .unwrap();
+        assert_eq!(engine.state.config().contempt_factor, -200);
+
+        assert!(engine
+            .process_command("setoption name Contempt value 500")
+            .await
+            .is_err());
+        assert!(engine
+            .process_command("setoption name Contempt value -1000")
+            .await
+            .is_err());
+        assert_eq!(engine.state.config().contempt_factor, -200);
+    }
+
+    #[tokio::test]
+    async fn test_setoption_tactical_depth_range() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        assert_eq!(engine.state.config().tactical_depth, 2);
+
+        engine
+            .process_command("setoption name TacticalDepth value 5")
+            .await
+            .unwrap();
+        assert_eq!(engine.state.config().tactical_depth, 5);
+
+        let result = engine
+            .process_command("setoption name TacticalDepth value 50")
+            .await;
+        assert!(matches!(result, Err(UCIError::Configuration { .. })));
+        assert_eq!(engine.state.config().tactical_depth, 5);
+
+        engine.process_command("go movetime 100").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_setoption_move_overhead_range() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        assert_eq!(engine.state.config().move_overhead_ms, 30);
+
+        engine
+            .process_command("setoption name MoveOverhead value 250")
+            .await
+            .unwrap();
+        assert_eq!(engine.state.config().move_overhead_ms, 250);
+
+        let result = engine
+            .process_command("setoption name MoveOverhead value 6000")
+            .await;
+        assert!(matches!(result, Err(UCIError::Configuration { .. })));
+        assert_eq!(engine.state.config().move_overhead_ms, 250);
+    }
+
+    #[tokio::test]
+    async fn test_setoption_spin_out_of_range_rejected() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let error = engine
+            .process_command("setoption name Hash value 4096")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, UCIError::Configuration { .. }));
+        assert!(error.to_string().contains("Hash"));
+        assert_eq!(engine.state.config().hash_size_mb, 16);
+
+        assert!(engine
+            .process_command("setoption name Threads value 0")
+            .await
+            .is_err());
+        assert_eq!(engine.state.config().thread_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_setoption_check_rejects_non_boolean() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let result = engine
+            .process_command("setoption name Ponder value yes")
+            .await;
+        assert!(matches!(result, Err(UCIError::Configuration { .. })));
+        assert!(!engine.state.config().ponder_enabled);
+
+        engine
+            .process_command("setoption name Ponder value TRUE")
+            .await
+            .unwrap();
+        assert!(engine.state.config().ponder_enabled);
+    }
+
+    #[tokio::test]
+    async fn test_setoption_unknown_name_rejected() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let error = engine
+            .process_command("setoption name BookFile value book.bin")
+            .await
+            .unwrap_err();
+        assert!(matches!(error, UCIError::Configuration { .. }));
+        assert!(error.to_string().contains("Unknown option: BookFile"));
+    }
+
+    #[tokio::test]
+    async fn test_announced_options_match_registry() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("uci").await.unwrap();
+        let mut announced = Vec::new();
+        while let Ok(line) = responses.try_recv() {
+            if let Some(rest) = line.strip_prefix("option name ") {
+                announced.push(rest.split(" type ").next().unwrap().to_string());
+            }
+        }
+        let declared: Vec<&str> = OPTION_SPECS.iter().map(|spec| spec.name).collect();
+        assert_eq!(announced, declared);
+
+        // A fresh engine reports the declared defaults
+        for (name, value) in engine.current_option_values() {
+            let spec = options::find_option(&name).unwrap();
+            assert_eq!(value, spec.default, "{}", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_config_profile() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let profile = EngineConfig {
+            hash_size_mb: 256,
+            thread_count: 4,
+            contempt_factor: 20,
+            morphy_style: true,
+            sacrifice_threshold: 300,
+            tactical_depth: 4,
+            ..EngineConfig::default()
+        };
+        engine.apply_config(profile).unwrap();
+
+        let config = engine.state.config();
+        assert_eq!(config.hash_size_mb, 256);
+        assert_eq!(config.thread_count, 4);
+        assert!(config.multithread_enabled);
+        assert_eq!(config.contempt_factor, 20);
+        assert!(config.morphy_style);
+        assert_eq!(config.sacrifice_threshold, 300);
+        assert_eq!(config.tactical_depth, 4);
+    }
+
+    #[tokio::test]
+    async fn test_apply_config_rejects_invalid_profile() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine
+            .process_command("setoption name Hash value 64")
+            .await
+            .unwrap();
+
+        // Valid hash, invalid thread count: nothing may be applied
+        let profile = EngineConfig {
+            hash_size_mb: 512,
+            thread_count: 0,
+            ..EngineConfig::default()
+        };
+        assert!(matches!(
+            engine.apply_config(profile),
+            Err(UCIError::Configuration { .. })
+        ));
+
+        let config = engine.state.config();
+        assert_eq!(config.hash_size_mb, 64);
+        assert_eq!(config.thread_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_apply_config_rejected_mid_search() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine.process_command("go infinite").await.unwrap();
+
+        let profile = EngineConfig {
+            hash_size_mb: 512,
+            ..EngineConfig::default()
+        };
+        assert!(engine.apply_config(profile).is_err());
+        assert_eq!(engine.state.config().hash_size_mb, 16);
+
+        engine.process_command("stop").await.unwrap();
+    }
+
+    /// A legal `position` command of `plies` knight shuffles
+    fn long_position_command(plies: usize) -> String {
+        let shuffle = ["g1f3", "g8f6", "f3g1", "f6g8"];
+        let moves: Vec<&str> = shuffle.iter().copied().cycle().take(plies).collect();
+        format!("position startpos moves {}", moves.join(" "))
+    }
+
+    #[tokio::test]
+    async fn test_default_move_limit_rejects_long_game() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        assert!(engine
+            .process_command(&long_position_command(600))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_raised_move_limit_accepts_long_game() {
+        let engine = UCIEngine::with_config(EngineConfig {
+            max_moves_per_command: 1000,
+            ..EngineConfig::default()
+        });
+        engine.initialize().await.unwrap();
+
+        engine
+            .process_command(&long_position_command(600))
+            .await
+            .unwrap();
+        assert_eq!(
+            engine.current_fen().unwrap(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 600 301"
+        );
+        assert_eq!(engine.input_limits().max_moves_per_command, 1000);
+    }
+
+    #[tokio::test]
+    async fn test_max_move_list_option() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        assert!(engine
+            .process_command(&long_position_command(600))
+            .await
+            .is_err());
+
+        engine
+            .process_command("setoption name MaxMoveList value 1000")
+            .await
+            .unwrap();
+        assert_eq!(engine.input_limits().max_moves_per_command, 1000);
+        engine
+            .process_command(&long_position_command(600))
+            .await
+            .unwrap();
+
+        // Lowering the limit applies to the very next command
+        engine
+            .process_command("setoption name MaxMoveList value 100")
+            .await
+            .unwrap();
+        assert!(engine
+            .process_command(&long_position_command(101))
+            .await
+            .is_err());
+        engine
+            .process_command(&long_position_command(100))
+            .await
+            .unwrap();
+
+        assert!(engine
+            .process_command("setoption name MaxMoveList value 5000")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_position_error_context_chain_in_debug_mode() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        // The f7 pawn is pinned by the queen on h5
+        let bad_position = "position startpos moves e2e4 e7e5 d1h5 f7f6";
+
+        let mut responses = engine.subscribe_responses();
+        assert!(engine.process_command(bad_position).await.is_err());
+        let summary = responses.try_recv().unwrap();
+        assert!(summary.starts_with("info string ERROR: "), "{}", summary);
+        assert!(
+            responses.try_recv().is_err(),
+            "Only the summary without debug"
+        );
+
+        engine.process_command("debug on").await.unwrap();
+        let mut responses = engine.subscribe_responses();
+        assert!(engine.process_command(bad_position).await.is_err());
+
+        let mut lines = Vec::new();
+        while let Ok(line) = responses.try_recv() {
+            lines.push(line);
+        }
+        assert_eq!(lines[0], summary);
+        assert_eq!(
+            lines[1..],
+            [
+                "info string context: position command".to_string(),
+                "info string context: Failed to apply move sequence".to_string(),
+                "info string context: Validate move sequence".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_position_command_updates_board() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        assert_eq!(
+            engine.current_fen().unwrap(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+
+        engine
+            .process_command("position startpos moves e2e4 e7e5")
+            .await
+            .unwrap();
+        assert_eq!(
+            engine.current_fen().unwrap(),
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2"
+        );
+
+        // A new position command replaces the previous one
+        engine.process_command("position startpos").await.unwrap();
+        assert_eq!(
+            engine.current_fen().unwrap(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_go_command() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        let mut state_changes = engine.subscribe_state_changes();
+
+        engine.process_command("go movetime 1000").await.unwrap();
+
+        // Should transition to searching
+        let state_change = tokio::time::timeout(Duration::from_millis(500), state_changes.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(state_change.to, EngineState::Searching);
+
+        // Should eventually get a best move response
+        let response = recv_bestmove(&mut responses).await;
+        assert!(response.starts_with("bestmove"));
+
+        // Should return to ready state
+        let state_change = tokio::time::timeout(Duration::from_millis(500), state_changes.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(state_change.to, EngineState::Ready);
+    }
+
+    #[tokio::test]
+    async fn test_go_searchmoves_restricts_bestmove() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+
+        engine
+            .process_command("go searchmoves d2d4 c2c4 depth 5")
+            .await
+            .unwrap();
+
+        let response = recv_bestmove(&mut responses).await;
+        assert_eq!(response, "bestmove d2d4");
+    }
+
+    #[tokio::test]
+    async fn test_searchmoves_promotion_reported_lowercase() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine
+            .process_command("position fen k7/4P3/8/8/8/8/8/K7 w - - 0 1")
+            .await
+            .unwrap();
+        engine
+            .process_command("go depth 1 searchmoves e7e8Q")
+            .await
+            .unwrap();
+        let output = recv_search_output(&mut responses).await;
+
+        assert!(output[0].ends_with(" pv e7e8q"), "{}", output[0]);
+        assert_eq!(output.last().unwrap(), "bestmove e7e8q");
+    }
+
+    #[tokio::test]
+    async fn test_go_mate_reports_forced_mate() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine.process_command("debug on").await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        // Re8+ Rxe8 Rxe8#; material alone prefers the quiet rook moves
+        engine
+            .process_command("position fen 1r4k1/5ppp/8/8/8/8/4RPPP/4R1K1 w - - 0 1")
+            .await
+            .unwrap();
+        engine.process_command("go mate 3").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+
+        let final_info = output
+            .iter()
+            .rev()
+            .find(|line| line.starts_with("info depth"))
+            .unwrap();
+        assert!(final_info.starts_with("info depth 3 "), "{}", final_info);
+        assert!(final_info.contains(" score mate 2 "), "{}", final_info);
+        assert!(final_info.ends_with(" pv e2e8"), "{}", final_info);
+        assert!(output.contains(&"info string stopreason mate".to_string()));
+        assert_eq!(output.last().unwrap(), "bestmove e2e8");
+    }
+
+    /// Blocks well past any search limit, like a wedged C++ search
+    struct StuckMateSearcher;
+
+    impl MateSearcher for StuckMateSearcher {
+        fn search_mate(
+            &self,
+            _board: &Board,
+            _max_moves: u32,
+            _max_nodes: u64,
+        ) -> UCIResult<Option<(u32, String)>> {
+            std::thread::sleep(Duration::from_millis(1500));
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stuck_mate_search_is_abandoned() {
+        let engine = UCIEngine::new().with_mate_searcher(Arc::new(StuckMateSearcher));
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        let started = std::time::Instant::now();
+        let result = engine.process_command("go mate 3 movetime 100").await;
+        assert!(matches!(result, Err(UCIError::Timeout { .. })));
+        assert!(started.elapsed() < Duration::from_millis(1000));
+        assert_eq!(recv_bestmove(&mut responses).await, "bestmove (none)");
+        assert_eq!(engine.state(), EngineState::Ready);
+
+        engine.process_command("isready").await.unwrap();
+        assert_eq!(recv_bestmove(&mut responses).await, "readyok");
+    }
+
+    #[tokio::test]
+    async fn test_go_mate_without_mate_plays_best_move() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine.process_command("debug on").await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("position startpos").await.unwrap();
+        engine.process_command("go mate 2").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+
+        assert!(!output.iter().any(|line| line.contains("score mate")));
+        assert!(output.contains(&"info string stopreason completed".to_string()));
+        assert!(output.last().unwrap().starts_with("bestmove "));
+    }
+
+    #[tokio::test]
+    async fn test_go_nodes_stops_at_node_limit() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine.process_command("debug on").await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        // 20 root moves, one node each per iteration
+        engine.process_command("position startpos").await.unwrap();
+        engine.process_command("go nodes 150").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+        let final_info = output
+            .iter()
+            .rev()
+            .find(|line| line.starts_with("info depth"))
+            .unwrap();
+        let nodes: u64 = final_info
+            .split(" nodes ")
+            .nth(1)
+            .and_then(|rest| rest.split(' ').next())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((150..170).contains(&nodes), "{}", final_info);
+        assert!(output.contains(&"info string stopreason nodes".to_string()));
+        assert!(output.last().unwrap().starts_with("bestmove "));
+
+        // A budget of 0 answers at once without searching
+        engine.process_command("go nodes 0").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+        assert!(!output.iter().any(|line| line.starts_with("info depth")));
+        assert!(output.contains(&"info string stopreason nodes".to_string()));
+        assert!(output.last().unwrap().starts_with("bestmove "));
+        assert_ne!(output.last().unwrap(), "bestmove (none)");
+    }
+
+    #[tokio::test]
+    async fn test_search_nps_ties_nodes_to_time() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        // Debug-only, so refused until `debug on`
+        let result = engine
+            .process_command("setoption name SearchNps value 1000000")
+            .await;
+        assert!(matches!(result, Err(UCIError::Configuration { .. })));
+        assert_eq!(engine.state.config().search_nps, 0);
+
+        engine.process_command("debug on").await.unwrap();
+        engine
+            .process_command("setoption name SearchNps value 1000000")
+            .await
+            .unwrap();
+        engine
+            .process_command("setoption name MoveOverhead value 0")
+            .await
+            .unwrap();
+        assert_eq!(engine.state.config().search_nps, 1_000_000);
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("position startpos").await.unwrap();
+        engine.process_command("go movetime 400").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+        let final_info = output
+            .iter()
+            .rev()
+            .find(|line| line.starts_with("info depth"))
+            .unwrap();
+        let field = |name: &str| -> u64 {
+            final_info
+                .split(&format!(" {} ", name))
+                .nth(1)
+                .and_then(|rest| rest.split(' ').next())
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+
+        // Nodes are the time searched at the configured rate
+        let nodes = field("nodes");
+        let expected = field("time") * 1000;
+        assert!((360_000..=440_000).contains(&nodes), "{}", final_info);
+        assert!(nodes.abs_diff(expected) <= 5_000, "{}", final_info);
+        assert!(output.last().unwrap().starts_with("bestmove "));
+    }
+
+    #[tokio::test]
+    async fn test_move_summary_follows_bestmove() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("position startpos").await.unwrap();
+        engine.process_command("go depth 3").await.unwrap();
+        recv_bestmove(&mut responses).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(responses.try_recv().is_err(), "Summary is off by default");
+
+        engine
+            .process_command("setoption name MoveSummary value true")
+            .await
+            .unwrap();
+        engine.process_command("go depth 3").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+        let final_info = output
+            .iter()
+            .rev()
+            .find(|line| line.starts_with("info depth"))
+            .unwrap();
+        let score = final_info
+            .split(" score cp ")
+            .nth(1)
+            .and_then(|rest| rest.split(' ').next())
+            .unwrap();
+
+        let summary = tokio::time::timeout(Duration::from_millis(500), responses.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            summary.starts_with(&format!(
+                "info string summary depth 3 score cp {} nodes ",
+                score
+            )),
+            "{}",
+            summary
+        );
+        let best_move = output.last().unwrap().strip_prefix("bestmove ").unwrap();
+        assert!(
+            summary.ends_with(&format!(" move {}", best_move)),
+            "{}",
+            summary
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multipv_reports_distinct_lines() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        engine
+            .process_command("setoption name MultiPV value 3")
+            .await
+            .unwrap();
+        assert_eq!(engine.state.config().multi_pv, 3);
+
+        // White can win the queen on d5, so that line must be ranked first
+        engine
+            .process_command("position fen 4k3/8/8/3q4/4P3/8/8/4K3 w - - 0 1")
+            .await
+            .unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("go movetime 100").await.unwrap();
+
+        let mut info_lines = Vec::new();
+        loop {
+            let line = tokio::time::timeout(Duration::from_millis(500), responses.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            if line.starts_with("bestmove") {
+                assert_eq!(line, "bestmove e4d5");
+                break;
+            }
+            info_lines.push(line);
+        }
+
+        assert_eq!(info_lines.len(), 3);
+        for (index, line) in info_lines.iter().enumerate() {
+            assert!(line.contains(&format!("multipv {}", index + 1)), "{}", line);
+        }
+        assert!(info_lines[0].ends_with("pv e4d5"));
+
+        let pvs: std::collections::HashSet<_> = info_lines
+            .iter()
+            .map(|line| line.rsplit(' ').next().unwrap().to_string())
+            .collect();
+        assert_eq!(pvs.len(), 3);
+    }
+
+    /// Depths of the `info depth` lines of one search, in order
+    /// Node count of the last iteration reported in `output`
+    fn final_nodes(output: &[String]) -> u64 {
+        output
+            .iter()
+            .rev()
+            .filter(|line| line.starts_with("info depth "))
+            .find_map(|line| line.split(" nodes ").nth(1))
+            .and_then(|rest| rest.split(' ').next())
+            .unwrap()
+            .parse()
+            .unwrap()
+    }
+
+    fn reported_depths(output: &[String]) -> Vec<u8> {
+        output
+            .iter()
+            .filter(|line| !line.contains(" currmove "))
+            .filter_map(|line| line.strip_prefix("info depth "))
+            .map(|rest| rest.split(' ').next().unwrap().parse().unwrap())
+            .collect()
+    }
+
+    /// Selective depth of the last `info depth` line
+    fn reported_seldepth(output: &[String]) -> u8 {
+        let final_info = output
+            .iter()
+            .rev()
+            .find(|line| line.starts_with("info depth"))
+            .unwrap();
+        final_info
+            .split(" seldepth ")
+            .nth(1)
+            .and_then(|rest| rest.split(' ').next())
+            .unwrap()
+            .parse()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_search_reports_each_iteration_before_bestmove() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("position startpos").await.unwrap();
+        engine.process_command("go depth 4").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+
+        assert!(output.last().unwrap().starts_with("bestmove"));
+        assert_eq!(reported_depths(&output), vec![1, 2, 3, 4]);
+        for line in output.iter().filter(|line| line.starts_with("info depth")) {
+            for field in [
+                " score cp ",
+                " nodes ",
+                " nps ",
+                " time ",
+                " hashfull ",
+                " pv ",
+            ] {
+                assert!(line.contains(field), "{} missing from {}", field, line);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_depth_limited_search_reports_seldepth() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("position startpos").await.unwrap();
+        engine.process_command("go depth 3").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+        assert_eq!(reported_depths(&output), vec![1, 2, 3]);
+        assert!(reported_seldepth(&output) >= 3);
+
+        // Depth 1 answers well within the simulated search window
+        let started = std::time::Instant::now();
+        engine.process_command("go depth 1").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+        assert!(started.elapsed() < Duration::from_millis(500));
+        assert_eq!(reported_depths(&output), vec![1]);
+        let best_move = output.last().unwrap().split(' ').nth(1).unwrap();
+        let board = Board::new().unwrap();
+        assert!(board
+            .legal_moves()
+            .unwrap()
+            .iter()
+            .any(|mv| mv == best_move));
+    }
+
+    #[tokio::test]
+    async fn test_tactical_depth_bounds_seldepth() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        // Open center with captures and checks available to both sides
+        engine
+            .process_command(
+                "position fen r1bqk2r/pppp1ppp/2n2n2/2b1p3/2B1P3/3P1N2/PPP2PPP/RNBQK2R w KQkq - 0 5",
+            )
+            .await
+            .unwrap();
+
+        engine
+            .process_command("setoption name TacticalDepth value 0")
+            .await
+            .unwrap();
+        engine.process_command("go depth 2").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+        assert_eq!(reported_seldepth(&output), 2);
+
+        engine
+            .process_command("setoption name TacticalDepth value 2")
+            .await
+            .unwrap();
+        engine.process_command("go depth 2").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+        assert!((2..=4).contains(&reported_seldepth(&output)));
+    }
+
+    #[tokio::test]
+    async fn test_hashfull_reported_empty_after_new_game() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("ucinewgame").await.unwrap();
+        engine.process_command("position startpos").await.unwrap();
+        engine.process_command("go depth 2").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+
+        let info_lines: Vec<_> = output
+            .iter()
+            .filter(|line| line.starts_with("info depth"))
+            .collect();
+        assert!(!info_lines.is_empty());
+        assert!(info_lines.iter().all(|line| line.contains(" hashfull 0 ")));
+    }
+
+    #[tokio::test]
+    async fn test_deep_search_streams_currmove() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("position startpos").await.unwrap();
+        engine.process_command("go depth 5").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+
+        let currmoves: Vec<&String> = output
+            .iter()
+            .filter(|line| line.contains(" currmove "))
+            .collect();
+        assert_eq!(currmoves.len(), 20, "{:?}", currmoves);
+        assert!(currmoves[0].starts_with("info depth 5 currmove "));
+        assert!(currmoves[0].ends_with(" currmovenumber 1"));
+        assert!(currmoves[19].ends_with(" currmovenumber 20"));
+
+        // Every root move is reported before the iteration's result
+        let result = output
+            .iter()
+            .position(|line| line.starts_with("info depth 5 ") && line.contains(" pv "))
+            .unwrap();
+        assert!(
+            output
+                .iter()
+                .rposition(|line| line.contains(" currmove "))
+                .unwrap()
+                < result
+        );
+        assert_eq!(reported_depths(&output), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_info_interval_throttles_iteration_reports() {
+        let config = EngineConfig {
+            info_interval_ms: 60_000,
+            ..EngineConfig::default()
+        };
+        let engine = UCIEngine::with_config(config);
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("position startpos").await.unwrap();
+        engine.process_command("go depth 4").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+
+        // The first iteration is reported, then only the final one
+        assert_eq!(reported_depths(&output), vec![1, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_statistics_report_pv_end_fen() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        // Winning the queen is the only sensible first PV move
+        engine
+            .process_command("position fen 4k3/8/8/3q4/4P3/8/8/4K3 w - - 0 1")
+            .await
+            .unwrap();
+        assert_eq!(engine.statistics().pv_fen, None);
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("go movetime 50").await.unwrap();
+        assert_eq!(recv_bestmove(&mut responses).await, "bestmove e4d5");
+
+        assert_eq!(
+            engine.statistics().pv_fen.as_deref(),
+            Some("4k3/8/8/3P4/8/8/8/4K3 b - - 0 1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_counters_monotonic_across_searches() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("position startpos").await.unwrap();
+
+        engine.process_command("go depth 2").await.unwrap();
+        recv_bestmove(&mut responses).await;
+        let first = engine.statistics();
+        // Every depth 2 leaf is resolved by quiescence search
+        assert!(first.qsearch_nodes > 0);
+
+        engine.process_command("go depth 2").await.unwrap();
+        recv_bestmove(&mut responses).await;
+        let second = engine.statistics();
+
+        assert_eq!(second.searches_completed, 2);
+        assert!(second.tt_hits >= first.tt_hits);
+        assert!(second.lmr_reductions >= first.lmr_reductions);
+        assert!(second.null_move_prunes >= first.null_move_prunes);
+        assert!(second.qsearch_nodes > first.qsearch_nodes);
+    }
+
+    #[tokio::test]
+    async fn test_timed_search_enters_searching() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        engine.process_command("go movetime 100").await.unwrap();
+        assert_eq!(engine.state(), EngineState::Searching);
+    }
+
+    #[tokio::test]
+    async fn test_analyse_mode_search_enters_analyzing() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        engine
+            .state
+            .update_config(|cfg| cfg.analysis_mode = true)
+            .unwrap();
+        engine.process_command("go movetime 100").await.unwrap();
+        assert_eq!(engine.state(), EngineState::Analyzing);
+
+        engine.process_command("stop").await.unwrap();
+        assert_eq!(engine.state(), EngineState::Ready);
+    }
+
+    #[tokio::test]
+    async fn test_movetime_sets_search_deadline() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        let started = Instant::now();
+        engine.process_command("go movetime 200").await.unwrap();
+
+        let response = recv_bestmove(&mut responses).await;
+        let elapsed = started.elapsed();
+        assert!(response.starts_with("bestmove"));
+        assert!(
+            elapsed >= Duration::from_millis(150) && elapsed < Duration::from_millis(400),
+            "bestmove after {:?}",
+            elapsed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_timer_enforces_movetime() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine
+            .process_command("setoption name MoveOverhead value 0")
+            .await
+            .unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        let started = Instant::now();
+        engine.process_command("go movetime 150").await.unwrap();
+
+        assert!(recv_bestmove(&mut responses).await.starts_with("bestmove"));
+        let elapsed = started.elapsed();
+        assert!(
+            elapsed >= Duration::from_millis(150) && elapsed <= Duration::from_millis(220),
+            "bestmove after {:?}",
+            elapsed
+        );
+        assert_eq!(engine.state(), EngineState::Ready);
+    }
+
+    #[tokio::test]
+    async fn test_infinite_search_never_stops_itself() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("go infinite").await.unwrap();
+        assert_no_bestmove(&mut responses, Duration::from_millis(400)).await;
+        assert_eq!(engine.state(), EngineState::Analyzing);
+
+        engine.process_command("stop").await.unwrap();
+        assert!(recv_bestmove(&mut responses).await.starts_with("bestmove"));
+    }
+
+    #[tokio::test]
+    async fn test_bare_go_searches_until_stop() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("go").await.unwrap();
+        assert_no_bestmove(&mut responses, Duration::from_millis(300)).await;
+        assert_eq!(engine.state(), EngineState::Analyzing);
+
+        engine.process_command("stop").await.unwrap();
+        assert!(recv_bestmove(&mut responses).await.starts_with("bestmove"));
+    }
+
+    /// Fixed limits whatever the clock, to place iterations around them
+    #[derive(Debug)]
+    struct FixedLimitsPolicy(TimeLimits);
+
+    impl TimePolicy for FixedLimitsPolicy {
+        fn calculate_time_limit(
+            &self,
+            _search_params: &SearchParams,
+            _position_info: &PositionInfo,
+        ) -> TimeLimits {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_soft_limit_finishes_current_iteration() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine.process_command("debug on").await.unwrap();
+
+        // Three iterations ending at 200, 400 and 600ms; the soft limit falls
+        // inside the second
+        engine.set_time_policy(Box::new(FixedLimitsPolicy(TimeLimits {
+            soft_limit: Duration::from_millis(250),
+            hard_limit: Duration::from_millis(600),
+        })));
+
+        let mut responses = engine.subscribe_responses();
+        let started = Instant::now();
+        engine.process_command("go depth 3").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+        let elapsed = started.elapsed();
+
+        assert!(output.iter().any(|line| line.starts_with("info depth 2 ")));
+        assert!(!output.iter().any(|line| line.starts_with("info depth 3 ")));
+        assert!(output.contains(&"info string stopreason soft_limit".to_string()));
+        assert!(output.last().unwrap().starts_with("bestmove "));
+        assert!(elapsed >= Duration::from_millis(390), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(580), "{:?}", elapsed);
+    }
+
+    #[tokio::test]
+    async fn test_set_time_policy() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        // A huge safety margin must not affect the hard limit for movetime
+        engine.set_time_policy(Box::new(StandardTimePolicy::new(1000, 0.5)));
+        engine
+            .process_command("setoption name MoveOverhead value 0")
+            .await
+            .unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        let started = Instant::now();
+        engine.process_command("go movetime 150").await.unwrap();
+
+        recv_bestmove(&mut responses).await;
+        assert!(started.elapsed() >= Duration::from_millis(150));
+    }
+
+    /// Policy that stops as soon as the best move survived two iterations
+    #[derive(Debug)]
+    struct StopWhenStablePolicy;
+
+    impl TimePolicy for StopWhenStablePolicy {
+        fn calculate_time_limit(
+            &self,
+            _search_params: &SearchParams,
+            _position_info: &PositionInfo,
+        ) -> crate::time::TimeLimits {
+            crate::time::TimeLimits::infinite()
+        }
+
+        fn should_stop_early(
+            &self,
+            progress: &SearchProgress,
+            _limits: &crate::time::TimeLimits,
+        ) -> bool {
+            progress.best_move_stability >= 2
+        }
+    }
+
+    /// Depth reported by the last info line of `go depth 6` under `StopWhenStablePolicy`
+    async fn stable_search_depth(analysis_mode: &str) -> String {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine.set_time_policy(Box::new(StopWhenStablePolicy));
+        engine
+            .process_command(&format!(
+                "setoption name UCI_AnalyseMode value {}",
+                analysis_mode
+            ))
+            .await
+            .unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("go depth 6").await.unwrap();
+
+        // Analysis mode holds its bestmove until `stop`
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        engine.process_command("stop").await.unwrap();
+        let output = recv_search_output(&mut responses).await;
+        let line = output
+            .iter()
+            .rev()
+            .find(|line| line.starts_with("info depth"))
+            .unwrap();
+        line.split_whitespace()
+            .skip_while(|token| *token != "depth")
+            .nth(1)
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_analyse_mode_disables_early_stop() {
+        assert_eq!(stable_search_depth("false").await, "3");
+        assert_eq!(stable_search_depth("true").await, "6");
+    }
+
+    #[tokio::test]
+    async fn test_forced_infinite_time_policy() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine.process_command("debug on").await.unwrap();
+
+        engine
+            .process_command("setoption name TimePolicy value Infinite")
+            .await
+            .unwrap();
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("go movetime 100").await.unwrap();
+        assert_eq!(
+            responses.try_recv().unwrap(),
+            "info string timepolicy Infinite (Infinite)"
+        );
+        assert_eq!(engine.state(), EngineState::Analyzing);
+
+        // Well past the movetime, the search is still running
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        while let Ok(line) = responses.try_recv() {
+            assert!(!line.starts_with("bestmove"), "{}", line);
+        }
+        assert_eq!(engine.state(), EngineState::Analyzing);
+        engine.process_command("stop").await.unwrap();
+        assert_eq!(engine.state(), EngineState::Ready);
+
+        // Auto restores the engine's own policy
+        engine
+            .process_command("setoption name TimePolicy value Auto")
+            .await
+            .unwrap();
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("go movetime 100").await.unwrap();
+        assert_eq!(
+            responses.try_recv().unwrap(),
+            "info string timepolicy Standard (Auto)"
+        );
+        assert_eq!(engine.state(), EngineState::Searching);
+        assert!(recv_bestmove(&mut responses).await.starts_with("bestmove"));
+
+        assert!(engine
+            .process_command("setoption name TimePolicy value Sometimes")
+            .await
+            .is_err());
+    }
+
+    /// Run `go_command` in debug mode and return the info string sent before bestmove
+    async fn stop_reason_line(go_command: &str) -> String {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        engine.process_command("debug on").await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command(go_command).await.unwrap();
+
+        let mut previous = String::new();
+        loop {
+            let line = tokio::time::timeout(Duration::from_millis(1500), responses.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            if line.starts_with("bestmove") {
+                return previous;
+            }
+            previous = line;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stop_reason_depth() {
+        assert_eq!(
+            stop_reason_line("go depth 5").await,
+            "info string stopreason depth"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stop_reason_hard_limit() {
+        assert_eq!(
+            stop_reason_line("go movetime 50").await,
+            "info string stopreason hard_limit"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stop_reason_hidden_without_debug() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("go depth 5").await.unwrap();
+
+        loop {
+            let line = tokio::time::timeout(Duration::from_millis(1500), responses.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(!line.contains("stopreason"));
+            if line.starts_with("bestmove") {
+                break;
+            }
+        }
     }
 
     #[tokio::test]
-    async fn test_uci_command() {
+    async fn test_illegal_ponder_move_is_dropped() {
         let engine = UCIEngine::new();
         engine.initialize().await.unwrap();
 
-        let mut responses = engine.subscribe_responses();
+        let bestmove = |moves: &[&str]| {
+            let pv: Vec<String> = moves.iter().map(|mv| mv.to_string()).collect();
+            let result = engine.search_result(&pv, 1, 0, 0).unwrap();
+            result.best_move_response().to_string()
+        };
 
-        engine.process_command("uci").await.unwrap();
+        assert_eq!(bestmove(&["e2e4", "e7e5"]), "bestmove e2e4 ponder e7e5");
 
-        // Should receive identification and options
-        let response1 = responses.recv().await.unwrap();
-        assert!(response1.starts_with("id name"));
+        // e2e4 again is not a legal reply for Black
+        assert_eq!(bestmove(&["e2e4", "e2e4"]), "bestmove e2e4");
+        assert_eq!(bestmove(&[]), "bestmove 0000");
 
-        let response2 = responses.recv().await.unwrap();
-        assert!(response2.starts_with("id author"));
+        // The check can be turned off
+        engine
+            .state
+            .update_config(|cfg| cfg.check_ponder_move = false)
+            .unwrap();
+        assert_eq!(bestmove(&["e2e4", "e2e4"]), "bestmove e2e4 ponder e2e4");
+    }
 
-        // Skip option responses
-        while let Ok(response) =
-            tokio::time::timeout(Duration::from_millis(10), responses.recv()).await
-        {
-            if response.is_ok() && response.unwrap() == "uciok" {
-                break;
-            }
+    #[test]
+    fn test_search_result_to_responses() {
+        let result = SearchResult {
+            best_move: "e2e4".to_string(),
+            ponder_move: Some("e7e5".to_string()),
+            depth: 12,
+            score: 35,
+            nodes: 150_000,
+            time_ms: 1500,
+            nps: 100_000,
+            principal_variation: vec!["e2e4".to_string(), "e7e5".to_string(), "g1f3".to_string()],
+        };
+
+        let lines: Vec<String> = result
+            .to_responses()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("info depth 12 score cp 35"));
+        assert!(lines[0].contains("nodes 150000"));
+        assert!(lines[0].ends_with("pv e2e4 e7e5 g1f3"));
+        assert_eq!(lines[1], "bestmove e2e4 ponder e7e5");
+
+        let no_ponder = SearchResult {
+            ponder_move: None,
+            ..result
+        };
+        assert_eq!(no_ponder.to_responses()[1].to_string(), "bestmove e2e4");
+    }
+
+    /// Lines received within `window`, failing if a `bestmove` is among them
+    async fn assert_no_bestmove(responses: &mut broadcast::Receiver<String>, window: Duration) {
+        tokio::time::sleep(window).await;
+        while let Ok(line) = responses.try_recv() {
+            assert!(!line.starts_with("bestmove"), "Unexpected {}", line);
         }
     }
 
     #[tokio::test]
-    async fn test_debug_command() {
+    async fn test_go_ponder_then_ponderhit() {
         let engine = UCIEngine::new();
         engine.initialize().await.unwrap();
 
-        assert!(!engine.state.is_debug_mode());
-
-        engine.process_command("debug on").await.unwrap();
-        assert!(engine.state.is_debug_mode());
-
-        engine.process_command("debug off").await.unwrap();
-        assert!(!engine.state.is_debug_mode());
+        let mut responses = engine.subscribe_responses();
+        engine
+            .process_command("go ponder wtime 10000 btime 10000 movetime 150")
+            .await
+            .unwrap();
+        assert_eq!(engine.state(), EngineState::Pondering);
+        assert!(engine.state.search_context().unwrap().is_ponder);
+
+        // Pondering never answers on its own
+        assert_no_bestmove(&mut responses, Duration::from_millis(20)).await;
+        assert_eq!(engine.state(), EngineState::Pondering);
+
+        let hit = Instant::now();
+        engine.process_command("ponderhit").await.unwrap();
+        assert_eq!(engine.state(), EngineState::Searching);
+        let context = engine.state.search_context().unwrap();
+        assert!(!context.is_ponder);
+        assert_eq!(context.time_control.move_time_ms, Some(150));
+
+        // The ponder search was still running, so the clock starts at ponderhit,
+        // less the default 30ms MoveOverhead
+        assert!(recv_bestmove(&mut responses).await.starts_with("bestmove"));
+        assert!(hit.elapsed() >= Duration::from_millis(120));
+        assert_eq!(engine.state(), EngineState::Ready);
     }
 
     #[tokio::test]
-    async fn test_isready_command() {
+    async fn test_ponderhit_after_ponder_search_finished() {
         let engine = UCIEngine::new();
         engine.initialize().await.unwrap();
 
         let mut responses = engine.subscribe_responses();
+        engine
+            .process_command("go ponder wtime 10000 btime 10000 depth 2")
+            .await
+            .unwrap();
 
-        engine.process_command("isready").await.unwrap();
+        // The ponder search finishes but keeps its move until ponderhit
+        assert_no_bestmove(&mut responses, Duration::from_millis(300)).await;
+        assert_eq!(engine.state(), EngineState::Pondering);
 
-        let response = responses.recv().await.unwrap();
-        assert_eq!(response, "readyok");
+        // The clock would allow far longer; the finished search answers at once
+        engine.process_command("ponderhit").await.unwrap();
+        assert!(responses.try_recv().unwrap().starts_with("bestmove "));
+        assert_eq!(engine.state(), EngineState::Ready);
+
+        // Once the move has been sent a late ponderhit changes nothing
+        engine.process_command("ponderhit").await.unwrap();
+        assert_eq!(engine.state(), EngineState::Ready);
+        assert_no_bestmove(&mut responses, Duration::from_millis(200)).await;
     }
 
     #[tokio::test]
-    async fn test_setoption_commands() {
+    async fn test_ponderhit_stops_ponder_search_output() {
         let engine = UCIEngine::new();
         engine.initialize().await.unwrap();
 
-        // Test hash size option
+        let mut responses = engine.subscribe_responses();
         engine
-            .process_command("setoption name Hash value 64")
+            .process_command("go ponder wtime 10000 btime 10000 movetime 1000")
             .await
             .unwrap();
-        let config = engine.state.config();
-        assert_eq!(config.hash_size_mb, 64);
+        engine.process_command("ponderhit").await.unwrap();
+        engine.process_command("stop").await.unwrap();
+        assert!(recv_bestmove(&mut responses).await.starts_with("bestmove"));
+
+        // The search pondered for the move just sent; it must not report on
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        while let Ok(line) = responses.try_recv() {
+            assert!(
+                !line.starts_with("info "),
+                "Unexpected {} after bestmove",
+                line
+            );
+        }
+    }
 
-        // Test threads option
-        engine
-            .process_command("setoption name Threads value 4")
-            .await
-            .unwrap();
-        let config = engine.state.config();
-        assert_eq!(config.thread_count, 4);
-        assert!(config.multithread_enabled);
+    #[tokio::test]
+    async fn test_go_ponder_then_stop() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
 
-        // Test ponder option
+        // Winning the queen is the pondered best move
         engine
-            .process_command("setoption name Ponder value true")
+            .process_command("position fen 4k3/8/8/3q4/4P3/8/8/4K3 w - - 0 1")
             .await
             .unwrap();
-        let config = engine.state.config();
-        assert!(config.ponder_enabled);
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("go ponder").await.unwrap();
+        assert_eq!(engine.state(), EngineState::Pondering);
+        assert_no_bestmove(&mut responses, Duration::from_millis(200)).await;
+
+        engine.process_command("stop").await.unwrap();
+        assert_eq!(responses.try_recv().unwrap(), "bestmove e4d5");
+        assert_eq!(engine.state(), EngineState::Ready);
+
+        // Nothing is left over for the next stop
+        engine.process_command("go infinite").await.unwrap();
+        engine.process_command("stop").await.unwrap();
+        assert_eq!(recv_bestmove(&mut responses).await, "bestmove e4d5");
+        assert_no_bestmove(&mut responses, Duration::from_millis(200)).await;
     }
 
     #[tokio::test]
-    async fn test_go_command() {
+    async fn test_stop_right_after_go_sends_one_bestmove() {
         let engine = UCIEngine::new();
         engine.initialize().await.unwrap();
-
         let mut responses = engine.subscribe_responses();
-        let mut state_changes = engine.subscribe_state_changes();
 
-        engine.process_command("go movetime 1000").await.unwrap();
+        for go_command in ["go infinite", "go movetime 50", "go depth 3"] {
+            for _ in 0..10 {
+                engine.process_command(go_command).await.unwrap();
+                engine.process_command("stop").await.unwrap();
+                assert!(recv_bestmove(&mut responses).await.starts_with("bestmove "));
+                assert_eq!(engine.state(), EngineState::Ready);
+            }
+        }
 
-        // Should transition to searching
-        let state_change = tokio::time::timeout(Duration::from_millis(500), state_changes.recv())
-            .await
-            .unwrap()
-            .unwrap();
-        assert_eq!(state_change.to, EngineState::Searching);
+        // No search left running sends a late second answer
+        assert_no_bestmove(&mut responses, Duration::from_millis(300)).await;
+    }
 
-        // Should eventually get a best move response
-        let response = tokio::time::timeout(Duration::from_millis(500), responses.recv())
-            .await
-            .unwrap()
-            .unwrap();
-        assert!(response.starts_with("bestmove"));
+    #[tokio::test]
+    async fn test_stop_after_search_finished_is_silent() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
 
-        // Should return to ready state
-        let state_change = tokio::time::timeout(Duration::from_millis(500), state_changes.recv())
-            .await
-            .unwrap()
-            .unwrap();
-        assert_eq!(state_change.to, EngineState::Ready);
+        engine.process_command("go depth 1").await.unwrap();
+        assert!(recv_bestmove(&mut responses).await.starts_with("bestmove "));
+        engine.process_command("stop").await.unwrap();
+        assert_no_bestmove(&mut responses, Duration::from_millis(100)).await;
     }
 
     #[tokio::test]
@@ -685,7 +4443,11 @@ mod tests {
             .await
             .unwrap()
             .unwrap();
-        assert_eq!(state_change.to, EngineState::Searching);
+        assert_eq!(state_change.to, EngineState::Analyzing);
+
+        // Analysis must not finish on its own
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(engine.state(), EngineState::Analyzing);
 
         // Stop the search
         engine.process_command("stop").await.unwrap();
@@ -709,6 +4471,122 @@ mod tests {
         assert_eq!(engine.state(), EngineState::Ready);
     }
 
+    #[tokio::test]
+    async fn test_ucinewgame_clears_position_and_repetitions() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        engine
+            .process_command("position fen 4k3/8/8/8/8/8/4P3/4K3 w - - 0 1 moves e1d1 e8d8")
+            .await
+            .unwrap();
+        assert_eq!(engine.position_handler.lock().position_keys().len(), 3);
+
+        engine.process_command("ucinewgame").await.unwrap();
+        {
+            let handler = engine.position_handler.lock();
+            // Repetition history restarts from the start position alone
+            assert_eq!(handler.position_keys().len(), 1);
+            assert!(handler.get_move_history().is_empty());
+            assert_eq!(handler.starting_fen(), None);
+        }
+        assert_eq!(
+            engine.current_fen().unwrap(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+        assert_eq!(engine.statistics().hash_clears, 1);
+    }
+
+    #[tokio::test]
+    async fn test_recent_commands_in_order_and_bounded() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        engine.process_command("isready").await.unwrap();
+        engine.process_command("debug on").await.unwrap();
+        let _ = engine.process_command("bogus command").await;
+        engine
+            .process_command("position startpos moves e2e4\n")
+            .await
+            .unwrap();
+        assert_eq!(
+            engine.recent_commands(),
+            [
+                "isready",
+                "debug on",
+                "bogus command",
+                "position startpos moves e2e4"
+            ]
+        );
+
+        for contempt in -150..150 {
+            engine
+                .process_command(&format!("setoption name Contempt value {}", contempt))
+                .await
+                .unwrap();
+        }
+        let commands = engine.recent_commands();
+        assert_eq!(commands.len(), COMMAND_LOG_CAPACITY);
+        assert_eq!(commands[0], "setoption name Contempt value -106");
+        assert_eq!(commands[255], "setoption name Contempt value 149");
+
+        engine.process_command("ucinewgame").await.unwrap();
+        assert!(engine.recent_commands().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ucinewgame_aborts_running_search() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+
+        let mut responses = engine.subscribe_responses();
+        engine.process_command("go infinite").await.unwrap();
+        assert_eq!(engine.state(), EngineState::Analyzing);
+
+        engine.process_command("ucinewgame").await.unwrap();
+        assert!(recv_bestmove(&mut responses).await.starts_with("bestmove"));
+        assert_eq!(engine.state(), EngineState::Ready);
+        assert_no_bestmove(&mut responses, Duration::from_millis(200)).await;
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_replays_session() {
+        let engine = UCIEngine::new();
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        let session: Vec<String> = [
+            "uci",
+            "setoption name Hash value 64",
+            "setoption name Hash value 99999",
+            "position startpos moves e2e4 e7e5",
+            "go depth 1",
+            "quit",
+            "setoption name Hash value 128",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+        let results = engine.process_batch(&session).await;
+        assert_eq!(results.len(), 6);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert!(matches!(results[2], Err(UCIError::Configuration { .. })));
+        assert!(results[3..].iter().all(Result::is_ok));
+
+        assert_eq!(engine.state(), EngineState::Stopping);
+        assert_eq!(engine.state.config().hash_size_mb, 64);
+        assert_eq!(
+            engine.current_fen().unwrap(),
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2"
+        );
+        assert_eq!(engine.recent_commands().len(), 6);
+
+        let first = responses.recv().await.unwrap();
+        assert!(first.starts_with("id name"));
+    }
+
     #[tokio::test]
     async fn test_quit_command() {
         let engine = UCIEngine::new();
@@ -766,4 +4644,174 @@ mod tests {
             engine.process_command("isready").await.unwrap();
         }
     }
+
+    #[tokio::test]
+    async fn test_warmup_hash_runs_off_event_loop() {
+        let warmup = Arc::new(RecordingWarmup::default());
+        let engine = UCIEngine::new().with_hash_warmup(warmup.clone());
+        engine.initialize().await.unwrap();
+
+        // Warmup is off by default, so resizing the hash does not touch it
+        engine
+            .process_command("setoption name Hash value 64")
+            .await
+            .unwrap();
+        assert_eq!(warmup.calls.load(Ordering::SeqCst), 0);
+
+        // Enabling warmup schedules it without waiting for it to finish
+        let started = Instant::now();
+        engine
+            .process_command("setoption name WarmupHash value true")
+            .await
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_millis(300));
+        assert_eq!(warmup.calls.load(Ordering::SeqCst), 0);
+
+        // The warmup completes in the background for the configured size
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while warmup.calls.load(Ordering::SeqCst) == 0 && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(warmup.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(warmup.last_size_mb.load(Ordering::SeqCst), 64);
+    }
+
+    #[tokio::test]
+    async fn test_isready_waits_for_pending_option_changes() {
+        let warmup = Arc::new(RecordingWarmup::default());
+        let engine = UCIEngine::new().with_hash_warmup(warmup.clone());
+        engine.initialize().await.unwrap();
+        engine
+            .process_command("setoption name WarmupHash value true")
+            .await
+            .unwrap();
+        engine.process_command("isready").await.unwrap();
+        assert_eq!(warmup.calls.load(Ordering::SeqCst), 1);
+
+        let mut responses = engine.subscribe_responses();
+        let started = Instant::now();
+        engine
+            .process_command("setoption name Hash value 512")
+            .await
+            .unwrap();
+        assert_eq!(warmup.calls.load(Ordering::SeqCst), 1);
+
+        // readyok only once the resized table has been warmed up
+        engine.process_command("isready").await.unwrap();
+        assert_eq!(responses.try_recv().unwrap(), "readyok");
+        assert!(started.elapsed() >= Duration::from_millis(300));
+        assert_eq!(warmup.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(warmup.last_size_mb.load(Ordering::SeqCst), 512);
+
+        // Nothing left to wait for
+        let started = Instant::now();
+        engine.process_command("isready").await.unwrap();
+        assert!(started.elapsed() < Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_setoption_hash_resizes_table() {
+        let allocator = LimitedAllocator::new(1024);
+        let engine = UCIEngine::new().with_hash_allocator(allocator.clone());
+        engine.initialize().await.unwrap();
+
+        engine
+            .process_command("setoption name Hash value 256")
+            .await
+            .unwrap();
+        assert_eq!(*allocator.sizes.lock(), [256]);
+        assert_eq!(engine.state.config().hash_size_mb, 256);
+
+        // The same size keeps the table as it is
+        engine
+            .process_command("setoption name Hash value 256")
+            .await
+            .unwrap();
+        assert_eq!(*allocator.sizes.lock(), [256]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_hash_allocation_reverts_config() {
+        let allocator = LimitedAllocator::new(1024);
+        let engine = UCIEngine::new().with_hash_allocator(allocator.clone());
+        engine.initialize().await.unwrap();
+        engine
+            .process_command("setoption name Hash value 64")
+            .await
+            .unwrap();
+
+        let result = engine
+            .process_command("setoption name Hash value 2048")
+            .await;
+        assert!(matches!(result, Err(UCIError::Configuration { .. })));
+        assert_eq!(engine.state.config().hash_size_mb, 64);
+        assert_eq!(*allocator.sizes.lock(), [64]);
+    }
+
+    #[tokio::test]
+    async fn test_hash_resize_waits_for_search() {
+        let allocator = LimitedAllocator::new(1024);
+        let engine = UCIEngine::new().with_hash_allocator(allocator.clone());
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine.process_command("go infinite").await.unwrap();
+        engine
+            .process_command("setoption name Hash value 128")
+            .await
+            .unwrap();
+        engine
+            .process_command("setoption name Hash value 512")
+            .await
+            .unwrap();
+        assert!(allocator.sizes.lock().is_empty());
+        assert_eq!(engine.state.config().hash_size_mb, 512);
+
+        // Only the last size is allocated, once the search is done with the table
+        engine.process_command("stop").await.unwrap();
+        recv_bestmove(&mut responses).await;
+        assert_eq!(*allocator.sizes.lock(), [512]);
+
+        // A deferred resize that cannot be allocated falls back to the kept table
+        engine.process_command("go infinite").await.unwrap();
+        engine
+            .process_command("setoption name Hash value 2048")
+            .await
+            .unwrap();
+        engine.process_command("stop").await.unwrap();
+        recv_bestmove(&mut responses).await;
+        assert_eq!(*allocator.sizes.lock(), [512]);
+        assert_eq!(engine.state.config().hash_size_mb, 512);
+    }
+
+    #[tokio::test]
+    async fn test_threads_sized_before_each_search() {
+        let threads = Arc::new(RecordingThreads::default());
+        let engine = UCIEngine::new().with_search_threads(threads.clone());
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        engine
+            .process_command("setoption name Threads value 4")
+            .await
+            .unwrap();
+        assert!(threads.counts.lock().is_empty());
+        engine.process_command("go depth 1").await.unwrap();
+        recv_bestmove(&mut responses).await;
+        assert_eq!(*threads.counts.lock(), [4]);
+
+        // A change during the search waits for the next `go`
+        engine.process_command("go infinite").await.unwrap();
+        engine
+            .process_command("setoption name Threads value 2")
+            .await
+            .unwrap();
+        assert_eq!(*threads.counts.lock(), [4, 4]);
+        engine.process_command("stop").await.unwrap();
+        recv_bestmove(&mut responses).await;
+
+        engine.process_command("go depth 1").await.unwrap();
+        recv_bestmove(&mut responses).await;
+        assert_eq!(*threads.counts.lock(), [4, 4, 2]);
+    }
 }
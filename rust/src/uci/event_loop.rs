@@ -5,24 +5,27 @@
 // and graceful shutdown.
 
 use std::sync::Arc;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::sync::{broadcast, oneshot};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::oneshot;
 use tokio::time::{timeout, Duration, Instant};
 use tokio::{select, signal};
 use tracing::{debug, error, info, instrument, warn};
 
 use crate::error::{UCIError, UCIResult};
-use crate::uci::engine::UCIEngine;
+use crate::uci::engine::{OutputReceiver, UCIEngine};
 use crate::uci::parser::ZeroCopyParser;
 use crate::uci::sanitizer::InputSanitizer;
 
 /// Main UCI event loop coordinator with async I/O processing
 pub struct UCIEventLoop {
     /// Input reader for stdin commands
-    stdin_reader: BufReader<tokio::io::Stdin>,
+    stdin_reader: BufReader<Box<dyn AsyncRead + Send + Unpin>>,
 
     /// Output writer for stdout responses
-    stdout_writer: tokio::io::Stdout,
+    stdout_writer: Box<dyn AsyncWrite + Send + Unpin>,
+
+    /// Set once a write fails with `BrokenPipe`: the GUI has gone away
+    output_closed: bool,
 
     /// UCI engine instance
     engine: Arc<UCIEngine>,
@@ -33,8 +36,8 @@ pub struct UCIEventLoop {
     /// Input sanitizer for security
     sanitizer: InputSanitizer,
 
-    /// Response receiver from engine
-    response_rx: broadcast::Receiver<String>,
+    /// Response stream from the engine, in send order
+    response_rx: OutputReceiver,
 
     /// Shutdown signal receiver
     shutdown_rx: Option<oneshot::Receiver<()>>,
@@ -42,6 +45,12 @@ pub struct UCIEventLoop {
     /// Performance statistics
     stats: EventLoopStats,
 
+    /// Token bucket for `max_commands_per_second`, if enabled
+    rate_limiter: Option<CommandRateLimiter>,
+
+    /// Token bucket for `info_string_budget_per_sec`, if enabled
+    info_string_limiter: Option<CommandRateLimiter>,
+
     /// Configuration
     config: EventLoopConfig,
 }
@@ -63,6 +72,17 @@ pub struct EventLoopConfig {
 
     /// Graceful shutdown timeout
     pub shutdown_timeout_ms: u64,
+
+    /// Commands accepted per second before further input is dropped;
+    /// `None` disables rate limiting. `stop` and `quit` are never limited.
+    pub max_commands_per_second: Option<u32>,
+
+    /// `info string` lines written per second before further ones are dropped;
+    /// `None` disables the budget. Other responses are never dropped.
+    pub info_string_budget_per_sec: Option<u32>,
+
+    /// Follow each search `info` update with an `info string json:` telemetry frame
+    pub telemetry_json: bool,
 }
 
 impl Default for EventLoopConfig {
@@ -72,7 +92,10 @@ impl Default for EventLoopConfig {
             command_timeout_ms: 1000,  // 1 second command timeout
             input_buffer_size: 8192,   // 8KB input buffer
             enable_monitoring: true,
-            shutdown_timeout_ms: 3000, // 3 second shutdown timeout
+            shutdown_timeout_ms: 3000,     // 3 second shutdown timeout
+            max_commands_per_second: None, // Trusted GUI on stdio
+            info_string_budget_per_sec: None,
+            telemetry_json: false,
         }
     }
 }
@@ -89,6 +112,12 @@ pub struct EventLoopStats {
     /// Commands that timed out
     pub command_timeouts: u64,
 
+    /// Commands dropped by the rate limiter
+    pub commands_throttled: u64,
+
+    /// `info string` lines dropped by the output budget
+    pub info_strings_dropped: u64,
+
     /// Average command processing time
     pub avg_command_time_ms: f64,
 
@@ -108,6 +137,8 @@ impl Default for EventLoopStats {
             commands_processed: 0,
             responses_sent: 0,
             command_timeouts: 0,
+            commands_throttled: 0,
+            info_strings_dropped: 0,
             avg_command_time_ms: 0.0,
             peak_memory_kb: 0,
             uptime: Duration::from_secs(0),
@@ -116,6 +147,43 @@ impl Default for EventLoopStats {
     }
 }
 
+/// Token bucket limiting how fast commands are accepted or `info string` lines written
+#[derive(Debug)]
+struct CommandRateLimiter {
+    /// Tokens refilled per second, also the burst capacity
+    rate: f64,
+    /// Tokens currently available
+    tokens: f64,
+    /// When tokens were last refilled
+    last_refill: Instant,
+}
+
+impl CommandRateLimiter {
+    fn new(commands_per_second: u32) -> Self {
+        let rate = f64::from(commands_per_second.max(1));
+        Self {
+            rate,
+            tokens: rate,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take a token for one command; `false` if the bucket is empty
+    fn try_acquire(&mut self) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
 impl UCIEventLoop {
     /// Create a new UCI event loop with default configuration
     pub fn new(engine: Arc<UCIEngine>) -> UCIResult<Self> {
@@ -124,21 +192,45 @@ impl UCIEventLoop {
 
     /// Create a new UCI event loop with custom configuration
     pub fn with_config(engine: Arc<UCIEngine>, config: EventLoopConfig) -> UCIResult<Self> {
-        let stdin = tokio::io::stdin();
-        let stdout = tokio::io::stdout();
-        let stdin_reader = BufReader::with_capacity(config.input_buffer_size, stdin);
+        Self::with_io(engine, config, tokio::io::stdin(), tokio::io::stdout())
+    }
+
+    /// Create an event loop reading commands from `input` and writing responses to `output`
+    ///
+    /// Used to drive the engine over pipes or sockets other than stdio, and by tests.
+    pub fn with_io<R, W>(
+        engine: Arc<UCIEngine>,
+        config: EventLoopConfig,
+        input: R,
+        output: W,
+    ) -> UCIResult<Self>
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        let input: Box<dyn AsyncRead + Send + Unpin> = Box::new(input);
+        let stdin_reader = BufReader::with_capacity(config.input_buffer_size, input);
 
         // Subscribe to engine responses
-        let response_rx = engine.subscribe_responses();
+        let response_rx = engine.open_output();
+        if config.telemetry_json {
+            engine.set_telemetry_json(true)?;
+        }
+        let input_limits = engine.input_limits();
 
         Ok(Self {
             stdin_reader,
-            stdout_writer: stdout,
+            stdout_writer: Box::new(output),
+            output_closed: false,
             engine,
-            parser: ZeroCopyParser::new(),
-            sanitizer: InputSanitizer::default(),
+            parser: ZeroCopyParser::with_sanitizer(InputSanitizer::new(input_limits.clone())),
+            sanitizer: InputSanitizer::new(input_limits),
             response_rx,
             shutdown_rx: None,
+            rate_limiter: config.max_commands_per_second.map(CommandRateLimiter::new),
+            info_string_limiter: config
+                .info_string_budget_per_sec
+                .map(CommandRateLimiter::new),
             stats: EventLoopStats {
                 start_time: Instant::now(),
                 ..Default::default()
@@ -199,18 +291,15 @@ impl UCIEventLoop {
                 // Handle engine responses
                 result = self.response_rx.recv() => {
                     match result {
-                        Ok(response) => {
+                        Some(response) => {
                             if let Err(e) = self.send_response(&response).await {
                                 error!(error = %e, response = %response, "Failed to send response");
                             }
                         }
-                        Err(broadcast::error::RecvError::Closed) => {
+                        None => {
                             info!("Engine response channel closed");
                             break;
                         }
-                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
-                            warn!(skipped_responses = skipped, "Response buffer lagged - some responses may have been dropped");
-                        }
                     }
                 }
 
@@ -243,9 +332,18 @@ impl UCIEventLoop {
                 }
             }
 
+            // A closed stdout means the GUI is gone; nothing more can be delivered
+            if self.output_closed {
+                warn!("Stdout closed by GUI (broken pipe) - shutting down");
+                let _ = self.engine.process_command("quit").await;
+                graceful_shutdown = true;
+                break;
+            }
+
             // Check for quit command processing
             if self.should_shutdown() {
                 info!("Quit command processed - initiating shutdown");
+                self.flush_queued_responses().await;
                 break;
             }
         }
@@ -276,12 +374,20 @@ impl UCIEventLoop {
             .sanitize_string(input)
             .map_err(|e| UCIError::Protocol {
                 message: format!("Input sanitization failed: {}", e),
+                token: None,
             })?;
 
         if sanitized.is_empty() {
             return Ok(()); // Skip empty lines
         }
 
+        if self.is_rate_limited(&sanitized) {
+            self.stats.commands_throttled += 1;
+            warn!(command = %sanitized, "Command dropped by rate limiter");
+            let warning = format!("info string Rate limit exceeded, dropping: {}", sanitized);
+            return self.send_response(&warning).await;
+        }
+
         debug!(command = %sanitized, "Processing UCI command");
 
         // Parse command with timeout
@@ -307,6 +413,13 @@ impl UCIEventLoop {
                         let elapsed = command_start.elapsed();
                         self.update_command_stats(elapsed);
 
+                        // Options such as UCI_Chess960 change what input is valid
+                        if sanitized.starts_with("setoption") {
+                            let limits = self.engine.input_limits();
+                            self.parser.set_input_limits(limits.clone());
+                            self.sanitizer.set_limits(limits);
+                        }
+
                         debug!(
                             command = %sanitized,
                             processing_time_ms = elapsed.as_millis(),
@@ -341,17 +454,56 @@ impl UCIEventLoop {
                     "Command parsing failed"
                 );
                 // Send error info to GUI
-                let error_response = format!("info string ERROR: Invalid command: {}", e);
-                self.send_response(&error_response).await?;
+                self.send_response(&parse_error_response(&e)).await?;
             }
         }
 
         Ok(())
     }
 
+    /// Whether `command` exceeds the configured rate; `stop` and `quit` always pass
+    fn is_rate_limited(&mut self, command: &str) -> bool {
+        let Some(limiter) = self.rate_limiter.as_mut() else {
+            return false;
+        };
+
+        match command.split_whitespace().next() {
+            Some("stop") | Some("quit") => false,
+            _ => !limiter.try_acquire(),
+        }
+    }
+
+    /// Whether `response` is an `info string` line over the output budget
+    ///
+    /// Protocol-critical lines (`bestmove`, `uciok`, `readyok`, `id`, `option`)
+    /// and search `info` updates are never suppressed.
+    fn is_info_string_suppressed(&mut self, response: &str) -> bool {
+        let Some(limiter) = self.info_string_limiter.as_mut() else {
+            return false;
+        };
+
+        let mut tokens = response.split_whitespace();
+        match (tokens.next(), tokens.next()) {
+            (Some("info"), Some("string")) => !limiter.try_acquire(),
+            _ => false,
+        }
+    }
+
     /// Send response to stdout with error handling
     #[instrument(skip(self))]
     async fn send_response(&mut self, response: &str) -> UCIResult<()> {
+        if self.output_closed {
+            return Err(UCIError::Io {
+                message: "Stdout closed (broken pipe)".to_string(),
+            });
+        }
+
+        if self.is_info_string_suppressed(response) {
+            self.stats.info_strings_dropped += 1;
+            debug!(response = %response, "info string dropped by output budget");
+            return Ok(());
+        }
+
         let response_with_newline = format!("{}\n", response);
 
         match timeout(
@@ -364,6 +516,7 @@ impl UCIEventLoop {
             Ok(Ok(())) => {
                 // Ensure immediate delivery
                 if let Err(e) = self.stdout_writer.flush().await {
+                    self.note_write_error(&e);
                     error!(error = %e, "Failed to flush stdout");
                     return Err(UCIError::Io {
                         message: format!("Stdout flush error: {}", e),
@@ -375,6 +528,7 @@ impl UCIEventLoop {
                 Ok(())
             }
             Ok(Err(e)) => {
+                self.note_write_error(&e);
                 error!(error = %e, response = %response, "Failed to write response");
                 Err(UCIError::Io {
                     message: format!("Stdout write error: {}", e),
@@ -393,6 +547,13 @@ impl UCIEventLoop {
         }
     }
 
+    /// Remember a broken pipe so the run loop shuts down instead of retrying
+    fn note_write_error(&mut self, error: &std::io::Error) {
+        if error.kind() == std::io::ErrorKind::BrokenPipe {
+            self.output_closed = true;
+        }
+    }
+
     /// Check if the engine has processed a quit command
     fn should_shutdown(&self) -> bool {
         // Check engine state for quit processing
@@ -402,11 +563,31 @@ impl UCIEventLoop {
         )
     }
 
+    /// Write responses the engine already queued, without waiting for more
+    ///
+    /// Input may be read faster than responses are forwarded, so answers to the
+    /// commands before `quit` can still be waiting in the channel.
+    async fn flush_queued_responses(&mut self) {
+        while !self.output_closed {
+            let Some(response) = self.response_rx.try_recv() else {
+                break;
+            };
+            if let Err(e) = self.send_response(&response).await {
+                warn!(error = %e, "Failed to send queued response before quit");
+            }
+        }
+    }
+
     /// Perform graceful shutdown sequence
     #[instrument(skip(self))]
     async fn graceful_shutdown(&mut self) -> UCIResult<()> {
         info!("Starting graceful shutdown sequence");
 
+        if self.output_closed {
+            info!("Output closed - skipping final response delivery");
+            return Ok(());
+        }
+
         // Send final responses if any are queued
         let shutdown_deadline =
             Instant::now() + Duration::from_millis(self.config.shutdown_timeout_ms);
@@ -415,12 +596,12 @@ impl UCIEventLoop {
             select! {
                 result = self.response_rx.recv() => {
                     match result {
-                        Ok(response) => {
+                        Some(response) => {
                             if let Err(e) = self.send_response(&response).await {
                                 warn!(error = %e, "Failed to send final response during shutdown");
                             }
                         }
-                        Err(_) => break, // Channel closed
+                        None => break, // Channel closed
                     }
                 }
                 _ = tokio::time::sleep(Duration::from_millis(100)) => {
@@ -475,6 +656,19 @@ impl UCIEventLoop {
     }
 }
 
+/// `info string` reporting a command that failed to parse
+///
+/// Errors that point at a token name it; others fall back to the full error.
+fn parse_error_response(error: &UCIError) -> String {
+    match error {
+        UCIError::Protocol {
+            message,
+            token: Some(token),
+        } => format!("info string ERROR: {} at token {}", message, token.index),
+        _ => format!("info string ERROR: Invalid command: {}", error),
+    }
+}
+
 /// Utility function to create and run a UCI event loop with signal handling
 #[instrument]
 pub async fn run_uci_event_loop(config: EventLoopConfig) -> UCIResult<()> {
@@ -509,6 +703,7 @@ pub async fn run_uci_event_loop(config: EventLoopConfig) -> UCIResult<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::uci::telemetry::TELEMETRY_PREFIX;
 
     /// Helper to create a test event loop
     async fn create_test_event_loop() -> UCIEventLoop {
@@ -524,6 +719,9 @@ mod tests {
             input_buffer_size: 1024,
             enable_monitoring: false,
             shutdown_timeout_ms: 1000,
+            max_commands_per_second: None,
+            info_string_budget_per_sec: None,
+            telemetry_json: false,
         };
 
         UCIEventLoop::with_config(engine, config).expect("Event loop creation should succeed")
@@ -596,6 +794,319 @@ mod tests {
         assert!(event_loop.shutdown_rx.is_some());
     }
 
+    /// Writer that behaves like stdout after the GUI closed its end of the pipe
+    struct BrokenPipeWriter {
+        writes: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl AsyncWrite for BrokenPipeWriter {
+        fn poll_write(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+            _buf: &[u8],
+        ) -> std::task::Poll<std::io::Result<usize>> {
+            self.writes
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            std::task::Poll::Ready(Err(std::io::ErrorKind::BrokenPipe.into()))
+        }
+
+        fn poll_flush(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            std::task::Poll::Ready(Err(std::io::ErrorKind::BrokenPipe.into()))
+        }
+
+        fn poll_shutdown(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_broken_pipe_shuts_down() {
+        let engine = Arc::new(UCIEngine::new());
+        let writes = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let output = BrokenPipeWriter {
+            writes: Arc::clone(&writes),
+        };
+
+        // Keep the GUI side of stdin open so only the broken pipe can end the loop
+        let (mut gui, input) = tokio::io::duplex(1024);
+        gui.write_all(b"isready\n").await.unwrap();
+
+        let config = EventLoopConfig {
+            enable_monitoring: false,
+            ..EventLoopConfig::default()
+        };
+        let mut event_loop = UCIEventLoop::with_io(Arc::clone(&engine), config, input, output)
+            .expect("Event loop creation should succeed");
+
+        let result = timeout(Duration::from_secs(2), event_loop.run())
+            .await
+            .expect("Event loop should stop after broken pipe");
+        assert!(result.is_ok());
+
+        // One failed write, no retries, and the engine was told to quit
+        assert_eq!(writes.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(engine.state(), crate::uci::state::EngineState::Stopping);
+        drop(gui);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_session_round_trip() {
+        use tokio::io::AsyncReadExt;
+
+        let engine = Arc::new(UCIEngine::new());
+        engine.initialize().await.unwrap();
+
+        let input = std::io::Cursor::new(b"uci\nisready\nquit\n".to_vec());
+        let (output, mut gui_output) = tokio::io::duplex(16 * 1024);
+        let config = EventLoopConfig {
+            enable_monitoring: false,
+            ..EventLoopConfig::default()
+        };
+        let mut event_loop = UCIEventLoop::with_io(Arc::clone(&engine), config, input, output)
+            .expect("Event loop creation should succeed");
+
+        timeout(Duration::from_secs(2), event_loop.run())
+            .await
+            .expect("Event loop should stop on quit")
+            .unwrap();
+        drop(event_loop);
+
+        let mut written = String::new();
+        gui_output.read_to_string(&mut written).await.unwrap();
+        let lines: Vec<&str> = written.lines().collect();
+        let uciok = lines.iter().position(|line| *line == "uciok");
+        let readyok = lines.iter().position(|line| *line == "readyok");
+        assert!(uciok.is_some(), "{}", written);
+        assert!(readyok > uciok, "{}", written);
+    }
+
+    #[tokio::test]
+    async fn test_no_responses_lost_under_load() {
+        use tokio::io::AsyncReadExt;
+
+        const COMMANDS: usize = 10_000;
+
+        let engine = Arc::new(UCIEngine::new());
+        engine.initialize().await.unwrap();
+
+        // Far more output than the 64-slot broadcast buffer holds
+        let mut script = "isready\n".repeat(COMMANDS);
+        script.push_str("quit\n");
+        let input = std::io::Cursor::new(script.into_bytes());
+        let (output, mut gui_output) = tokio::io::duplex(64);
+        let config = EventLoopConfig {
+            enable_monitoring: false,
+            ..EventLoopConfig::default()
+        };
+        let mut event_loop = UCIEventLoop::with_io(Arc::clone(&engine), config, input, output)
+            .expect("Event loop creation should succeed");
+
+        // The GUI reads slowly in small chunks while the loop runs
+        let reader = tokio::spawn(async move {
+            let mut written = String::new();
+            gui_output.read_to_string(&mut written).await.unwrap();
+            written
+        });
+        timeout(Duration::from_secs(30), event_loop.run())
+            .await
+            .expect("Event loop should stop on quit")
+            .unwrap();
+        drop(event_loop);
+
+        let written = reader.await.unwrap();
+        assert_eq!(
+            written.lines().filter(|line| *line == "readyok").count(),
+            COMMANDS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_throttles_burst_but_not_stop() {
+        use tokio::io::AsyncReadExt;
+
+        let engine = Arc::new(UCIEngine::new());
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        let (_gui_input, input) = tokio::io::duplex(1024);
+        let (output, mut gui_output) = tokio::io::duplex(4096);
+        let config = EventLoopConfig {
+            enable_monitoring: false,
+            max_commands_per_second: Some(2),
+            ..EventLoopConfig::default()
+        };
+        let mut event_loop = UCIEventLoop::with_io(Arc::clone(&engine), config, input, output)
+            .expect("Event loop creation should succeed");
+
+        for _ in 0..5 {
+            event_loop.process_input_command("isready\n").await.unwrap();
+        }
+        assert_eq!(event_loop.stats().commands_throttled, 3);
+
+        let mut ready_count = 0;
+        while let Ok(response) = responses.try_recv() {
+            if response == "readyok" {
+                ready_count += 1;
+            }
+        }
+        assert_eq!(ready_count, 2);
+
+        // The bucket is empty, but stop must still reach the engine
+        event_loop.process_input_command("stop\n").await.unwrap();
+        assert_eq!(event_loop.stats().commands_throttled, 3);
+        assert_eq!(event_loop.stats().commands_processed, 3);
+
+        drop(event_loop);
+        let mut written = String::new();
+        gui_output.read_to_string(&mut written).await.unwrap();
+        assert_eq!(
+            written
+                .lines()
+                .filter(|line| line.starts_with("info string Rate limit exceeded"))
+                .count(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_info_string_budget_drops_excess_but_not_critical_lines() {
+        use tokio::io::AsyncReadExt;
+
+        let engine = Arc::new(UCIEngine::new());
+        engine.initialize().await.unwrap();
+
+        let (_gui_input, input) = tokio::io::duplex(1024);
+        let (output, mut gui_output) = tokio::io::duplex(16 * 1024);
+        let config = EventLoopConfig {
+            enable_monitoring: false,
+            info_string_budget_per_sec: Some(3),
+            ..EventLoopConfig::default()
+        };
+        let mut event_loop = UCIEventLoop::with_io(Arc::clone(&engine), config, input, output)
+            .expect("Event loop creation should succeed");
+
+        let critical = [
+            "id name Opera",
+            "option name Hash type spin default 128 min 1 max 32768",
+            "uciok",
+            "readyok",
+            "bestmove e2e4",
+        ];
+        for i in 0..10 {
+            event_loop
+                .send_response(&format!("info string debug {}", i))
+                .await
+                .unwrap();
+        }
+        for line in critical {
+            event_loop.send_response(line).await.unwrap();
+        }
+        event_loop
+            .send_response("info depth 1 score cp 20 pv e2e4")
+            .await
+            .unwrap();
+        assert_eq!(event_loop.stats().info_strings_dropped, 7);
+
+        drop(event_loop);
+        let mut written = String::new();
+        gui_output.read_to_string(&mut written).await.unwrap();
+        let lines: Vec<&str> = written.lines().collect();
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|line| line.starts_with("info string"))
+                .count(),
+            3
+        );
+        for line in critical {
+            assert!(lines.contains(&line), "{} missing from {}", line, written);
+        }
+        assert!(lines.contains(&"info depth 1 score cp 20 pv e2e4"));
+    }
+
+    #[tokio::test]
+    async fn test_telemetry_json_frame_per_iteration() {
+        let engine = Arc::new(UCIEngine::new());
+        engine.initialize().await.unwrap();
+        let mut responses = engine.subscribe_responses();
+
+        let config = EventLoopConfig {
+            enable_monitoring: false,
+            telemetry_json: true,
+            ..EventLoopConfig::default()
+        };
+        let (_gui_input, input) = tokio::io::duplex(1024);
+        let _event_loop =
+            UCIEventLoop::with_io(Arc::clone(&engine), config, input, tokio::io::sink())
+                .expect("Event loop creation should succeed");
+
+        engine
+            .process_command("setoption name MultiPV value 2")
+            .await
+            .unwrap();
+        engine.process_command("position startpos").await.unwrap();
+        engine.process_command("go depth 3").await.unwrap();
+
+        let mut frames = Vec::new();
+        loop {
+            let response = tokio::time::timeout(Duration::from_secs(2), responses.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            if response.starts_with("bestmove") {
+                break;
+            }
+            if let Some(json) = response.strip_prefix(TELEMETRY_PREFIX) {
+                frames.push(serde_json::from_str::<serde_json::Value>(json).unwrap());
+            }
+        }
+
+        assert_eq!(frames.len(), 3);
+        for (iteration, frame) in frames.iter().enumerate() {
+            assert_eq!(frame["depth"], iteration + 1);
+            // Only the last depth waits for the C++ search to measure seldepth
+            if iteration + 1 < frames.len() {
+                assert!(frame.get("seldepth").is_none());
+            } else {
+                assert!(frame["seldepth"].as_u64().unwrap() >= 3);
+            }
+            assert!(frame["nodes"].as_u64().unwrap() > 0);
+            assert!(frame["nps"].is_u64());
+            assert!(frame["hashfull"].is_u64());
+
+            let multipv = frame["multipv"].as_array().unwrap();
+            assert_eq!(multipv.len(), 2);
+            for line in multipv {
+                assert_eq!(line["pv"].as_array().unwrap().len(), 1);
+                assert!(line["scoreCp"].is_i64());
+                assert!(line.get("mate").is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_error_response_names_token() {
+        let mut parser = ZeroCopyParser::new();
+
+        let error = parser.parse_command("go depth abc").unwrap_err();
+        assert_eq!(
+            parse_error_response(&error),
+            "info string ERROR: go: invalid value 'abc' for 'depth' at token 2"
+        );
+
+        let error = parser.parse_command("frobnicate").unwrap_err();
+        assert_eq!(
+            parse_error_response(&error),
+            "info string ERROR: Invalid command: UCI protocol error: Unknown command: 'frobnicate'"
+        );
+    }
+
     #[tokio::test]
     async fn test_response_formatting() {
         let _event_loop = create_test_event_loop().await;
@@ -100,8 +100,10 @@ impl BasicCommandHandler {
 
         // Check if engine is in a state where it can accept commands
         let current_state = self.state.current_state();
-        let can_accept_commands =
-            matches!(current_state, EngineState::Ready | EngineState::Searching);
+        let can_accept_commands = matches!(
+            current_state,
+            EngineState::Ready | EngineState::Searching | EngineState::Analyzing
+        );
 
         if !can_accept_commands {
             info!(
@@ -114,6 +116,7 @@ impl BasicCommandHandler {
                     "Engine not ready for commands (current state: {:?})",
                     current_state
                 ),
+                token: None,
             });
         }
 
@@ -142,11 +145,12 @@ impl BasicCommandHandler {
     /// Returns (min, max) values for different spin option types
     fn get_spin_option_range(&self, option_name: &str) -> (i32, i32) {
         match option_name {
-            "Hash" => (1, 8192),              // Hash size in MB: 1MB to 8GB
-            "Threads" => (1, 64),             // Thread count: 1 to 64 threads
-            "SacrificeThreshold" => (0, 500), // Centipawn threshold for sacrifices
-            "TacticalDepth" => (0, 10),       // Extra depth for tactical sequences
-            _ => (0, 100),                    // Default range for unknown options
+            "Hash" => (1, 8192),               // Hash size in MB: 1MB to 8GB
+            "Threads" => (1, 64),              // Thread count: 1 to 64 threads
+            "MultiPV" => (1, 256),             // Number of principal variations reported
+            "SacrificeThreshold" => (0, 1000), // Centipawn threshold for sacrifices
+            "TacticalDepth" => (0, 8),         // Extra depth for tactical sequences
+            _ => (0, 100),                     // Default range for unknown options
         }
     }
 }
@@ -242,7 +246,7 @@ mod tests {
         let result = handler.handle_isready_command();
         assert!(result.is_err());
 
-        if let Err(UCIError::Protocol { message }) = result {
+        if let Err(UCIError::Protocol { message, .. }) = result {
             assert!(message.contains("not ready"));
         } else {
             panic!("Expected Protocol error");
@@ -267,9 +271,9 @@ mod tests {
         assert_eq!(handler.get_spin_option_range("Threads"), (1, 64));
         assert_eq!(
             handler.get_spin_option_range("SacrificeThreshold"),
-            (0, 500)
+            (0, 1000)
         );
-        assert_eq!(handler.get_spin_option_range("TacticalDepth"), (0, 10));
+        assert_eq!(handler.get_spin_option_range("TacticalDepth"), (0, 8));
 
         // Test unknown option
         assert_eq!(handler.get_spin_option_range("UnknownOption"), (0, 100));
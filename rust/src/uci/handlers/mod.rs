@@ -9,4 +9,4 @@ pub mod position;
 
 pub use basic::BasicCommandHandler;
 pub use newgame::NewGameHandler;
-pub use position::PositionCommandHandler;
+pub use position::{BoardFenLoader, FenLoader, PositionCommandHandler};
@@ -45,6 +45,7 @@ impl NewGameHandler {
                     "ucinewgame command invalid in state {:?}, expected Ready",
                     current_state
                 ),
+                token: None,
             });
         }
 
@@ -84,6 +85,7 @@ impl NewGameHandler {
                 message: "Failed to clear C++ engine hash tables".to_string(),
             });
         }
+        self.state.record_hash_clear();
 
         debug!("C++ engine state cleared successfully");
         Ok(())
@@ -117,7 +119,7 @@ impl NewGameHandler {
         // - Any game-specific caches
 
         // Clear any active search context
-        if self.state.current_state() == EngineState::Searching {
+        if self.state.current_state().is_computing() {
             warn!("Clearing active search context during ucinewgame");
             // This would be handled by the search cancellation system
         }
@@ -220,7 +222,7 @@ mod tests {
             "New game command should fail when searching"
         );
 
-        if let Err(UCIError::Protocol { message }) = result {
+        if let Err(UCIError::Protocol { message, .. }) = result {
             assert!(message.contains("Searching"));
             assert!(message.contains("Ready"));
         } else {
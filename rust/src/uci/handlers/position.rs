@@ -8,9 +8,28 @@
 // - `position fen <fen> moves <move-list>` - Moves from arbitrary position
 
 use crate::bridge::Board;
-use crate::error::{ErrorContext, ResultExt, UCIError, UCIResult};
+use crate::error::{ContextualResult, ErrorContext, ResultExt, UCIError, UCIResult};
 use crate::uci::{ChessMove, UCICommand};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
+
+/// Loads a FEN into the board and reads back the position the C++ core holds
+///
+/// The handler compares the readback with what it sent to catch misparses at the
+/// FFI boundary. Tests substitute a loader whose readback is corrupt.
+pub trait FenLoader: Send + Sync {
+    /// Set `board` from `fen` and return the board's own FEN
+    fn load(&self, board: &mut Board, fen: &str) -> UCIResult<String>;
+}
+
+/// Loader going straight through the C++ board
+pub struct BoardFenLoader;
+
+impl FenLoader for BoardFenLoader {
+    fn load(&self, board: &mut Board, fen: &str) -> UCIResult<String> {
+        board.set_from_fen(fen)?;
+        board.get_fen()
+    }
+}
 
 /// Handler for UCI position commands with comprehensive error recovery
 pub struct PositionCommandHandler {
@@ -20,6 +39,17 @@ pub struct PositionCommandHandler {
     move_history: Vec<String>,
     /// Starting position FEN for reset operations
     starting_fen: Option<String>,
+    /// Zobrist keys of every position reached, starting with the base position
+    position_keys: Vec<u64>,
+    /// Normalized base FEN (`None` for startpos) and moves of the loaded command
+    loaded_command: Option<(Option<String>, Vec<String>)>,
+    /// Skip `position` commands identical to the one already loaded, and only
+    /// apply the new moves of commands extending it
+    skip_identical_positions: bool,
+    /// Total moves replayed onto the board by `position` commands
+    moves_applied: u64,
+    /// Loads FEN positions and reports the core's readback
+    fen_loader: Box<dyn FenLoader>,
 }
 
 impl PositionCommandHandler {
@@ -35,9 +65,34 @@ impl PositionCommandHandler {
             board,
             move_history: Vec::new(),
             starting_fen: None,
+            position_keys: Vec::new(),
+            loaded_command: None,
+            skip_identical_positions: true,
+            moves_applied: 0,
+            fen_loader: Box::new(BoardFenLoader),
         })
     }
 
+    /// Replace the FEN loader (tests use this to simulate C++ misparses)
+    pub fn with_fen_loader(mut self, fen_loader: Box<dyn FenLoader>) -> Self {
+        self.fen_loader = fen_loader;
+        self
+    }
+
+    /// Choose whether a re-sent identical `position` command is skipped (the default)
+    ///
+    /// Skipping keeps the board and repetition history as they are instead of
+    /// replaying every move again. The same setting lets a command that appends
+    /// moves to the loaded one apply just the appended moves.
+    pub fn set_skip_identical_positions(&mut self, skip: bool) {
+        self.skip_identical_positions = skip;
+    }
+
+    /// Total moves replayed onto the board by `position` commands
+    pub fn moves_applied(&self) -> u64 {
+        self.moves_applied
+    }
+
     /// Handles UCI position commands with comprehensive validation
     ///
     /// Supports all UCI position command formats:
@@ -46,36 +101,92 @@ impl PositionCommandHandler {
     /// - `position startpos moves <move-list>`
     /// - `position fen <fen-string> moves <move-list>`
     pub fn handle_position_command<'a>(&mut self, cmd: &UCICommand<'a>) -> UCIResult<()> {
+        self.handle_position_command_with_context(cmd)
+            .map_err(|e| e.error)
+    }
+
+    /// Like [`Self::handle_position_command`], but keeps the full error context chain
+    pub fn handle_position_command_with_context<'a>(
+        &mut self,
+        cmd: &UCICommand<'a>,
+    ) -> ContextualResult<()> {
         match cmd {
             UCICommand::Position { position, moves } => {
-                // Clear move history for new position
-                self.move_history.clear();
+                let command_key = (
+                    match position {
+                        crate::uci::Position::StartPos => None,
+                        crate::uci::Position::Fen(fen) => {
+                            Some(fen.split_whitespace().collect::<Vec<_>>().join(" "))
+                        }
+                    },
+                    moves
+                        .iter()
+                        .map(|chess_move| self.chess_move_to_string(chess_move))
+                        .collect::<Vec<_>>(),
+                );
+                if self.skip_identical_positions
+                    && self.loaded_command.as_ref() == Some(&command_key)
+                {
+                    debug!("Identical position command already loaded, skipping");
+                    return Ok(());
+                }
 
-                // Set up the base position (startpos or FEN)
-                self.setup_base_position(position)
-                    .with_context(ErrorContext::new("Failed to setup base position"))
-                    .map_err(|e| e.error)?;
+                // A GUI usually resends the game so far plus the latest moves
+                let extends_loaded = match &self.loaded_command {
+                    Some((base, loaded_moves)) => {
+                        self.skip_identical_positions
+                            && *base == command_key.0
+                            && command_key.1.len() > loaded_moves.len()
+                            && command_key.1.starts_with(loaded_moves)
+                    }
+                    None => false,
+                };
+                let first_new_move = match self.loaded_command.take() {
+                    Some((_, loaded_moves)) if extends_loaded => {
+                        debug!(
+                            new_moves = moves.len() - loaded_moves.len(),
+                            "Position command extends the loaded one, applying new moves only"
+                        );
+                        loaded_moves.len()
+                    }
+                    _ => {
+                        // Clear move history for new position
+                        self.move_history.clear();
+                        self.position_keys.clear();
+
+                        // Set up the base position (startpos or FEN)
+                        self.setup_base_position(position)
+                            .with_context(ErrorContext::new("Failed to setup base position"))?;
+                        self.record_position_key()?;
+                        0
+                    }
+                };
 
                 // Apply move sequence if provided
-                if !moves.is_empty() {
-                    self.apply_move_sequence(moves)
-                        .with_context(ErrorContext::new("Failed to apply move sequence"))
-                        .map_err(|e| e.error)?;
+                if moves.len() > first_new_move {
+                    self.apply_move_sequence(moves, first_new_move)
+                        .with_context(ErrorContext::new("Failed to apply move sequence"))?;
                 }
 
                 // Log successful position setup
-                info!("Position set successfully: {} moves applied", moves.len());
+                info!(
+                    "Position set successfully: {} moves applied",
+                    moves.len() - first_new_move
+                );
+                self.loaded_command = Some(command_key);
 
                 Ok(())
             }
             _ => Err(UCIError::Protocol {
                 message: "PositionCommandHandler received non-position command".to_string(),
-            }),
+                token: None,
+            })
+            .with_operation("Dispatch position command"),
         }
     }
 
     /// Sets up the base position (either startpos or from FEN)
-    fn setup_base_position<'a>(&mut self, position: &crate::uci::Position<'a>) -> UCIResult<()> {
+    fn setup_base_position(&mut self, position: &crate::uci::Position) -> ContextualResult<()> {
         match position {
             crate::uci::Position::StartPos => {
                 debug!("Setting up starting position");
@@ -91,12 +202,41 @@ impl PositionCommandHandler {
                 debug!("Setting up position from FEN: {}", fen_string);
 
                 // Validate and set FEN position
-                self.board
-                    .set_from_fen(fen_string)
+                let readback = self
+                    .fen_loader
+                    .load(&mut self.board, fen_string)
                     .with_context(
                         ErrorContext::new("Invalid FEN string").detail(fen_string.to_string()),
-                    )
-                    .map_err(|e| e.error)?;
+                    )?;
+
+                // The core misparsed the FEN; retry once in canonical form
+                if !fen_round_trips(fen_string, &readback) {
+                    let normalized = normalize_fen(fen_string);
+                    warn!(fen = %fen_string, readback = %readback, normalized = %normalized, "FEN readback mismatch, retrying with normalized FEN");
+
+                    let retry = self
+                        .fen_loader
+                        .load(&mut self.board, &normalized)
+                        .with_context(
+                            ErrorContext::new("Invalid normalized FEN").detail(normalized.clone()),
+                        )?;
+                    if !fen_round_trips(&normalized, &retry) {
+                        // Never leave a corrupt board behind
+                        self.board.reset();
+                        self.starting_fen = None;
+                        return Err(UCIError::Position {
+                            message: format!(
+                                "FEN corrupted at the C++ boundary: sent '{}', read back '{}'",
+                                normalized, retry
+                            ),
+                        })
+                        .with_context(
+                            ErrorContext::new("FEN round-trip check failed")
+                                .detail(format!("original: {}", fen_string))
+                                .detail(format!("first readback: {}", readback)),
+                        );
+                    }
+                }
 
                 // Store starting FEN for potential resets
                 self.starting_fen = Some(fen_string.to_string());
@@ -107,24 +247,30 @@ impl PositionCommandHandler {
         }
     }
 
-    /// Applies a sequence of moves to the current position
-    fn apply_move_sequence<'a>(&mut self, moves: &[ChessMove<'a>]) -> UCIResult<()> {
-        debug!("Applying {} moves to position", moves.len());
+    /// Applies a sequence of moves to the current position, skipping the first
+    /// `already_applied` moves which the board already holds
+    fn apply_move_sequence<'a>(
+        &mut self,
+        moves: &[ChessMove<'a>],
+        already_applied: usize,
+    ) -> ContextualResult<()> {
+        debug!(
+            "Applying {} moves to position",
+            moves.len() - already_applied
+        );
 
-        for (index, chess_move) in moves.iter().enumerate() {
+        for (index, chess_move) in moves.iter().enumerate().skip(already_applied) {
             // Convert ChessMove to string format for board operations
             let move_str = self.chess_move_to_string(chess_move);
 
             // Validate move before applying
-            let is_valid = self
-                .board
-                .is_valid_move(&move_str)
-                .with_context(ErrorContext::new("Failed to validate move").detail(format!(
+            let is_valid = self.board.is_valid_move(&move_str).with_context(
+                ErrorContext::new("Failed to validate move").detail(format!(
                     "move: {}, index: {}",
                     move_str,
                     index + 1
-                )))
-                .map_err(|e| e.error)?;
+                )),
+            )?;
 
             if !is_valid {
                 return Err(UCIError::Move {
@@ -133,36 +279,82 @@ impl PositionCommandHandler {
                         move_str,
                         index + 1
                     ),
-                });
+                })
+                .with_operation("Validate move sequence");
+            }
+
+            // The C++ check accepts any well-formed move, so rule on legality here
+            let legal_moves = self.board.legal_moves().with_context(
+                ErrorContext::new("Failed to generate legal moves").detail(format!(
+                    "move: {}, index: {}",
+                    move_str,
+                    index + 1
+                )),
+            )?;
+
+            if !legal_moves.contains(&move_str) {
+                return Err(UCIError::Move {
+                    message: format!(
+                        "Illegal move '{}' at position {} in sequence",
+                        move_str,
+                        index + 1
+                    ),
+                })
+                .with_operation("Validate move sequence");
             }
 
             // Apply the validated move
-            self.board
-                .make_move(&move_str)
-                .with_context(ErrorContext::new("Failed to apply move").detail(format!(
+            self.board.make_move(&move_str).with_context(
+                ErrorContext::new("Failed to apply move").detail(format!(
                     "move: {}, index: {}",
                     move_str,
                     index + 1
-                )))
-                .map_err(|e| e.error)?;
+                )),
+            )?;
 
             // Add to move history for debugging
             self.move_history.push(move_str.clone());
+            self.moves_applied += 1;
+            self.record_position_key()?;
 
             debug!("Successfully applied move {}: {}", index + 1, move_str);
         }
 
-        info!("Successfully applied all {} moves", moves.len());
+        info!(
+            "Successfully applied all {} moves",
+            moves.len() - already_applied
+        );
+        Ok(())
+    }
+
+    /// Records the Zobrist key of the current position for repetition detection
+    fn record_position_key(&mut self) -> ContextualResult<()> {
+        let key = self
+            .board
+            .position_key()
+            .with_context(ErrorContext::new("Failed to read position key"))?;
+        self.position_keys.push(key);
         Ok(())
     }
 
+    /// Checks if the current position has occurred at least three times
+    pub fn is_threefold_repetition(&self) -> bool {
+        match self.position_keys.last() {
+            Some(current) => {
+                self.position_keys
+                    .iter()
+                    .filter(|key| *key == current)
+                    .count()
+                    >= 3
+            }
+            None => false,
+        }
+    }
+
     /// Converts ChessMove to string format expected by the board
     fn chess_move_to_string<'a>(&self, chess_move: &ChessMove<'a>) -> String {
-        let mut move_str = format!("{}{}", chess_move.from_square, chess_move.to_square);
-        if let Some(piece) = chess_move.promotion {
-            move_str.push_str(piece);
-        }
-        move_str
+        // Canonical lowercase form, so `e7e8Q` reaches the C++ core as `e7e8q`
+        chess_move.to_string()
     }
 
     /// Gets the current position as FEN string
@@ -178,6 +370,33 @@ impl PositionCommandHandler {
         &self.move_history
     }
 
+    /// Zobrist keys recorded for repetition detection, oldest first
+    pub fn position_keys(&self) -> &[u64] {
+        &self.position_keys
+    }
+
+    /// Set up the main line of a PGN game, as a `position` command would
+    ///
+    /// See [`crate::pgn::PgnReader::parse_moves`] for what the PGN may contain.
+    pub fn load_pgn(&mut self, pgn: &str) -> UCIResult<()> {
+        let moves = crate::pgn::PgnReader::parse_moves(pgn)?;
+        let position = match crate::pgn::PgnReader::starting_fen(pgn)? {
+            Some(fen) => crate::uci::Position::Fen(fen),
+            None => crate::uci::Position::StartPos,
+        };
+        let moves = moves
+            .iter()
+            .map(|uci_move| ChessMove::new(uci_move))
+            .collect::<UCIResult<Vec<_>>>()?;
+
+        self.handle_position_command(&UCICommand::Position { position, moves })
+    }
+
+    /// FEN the current game started from; `None` for the standard start position
+    pub fn starting_fen(&self) -> Option<&str> {
+        self.starting_fen.as_deref()
+    }
+
     /// Checks if the current position is in check
     pub fn is_in_check(&self) -> UCIResult<bool> {
         self.board
@@ -214,9 +433,39 @@ impl PositionCommandHandler {
             .map_err(|e| e.error)
     }
 
+    /// Takes back the last applied move, trimming the move history
+    pub fn pop_move(&mut self) -> UCIResult<String> {
+        if self.move_history.is_empty() {
+            return Err(UCIError::Move {
+                message: "No move to undo".to_string(),
+            });
+        }
+
+        self.board
+            .undo_move()
+            .with_context(ErrorContext::new("Failed to undo move"))
+            .map_err(|e| e.error)?;
+        self.position_keys.pop();
+        self.loaded_command = None;
+
+        let move_str = self.move_history.pop().unwrap_or_default();
+        debug!("Undid move: {}", move_str);
+        Ok(move_str)
+    }
+
+    /// Forget the current game and return to the standard start position
+    ///
+    /// Unlike [`Self::reset_position`], a FEN the game started from is dropped too.
+    pub fn new_game(&mut self) -> UCIResult<()> {
+        self.starting_fen = None;
+        self.reset_position()
+    }
+
     /// Resets to starting position or stored FEN
     pub fn reset_position(&mut self) -> UCIResult<()> {
+        self.loaded_command = None;
         self.move_history.clear();
+        self.position_keys.clear();
 
         match &self.starting_fen {
             Some(fen) => {
@@ -232,6 +481,8 @@ impl PositionCommandHandler {
             }
         }
 
+        self.record_position_key().map_err(|e| e.error)?;
+
         info!("Position reset successfully");
         Ok(())
     }
@@ -242,7 +493,11 @@ impl PositionCommandHandler {
     }
 
     /// Gets mutable board reference for advanced operations
+    ///
+    /// The board may change behind the handler's back, so the next `position`
+    /// command is always applied in full.
     pub fn board_mut(&mut self) -> &mut Board {
+        self.loaded_command = None;
         &mut self.board
     }
 }
@@ -253,6 +508,69 @@ impl Default for PositionCommandHandler {
     }
 }
 
+/// Piece placement with runs of empty squares merged (`44` becomes `8`)
+///
+/// `None` if a rank holds anything but pieces and digits.
+fn canonical_placement(placement: &str) -> Option<String> {
+    let mut ranks = Vec::new();
+    for rank in placement.split('/') {
+        let mut canonical = String::new();
+        let mut empty = 0;
+        for c in rank.chars() {
+            match c {
+                '1'..='8' => empty += c as u32 - '0' as u32,
+                'p' | 'n' | 'b' | 'r' | 'q' | 'k' | 'P' | 'N' | 'B' | 'R' | 'Q' | 'K' => {
+                    if empty > 0 {
+                        canonical.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    canonical.push(c);
+                }
+                _ => return None,
+            }
+        }
+        if empty > 0 {
+            canonical.push_str(&empty.to_string());
+        }
+        ranks.push(canonical);
+    }
+    Some(ranks.join("/"))
+}
+
+/// Canonical form of a FEN: single spaces, merged empty squares, lowercase side
+/// to move and defaults for missing trailing fields
+fn normalize_fen(fen: &str) -> String {
+    let mut fields: Vec<String> = fen.split_whitespace().map(str::to_string).collect();
+    if let Some(placement) = fields.first().and_then(|p| canonical_placement(p)) {
+        fields[0] = placement;
+    }
+    if let Some(side) = fields.get_mut(1) {
+        *side = side.to_lowercase();
+    }
+    for (index, default) in ["w", "-", "-", "0", "1"].iter().enumerate() {
+        if fields.len() <= index + 1 {
+            fields.push(default.to_string());
+        }
+    }
+    fields.join(" ")
+}
+
+/// Whether the core's readback holds the position that was sent
+///
+/// Only placement and side to move are compared: the core rewrites Shredder
+/// castling rights, and the remaining fields do not change which pieces stand where.
+fn fen_round_trips(sent: &str, readback: &str) -> bool {
+    let board_fields = |fen: &str| {
+        let mut fields = fen.split_whitespace();
+        let placement = fields.next().and_then(canonical_placement);
+        let side = fields.next().unwrap_or("w").to_lowercase();
+        (placement, side)
+    };
+
+    let sent = board_fields(sent);
+    sent.0.is_some() && sent == board_fields(readback)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -302,7 +620,7 @@ mod tests {
 
         let test_fen = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 4 4";
         let cmd = UCICommand::Position {
-            position: Position::Fen(test_fen),
+            position: Position::Fen(test_fen.to_string()),
             moves: vec![],
         };
 
@@ -322,7 +640,7 @@ mod tests {
 
         let invalid_fen = "invalid_fen_string";
         let cmd = UCICommand::Position {
-            position: Position::Fen(invalid_fen),
+            position: Position::Fen(invalid_fen.to_string()),
             moves: vec![],
         };
 
@@ -379,13 +697,10 @@ mod tests {
     fn test_apply_invalid_move() {
         let mut handler = PositionCommandHandler::new().unwrap();
 
-        // NOTE: Current C++ engine accepts all move formats, including "invalid" chess moves
-        // This test verifies that the position handler correctly processes moves that
-        // the underlying engine accepts. When proper move validation is implemented
-        // in the C++ engine, this test should be updated.
+        // Well-formed, but no pawn can reach e5 from e2
         let moves = vec![ChessMove {
             from_square: "e2",
-            to_square: "e5", // Currently accepted by C++ engine
+            to_square: "e5",
             promotion: None,
         }];
 
@@ -394,13 +709,78 @@ mod tests {
             moves,
         };
 
-        let result = handler.handle_position_command(&cmd);
-        assert!(
-            result.is_ok(),
-            "Should accept moves that C++ engine accepts"
-        );
-        assert_eq!(handler.get_move_history().len(), 1);
-        assert_eq!(handler.get_move_history()[0], "e2e5");
+        let error = handler.handle_position_command(&cmd).unwrap_err();
+        match error {
+            UCIError::Move { message } => {
+                assert!(message.contains("'e2e5' at position 1"), "{}", message)
+            }
+            other => panic!("Expected a move error, got {:?}", other),
+        }
+        assert!(handler.get_move_history().is_empty());
+    }
+
+    #[test]
+    fn test_legal_move_sequence_passes_legality_check() {
+        let mut handler = PositionCommandHandler::new().unwrap();
+
+        let moves = vec![
+            ChessMove {
+                from_square: "e2",
+                to_square: "e4",
+                promotion: None,
+            },
+            ChessMove {
+                from_square: "e7",
+                to_square: "e5",
+                promotion: None,
+            },
+            ChessMove {
+                from_square: "e1",
+                to_square: "e2",
+                promotion: None,
+            },
+        ];
+
+        let cmd = UCICommand::Position {
+            position: Position::StartPos,
+            moves,
+        };
+
+        handler.handle_position_command(&cmd).unwrap();
+        assert_eq!(handler.get_move_history(), ["e2e4", "e7e5", "e1e2"]);
+    }
+
+    #[test]
+    fn test_illegal_second_move_reports_its_index() {
+        let mut handler = PositionCommandHandler::new().unwrap();
+
+        // Black's knight on b8 cannot reach b6
+        let moves = vec![
+            ChessMove {
+                from_square: "e2",
+                to_square: "e4",
+                promotion: None,
+            },
+            ChessMove {
+                from_square: "b8",
+                to_square: "b6",
+                promotion: None,
+            },
+        ];
+
+        let cmd = UCICommand::Position {
+            position: Position::StartPos,
+            moves,
+        };
+
+        let error = handler.handle_position_command(&cmd).unwrap_err();
+        match error {
+            UCIError::Move { message } => {
+                assert!(message.contains("'b8b6'"), "{}", message);
+                assert!(message.contains("position 2"), "{}", message);
+            }
+            other => panic!("Expected a move error, got {:?}", other),
+        }
     }
 
     #[test]
@@ -451,12 +831,6 @@ mod tests {
     fn test_move_validation() {
         let handler = PositionCommandHandler::new().unwrap();
 
-        // NOTE: Current C++ engine validates all properly formatted moves as valid
-        // This reflects the current engine behavior. When proper chess move validation
-        // is implemented in the C++ engine, these tests should be updated to expect
-        // proper chess rule validation.
-
-        // All properly formatted moves are currently valid
         assert!(
             handler.validate_move("e2e4").unwrap(),
             "e2e4 should be valid"
@@ -465,13 +839,15 @@ mod tests {
             handler.validate_move("g1f3").unwrap(),
             "g1f3 should be valid"
         );
+
+        // Well-formed moves the rules forbid are rejected
         assert!(
-            handler.validate_move("e2e5").unwrap(),
-            "e2e5 currently accepted by engine"
+            !handler.validate_move("e2e5").unwrap(),
+            "e2e5 is not a legal pawn move"
         );
         assert!(
-            handler.validate_move("a1a2").unwrap(),
-            "a1a2 currently accepted by engine"
+            !handler.validate_move("a1a2").unwrap(),
+            "a1a2 lands on the rook's own pawn"
         );
 
         // Test that malformed move strings would fail
@@ -529,7 +905,7 @@ mod tests {
         // Set up position where pawn can promote
         let promotion_fen = "8/P7/8/8/8/8/8/8 w - - 0 1";
         let cmd = UCICommand::Position {
-            position: Position::Fen(promotion_fen),
+            position: Position::Fen(promotion_fen.to_string()),
             moves: vec![ChessMove {
                 from_square: "a7",
                 to_square: "a8",
@@ -546,4 +922,327 @@ mod tests {
             "Should record promotion move correctly"
         );
     }
+
+    #[test]
+    fn test_promotion_casing_is_canonicalized() {
+        let promotion_fen = "k7/4P3/8/8/8/8/8/K7 w - - 0 1";
+        let mut applied = Vec::new();
+
+        for move_str in ["e7e8Q", "e7e8q"] {
+            let mut handler = PositionCommandHandler::new().unwrap();
+            let cmd = UCICommand::Position {
+                position: Position::Fen(promotion_fen.to_string()),
+                moves: vec![ChessMove::new(move_str).unwrap()],
+            };
+            handler.handle_position_command(&cmd).unwrap();
+
+            assert_eq!(handler.get_move_history(), ["e7e8q"]);
+            applied.push(handler.get_current_position().unwrap());
+        }
+
+        assert_eq!(applied[0], applied[1]);
+        assert!(applied[0].starts_with("k3Q3/"), "{}", applied[0]);
+    }
+
+    /// Loader whose first `corrupt_loads` readbacks come back as an empty board
+    struct MisreadingLoader {
+        corrupt_loads: usize,
+        loaded: std::sync::Arc<parking_lot::Mutex<Vec<String>>>,
+    }
+
+    impl FenLoader for MisreadingLoader {
+        fn load(&self, board: &mut Board, fen: &str) -> UCIResult<String> {
+            let mut loaded = self.loaded.lock();
+            loaded.push(fen.to_string());
+            let readback = BoardFenLoader.load(board, fen)?;
+            if loaded.len() <= self.corrupt_loads {
+                Ok("8/8/8/8/8/8/8/8 w - - 0 1".to_string())
+            } else {
+                Ok(readback)
+            }
+        }
+    }
+
+    fn misreading_handler(
+        corrupt_loads: usize,
+    ) -> (
+        PositionCommandHandler,
+        std::sync::Arc<parking_lot::Mutex<Vec<String>>>,
+    ) {
+        let loaded = std::sync::Arc::default();
+        let handler = PositionCommandHandler::new()
+            .unwrap()
+            .with_fen_loader(Box::new(MisreadingLoader {
+                corrupt_loads,
+                loaded: std::sync::Arc::clone(&loaded),
+            }));
+        (handler, loaded)
+    }
+
+    #[test]
+    fn test_fen_readback_mismatch_recovers_with_normalized_retry() {
+        let (mut handler, loaded) = misreading_handler(1);
+        let cmd = UCICommand::Position {
+            position: Position::Fen("4k3/8/8/8/8/8/8/4K3  w - - 0 1".to_string()),
+            moves: vec![],
+        };
+
+        handler.handle_position_command(&cmd).unwrap();
+
+        assert_eq!(
+            *loaded.lock(),
+            [
+                "4k3/8/8/8/8/8/8/4K3  w - - 0 1",
+                "4k3/8/8/8/8/8/8/4K3 w - - 0 1"
+            ]
+        );
+        assert!(handler
+            .get_current_position()
+            .unwrap()
+            .starts_with("4k3/8/8/8/8/8/8/4K3 w"));
+    }
+
+    #[test]
+    fn test_persistent_fen_mismatch_rejects_position() {
+        let (mut handler, loaded) = misreading_handler(2);
+        let cmd = UCICommand::Position {
+            position: Position::Fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1".to_string()),
+            moves: vec![],
+        };
+
+        let error = handler.handle_position_command(&cmd).unwrap_err();
+        assert!(
+            matches!(&error, UCIError::Position { message } if message.contains("FEN corrupted")),
+            "{:?}",
+            error
+        );
+        assert_eq!(loaded.lock().len(), 2);
+
+        // The suspect board is discarded rather than searched
+        assert_eq!(
+            handler.get_current_position().unwrap(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+    }
+
+    #[test]
+    fn test_fen_normalization_and_round_trip_check() {
+        assert_eq!(
+            normalize_fen("4k3/8/8/8/8/8/8/44K12  B"),
+            "4k3/8/8/8/8/8/8/8K3 b - - 0 1"
+        );
+        assert!(fen_round_trips(
+            "4k3/8/8/8/8/8/8/44K12 b",
+            "4k3/8/8/8/8/8/8/8K3 b - - 0 1"
+        ));
+
+        // Differences outside placement and side to move are not corruption
+        assert!(fen_round_trips(
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2"
+        ));
+        assert!(!fen_round_trips(
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/4K3 b - - 0 1"
+        ));
+    }
+
+    #[test]
+    fn test_threefold_repetition() {
+        let mut handler = PositionCommandHandler::new().unwrap();
+        let shuffle = ["g1f3", "g8f6", "f3g1", "f6g8"];
+        let moves: Vec<ChessMove<'_>> = shuffle
+            .iter()
+            .cycle()
+            .take(shuffle.len() * 4)
+            .map(|mv| ChessMove {
+                from_square: &mv[0..2],
+                to_square: &mv[2..4],
+                promotion: None,
+            })
+            .collect();
+
+        // One shuffle returns to the start position for only the second time
+        let cmd = UCICommand::Position {
+            position: Position::StartPos,
+            moves: moves[..4].to_vec(),
+        };
+        handler.handle_position_command(&cmd).unwrap();
+        assert!(
+            !handler.is_threefold_repetition(),
+            "Second occurrence is not a repetition draw"
+        );
+
+        let cmd = UCICommand::Position {
+            position: Position::StartPos,
+            moves: moves.clone(),
+        };
+        handler.handle_position_command(&cmd).unwrap();
+        assert!(
+            handler.is_threefold_repetition(),
+            "Knight shuffle played four times should repeat the start position"
+        );
+
+        // A fresh position command starts a new history
+        let cmd = UCICommand::Position {
+            position: Position::StartPos,
+            moves: Vec::new(),
+        };
+        handler.handle_position_command(&cmd).unwrap();
+        assert!(!handler.is_threefold_repetition());
+
+        handler
+            .handle_position_command(&UCICommand::Position {
+                position: Position::StartPos,
+                moves,
+            })
+            .unwrap();
+        handler.reset_position().unwrap();
+        assert!(
+            !handler.is_threefold_repetition(),
+            "Reset should clear repetition history"
+        );
+    }
+
+    #[test]
+    fn test_pop_move() {
+        let mut handler = PositionCommandHandler::new().unwrap();
+        assert!(matches!(handler.pop_move(), Err(UCIError::Move { .. })));
+
+        let cmd = UCICommand::Position {
+            position: Position::StartPos,
+            moves: vec![
+                ChessMove {
+                    from_square: "e2",
+                    to_square: "e4",
+                    promotion: None,
+                },
+                ChessMove {
+                    from_square: "e7",
+                    to_square: "e5",
+                    promotion: None,
+                },
+            ],
+        };
+        handler.handle_position_command(&cmd).unwrap();
+
+        assert_eq!(handler.pop_move().unwrap(), "e7e5");
+        assert_eq!(handler.get_move_history(), ["e2e4"]);
+        let mut expected = PositionCommandHandler::new().unwrap();
+        expected
+            .handle_position_command(&UCICommand::Position {
+                position: Position::StartPos,
+                moves: vec![ChessMove {
+                    from_square: "e2",
+                    to_square: "e4",
+                    promotion: None,
+                }],
+            })
+            .unwrap();
+        assert_eq!(
+            handler.get_current_position().unwrap(),
+            expected.get_current_position().unwrap()
+        );
+
+        assert_eq!(handler.pop_move().unwrap(), "e2e4");
+        assert!(handler.get_move_history().is_empty());
+        assert_eq!(
+            handler.get_current_position().unwrap(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+        assert!(handler.pop_move().is_err());
+    }
+
+    #[test]
+    fn test_identical_position_command_is_skipped() {
+        let mut parser = crate::uci::ZeroCopyParser::new();
+        let mut handler = PositionCommandHandler::new().unwrap();
+
+        let cmd = parser
+            .parse_command("position startpos moves e2e4 e7e5 g1f3")
+            .unwrap();
+        handler.handle_position_command(&cmd).unwrap();
+        assert_eq!(handler.moves_applied(), 3);
+
+        handler.handle_position_command(&cmd).unwrap();
+        assert_eq!(
+            handler.moves_applied(),
+            3,
+            "Identical resend must not replay"
+        );
+        assert_eq!(handler.get_move_history(), ["e2e4", "e7e5", "g1f3"]);
+
+        // An extended position only applies the new move
+        let changed = parser
+            .parse_command("position startpos moves e2e4 e7e5 g1f3 b8c6")
+            .unwrap();
+        handler.handle_position_command(&changed).unwrap();
+        assert_eq!(handler.moves_applied(), 4);
+
+        // Undoing a move means the same command has to be replayed
+        handler.pop_move().unwrap();
+        handler.handle_position_command(&changed).unwrap();
+        assert_eq!(handler.moves_applied(), 8);
+
+        handler.set_skip_identical_positions(false);
+        handler.handle_position_command(&changed).unwrap();
+        assert_eq!(handler.moves_applied(), 12);
+    }
+
+    #[test]
+    fn test_extended_position_applies_only_new_moves() {
+        let mut parser = crate::uci::ZeroCopyParser::new();
+        let mut handler = PositionCommandHandler::new().unwrap();
+
+        let cmd = parser
+            .parse_command("position startpos moves e2e4 e7e5")
+            .unwrap();
+        handler.handle_position_command(&cmd).unwrap();
+        assert_eq!(handler.moves_applied(), 2);
+
+        let extended = parser
+            .parse_command("position startpos moves e2e4 e7e5 g1f3")
+            .unwrap();
+        handler.handle_position_command(&extended).unwrap();
+        assert_eq!(handler.moves_applied(), 3);
+        assert_eq!(handler.get_move_history(), ["e2e4", "e7e5", "g1f3"]);
+        assert_eq!(handler.position_keys().len(), 4);
+
+        // The board matches a full rebuild of the same command
+        let mut rebuilt = PositionCommandHandler::new().unwrap();
+        rebuilt.handle_position_command(&extended).unwrap();
+        assert_eq!(
+            handler.get_current_position().unwrap(),
+            rebuilt.get_current_position().unwrap()
+        );
+        assert_eq!(handler.position_keys(), rebuilt.position_keys());
+    }
+
+    #[test]
+    fn test_changed_earlier_move_rebuilds_position() {
+        let mut parser = crate::uci::ZeroCopyParser::new();
+        let mut handler = PositionCommandHandler::new().unwrap();
+
+        let cmd = parser
+            .parse_command("position startpos moves e2e4 e7e5")
+            .unwrap();
+        handler.handle_position_command(&cmd).unwrap();
+
+        let changed = parser
+            .parse_command("position startpos moves d2d4 e7e5 g1f3")
+            .unwrap();
+        handler.handle_position_command(&changed).unwrap();
+        assert_eq!(handler.moves_applied(), 5);
+        assert_eq!(handler.get_move_history(), ["d2d4", "e7e5", "g1f3"]);
+
+        // Same moves from a different base position are not an extension either
+        let from_fen = parser
+            .parse_command(
+                "position fen rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 \
+                 moves d2d4 e7e5 g1f3 b8c6",
+            )
+            .unwrap();
+        handler.handle_position_command(&from_fen).unwrap();
+        assert_eq!(handler.moves_applied(), 9);
+    }
 }
@@ -0,0 +1,91 @@
+// Transposition Table Sizing
+//
+// `setoption name Hash` reallocates the C++ transposition table. Allocation is
+// pluggable so tests can simulate running out of memory, and a resize asked for
+// while a search still probes the table waits until that search completes.
+
+use std::sync::Arc;
+
+use tracing::{info, warn};
+
+use crate::error::{UCIError, UCIResult};
+use crate::ffi::ffi;
+use crate::uci::state::UCIState;
+
+/// Reallocates the transposition table
+///
+/// Implementations run on the event loop and must not block for long.
+pub trait HashAllocator: Send + Sync {
+    /// Reallocate the table to `size_mb` megabytes; false if the memory could
+    /// not be allocated, leaving the old table in place
+    fn resize(&self, size_mb: u32) -> bool;
+}
+
+/// Default allocator that resizes the C++ engine's table through FFI
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EngineHashAllocator;
+
+impl HashAllocator for EngineHashAllocator {
+    fn resize(&self, size_mb: u32) -> bool {
+        ffi::engine_set_hash_size(size_mb)
+    }
+}
+
+/// Transposition table size, with at most one resize waiting for a search
+pub struct HashTable {
+    /// Performs the actual reallocation
+    allocator: Arc<dyn HashAllocator>,
+    /// `(allocated, requested)` megabytes of a resize held back by a search
+    deferred: parking_lot::Mutex<Option<(u32, u32)>>,
+}
+
+impl HashTable {
+    /// Table sized through `allocator`
+    pub fn new(allocator: Arc<dyn HashAllocator>) -> Self {
+        Self {
+            allocator,
+            deferred: parking_lot::Mutex::new(None),
+        }
+    }
+
+    /// Reallocate the table to `size_mb` megabytes now
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` - The table has the new size
+    /// - `Err(UCIError::Configuration)` - Allocation failed; the old table is kept
+    pub fn resize(&self, size_mb: u32) -> UCIResult<()> {
+        if !self.allocator.resize(size_mb) {
+            return Err(UCIError::Configuration {
+                message: format!("Failed to allocate a {} MB hash table", size_mb),
+            });
+        }
+        info!(size_mb, "Hash table resized");
+        Ok(())
+    }
+
+    /// Hold a resize from `allocated_mb` to `size_mb` until `apply_deferred`
+    ///
+    /// A later request replaces an earlier one; the table still has the size
+    /// it had before the first.
+    pub fn defer_resize(&self, allocated_mb: u32, size_mb: u32) {
+        let mut deferred = self.deferred.lock();
+        let allocated_mb = deferred.map_or(allocated_mb, |(allocated, _)| allocated);
+        *deferred = Some((allocated_mb, size_mb));
+        info!(size_mb, "Hash resize deferred until the search completes");
+    }
+
+    /// Apply a deferred resize once the search has completed
+    ///
+    /// Nobody is left to report an allocation failure to, so the configured
+    /// size falls back to the table that was kept.
+    pub fn apply_deferred(&self, state: &UCIState) {
+        let Some((allocated_mb, size_mb)) = self.deferred.lock().take() else {
+            return;
+        };
+        if let Err(e) = self.resize(size_mb) {
+            warn!(error = %e, allocated_mb, "Deferred hash resize failed");
+            let _ = state.update_config(|cfg| cfg.hash_size_mb = allocated_mb);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// Mate Search
+//
+// `go mate` runs a blocking search in the C++ engine. The engine calls it on a
+// blocking thread and abandons it past the hard time limit; the search itself is
+// pluggable so tests can stand in a search that never returns in time.
+
+use crate::bridge::{Board, SearchEngine};
+use crate::error::UCIResult;
+
+/// Searches a position for a forced mate
+///
+/// Implementations are called on a blocking thread and may run for a long time;
+/// they must never be invoked from the async event loop directly.
+pub trait MateSearcher: Send + Sync {
+    /// Look for a mate in at most `max_moves` moves, playing up to `max_nodes`
+    /// moves per depth
+    ///
+    /// Returns the number of moves to mate and the first move, or `None` if no
+    /// mate was proven.
+    fn search_mate(
+        &self,
+        board: &Board,
+        max_moves: u32,
+        max_nodes: u64,
+    ) -> UCIResult<Option<(u32, String)>>;
+}
+
+/// Default searcher backed by the C++ engine
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EngineMateSearcher;
+
+impl MateSearcher for EngineMateSearcher {
+    fn search_mate(
+        &self,
+        board: &Board,
+        max_moves: u32,
+        max_nodes: u64,
+    ) -> UCIResult<Option<(u32, String)>> {
+        SearchEngine::search_mate(board, max_moves, max_nodes)
+    }
+}
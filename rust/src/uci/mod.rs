@@ -3,25 +3,57 @@
 // This module provides a complete UCI protocol implementation with zero-copy parsing,
 // comprehensive input validation, and never-panic operation for production use.
 
+/// Resign and draw signals for self-play games
+pub mod adjudication;
+/// Fixed position suite for the `bench` command
+pub mod bench;
 pub mod commands;
 pub mod engine;
 pub mod event_loop;
 pub mod handlers;
+/// Transposition table resizing for the `Hash` option
+pub mod hash_table;
+/// Blocking `go mate` search run off the event loop
+pub mod mate_search;
+/// Pluggable root move ordering for search experiments
+pub mod move_ordering;
+/// Declarations of the options `uci` announces and `setoption` accepts
+pub mod options;
 pub mod parser;
 pub mod response;
 pub mod sanitizer;
+/// Worker set sizing for the `Threads` option
+pub mod search_threads;
 pub mod state;
+/// JSON search telemetry for structured front ends
+pub mod telemetry;
+/// Hash table warmup run after allocation
+pub mod warmup;
 
+pub use adjudication::{Adjudication, ScoreHistory};
 pub use commands::{ChessMove, Position, TimeControl, UCICommand};
-pub use engine::{EngineCommand, EngineIdentification, SearchResult, UCIEngine};
+pub use engine::{EngineCommand, EngineIdentification, OutputReceiver, SearchResult, UCIEngine};
 pub use event_loop::{run_uci_event_loop, EventLoopConfig, EventLoopStats, UCIEventLoop};
 pub use handlers::{BasicCommandHandler, NewGameHandler, PositionCommandHandler};
+pub use hash_table::{EngineHashAllocator, HashAllocator, HashTable};
+pub use mate_search::{EngineMateSearcher, MateSearcher};
+pub use move_ordering::{MorphyAggressiveOrdering, RootMoveOrdering};
+pub use options::{
+    find_debug_option, find_option, OptionKind, OptionSpec, OptionValue, DEBUG_OPTION_SPECS,
+    OPTION_SPECS,
+};
 pub use parser::{BatchParser, ParserStats, ZeroCopyParser};
-pub use response::{BestMoveBuilder, InfoBuilder, ResponseFormatter, UCIResponse};
-pub use sanitizer::{InputLimits, InputSanitizer};
+pub use response::{
+    BestMoveBuilder, InfoBuilder, ResponseFormatter, ScoreBound, ScoreKind, UCIResponse,
+};
+pub use sanitizer::{InputLimits, InputSanitizer, MAX_MOVES_PER_COMMAND_CEILING};
+pub use search_threads::{worker_count, EngineSearchThreads, SearchThreads, MAX_SEARCH_THREADS};
 pub use state::{
-    EngineConfig, EngineState, EngineStatistics, SearchContext, StateChangeEvent, UCIState,
+    EngineConfig, EngineState, EngineStatistics, PlayingStyle, SearchContext, SearchCounters,
+    SearchTotals, StateChangeEvent, StopReason, UCIState,
 };
+pub use telemetry::{TelemetryFrame, TelemetryLine, TELEMETRY_PREFIX};
+pub use warmup::{EngineHashWarmup, HashWarmup};
 
 // Re-export commonly used error types
 pub use crate::error::{UCIError, UCIResult};
@@ -0,0 +1,68 @@
+// Root Move Ordering
+//
+// The order root moves are searched in decides how early the search finds a
+// good move to cut against. The ordering is pluggable so style experiments can
+// bias it from Rust; the result reaches the C++ search as an ordering hint.
+
+use crate::bridge::Board;
+
+/// Reorders the root moves of a search before it starts
+///
+/// Called on the event loop with the position about to be searched; `moves`
+/// holds its legal root moves (restricted by `searchmoves`, if given) in UCI
+/// notation. Implementations only permute the list, never add or drop moves.
+pub trait RootMoveOrdering: Send + Sync {
+    /// Put the moves to search first at the front of `moves`
+    fn order(&self, board: &Board, moves: &mut Vec<String>);
+}
+
+/// Morphy-style ordering: captures and checks before quiet moves
+///
+/// The sort is stable, so moves within each group keep their incoming order.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MorphyAggressiveOrdering;
+
+impl MorphyAggressiveOrdering {
+    /// Whether `root_move` captures a piece or gives check
+    fn is_forcing(board: &Board, root_move: &str) -> bool {
+        // SAN marks captures (en passant included) with `x` and checks with `+`/`#`
+        board
+            .uci_to_san(root_move)
+            .is_ok_and(|san| san.contains(['x', '+', '#']))
+    }
+}
+
+impl RootMoveOrdering for MorphyAggressiveOrdering {
+    fn order(&self, board: &Board, moves: &mut Vec<String>) {
+        moves.sort_by_cached_key(|root_move| !Self::is_forcing(board, root_move));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_precedes_quiet_move() {
+        let mut board = Board::new().unwrap();
+        board
+            .set_from_fen("4k3/8/8/3q4/4P3/8/P7/4K3 w - - 0 1")
+            .unwrap();
+
+        let mut moves = vec!["a2a3".to_string(), "e4d5".to_string()];
+        MorphyAggressiveOrdering.order(&board, &mut moves);
+        assert_eq!(moves, ["e4d5", "a2a3"]);
+    }
+
+    #[test]
+    fn test_checks_move_ahead_and_quiet_order_is_kept() {
+        let mut board = Board::new().unwrap();
+        board
+            .set_from_fen("4k3/8/8/8/8/8/1P5P/R3K3 w - - 0 1")
+            .unwrap();
+
+        let mut moves = vec!["h2h3".to_string(), "a1a8".to_string(), "b2b3".to_string()];
+        MorphyAggressiveOrdering.order(&board, &mut moves);
+        assert_eq!(moves, ["a1a8", "h2h3", "b2b3"]);
+    }
+}
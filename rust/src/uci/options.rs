@@ -0,0 +1,387 @@
+// UCI Option Registry
+//
+// Every option the engine advertises is declared once here. `uci` announces the
+// registry and `setoption` checks values against it, so a GUI that sends a value
+// outside what was advertised gets an error instead of a silently clamped value.
+// Debug-only options live in a separate registry that `uci` never announces.
+
+use crate::error::{UCIError, UCIResult};
+use crate::time::TimePolicyKind;
+use crate::uci::response::{OptionType, UCIResponse};
+use crate::uci::sanitizer::MAX_MOVES_PER_COMMAND_CEILING;
+use crate::uci::state::{EngineConfig, PlayingStyle};
+
+/// Type and accepted values of a declared option
+#[derive(Debug, Clone, Copy)]
+pub enum OptionKind {
+    /// `true` or `false`
+    Check,
+    /// Integer within `min..=max`
+    Spin {
+        /// Smallest accepted value
+        min: i32,
+        /// Largest accepted value
+        max: i32,
+    },
+    /// One of a fixed list of names
+    Combo {
+        /// Accepted names, in the order they are announced
+        values: fn() -> Vec<String>,
+    },
+    /// Action without a value
+    Button,
+    /// Free-form text
+    String,
+}
+
+/// Declaration of one UCI option
+#[derive(Debug, Clone, Copy)]
+pub struct OptionSpec {
+    /// Name as announced to the GUI
+    pub name: &'static str,
+    /// Type and accepted values
+    pub kind: OptionKind,
+    /// Value of a freshly constructed engine; empty for buttons and unset strings
+    pub default: &'static str,
+    /// Read the option's current value from the engine configuration
+    pub current: fn(&EngineConfig) -> String,
+}
+
+const fn spec(
+    name: &'static str,
+    kind: OptionKind,
+    default: &'static str,
+    current: fn(&EngineConfig) -> String,
+) -> OptionSpec {
+    OptionSpec {
+        name,
+        kind,
+        default,
+        current,
+    }
+}
+
+/// A `setoption` value parsed according to its option's declaration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionValue {
+    /// Value of a check option
+    Check(bool),
+    /// Value of a spin option, within its declared range
+    Spin(i32),
+    /// Value of a combo option, spelled as declared
+    Combo(String),
+    /// Press of a button option
+    Button,
+    /// Value of a string option; empty when the GUI sent none
+    String(String),
+}
+
+impl OptionValue {
+    /// The value of a check option; `false` for any other kind
+    pub fn as_bool(&self) -> bool {
+        matches!(self, Self::Check(true))
+    }
+
+    /// The value of a spin option in the type of the config field it sets
+    ///
+    /// Declared ranges fit their fields, so the conversion only falls back to
+    /// the default for values that are not spins.
+    pub fn as_spin<T: TryFrom<i32> + Default>(&self) -> T {
+        match self {
+            Self::Spin(number) => T::try_from(*number).unwrap_or_default(),
+            _ => T::default(),
+        }
+    }
+
+    /// The text of a combo or string option; empty for any other kind
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Combo(text) | Self::String(text) => text,
+            _ => "",
+        }
+    }
+}
+
+const fn spin(min: i32, max: i32) -> OptionKind {
+    OptionKind::Spin { min, max }
+}
+
+fn time_policy_values() -> Vec<String> {
+    TimePolicyKind::ALL
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn style_values() -> Vec<String> {
+    PlayingStyle::ALL.iter().map(ToString::to_string).collect()
+}
+
+/// Every option the engine supports, in the order `uci` announces them
+pub const OPTION_SPECS: &[OptionSpec] = &[
+    spec("Hash", spin(1, 2048), "16", |cfg| {
+        cfg.hash_size_mb.to_string()
+    }),
+    spec("Threads", spin(1, 64), "1", |cfg| {
+        cfg.thread_count.to_string()
+    }),
+    spec("Clear Hash", OptionKind::Button, "", |_| String::new()),
+    spec("MultiPV", spin(1, 256), "1", |cfg| cfg.multi_pv.to_string()),
+    spec("Ponder", OptionKind::Check, "false", |cfg| {
+        cfg.ponder_enabled.to_string()
+    }),
+    spec("UCI_AnalyseMode", OptionKind::Check, "false", |cfg| {
+        cfg.analysis_mode.to_string()
+    }),
+    spec("UCI_Chess960", OptionKind::Check, "false", |cfg| {
+        cfg.chess960.to_string()
+    }),
+    spec("UCI_LimitStrength", OptionKind::Check, "false", |cfg| {
+        cfg.limit_strength.to_string()
+    }),
+    spec("UCI_Elo", spin(1320, 3190), "1320", |cfg| {
+        cfg.uci_elo.to_string()
+    }),
+    spec(
+        "TimePolicy",
+        OptionKind::Combo {
+            values: time_policy_values,
+        },
+        "Auto",
+        |cfg| cfg.time_policy.to_string(),
+    ),
+    spec("MoveOverhead", spin(0, 5000), "30", |cfg| {
+        cfg.move_overhead_ms.to_string()
+    }),
+    spec(
+        "Style",
+        OptionKind::Combo {
+            values: style_values,
+        },
+        "Balanced",
+        |cfg| cfg.style.to_string(),
+    ),
+    spec("MorphyStyle", OptionKind::Check, "false", |cfg| {
+        cfg.morphy_style.to_string()
+    }),
+    spec("SacrificeThreshold", spin(0, 1000), "100", |cfg| {
+        cfg.sacrifice_threshold.to_string()
+    }),
+    spec("TacticalDepth", spin(0, 8), "2", |cfg| {
+        cfg.tactical_depth.to_string()
+    }),
+    spec("Contempt", spin(-200, 200), "0", |cfg| {
+        cfg.contempt_factor.to_string()
+    }),
+    spec("ResignThreshold", spin(0, 10000), "0", |cfg| {
+        cfg.resign_threshold.to_string()
+    }),
+    spec("DrawThreshold", spin(0, 1000), "10", |cfg| {
+        cfg.draw_threshold.to_string()
+    }),
+    spec("DrawMoveCount", spin(0, 200), "0", |cfg| {
+        cfg.draw_move_count.to_string()
+    }),
+    spec("WarmupHash", OptionKind::Check, "false", |cfg| {
+        cfg.warmup_hash.to_string()
+    }),
+    spec("EchoCommands", OptionKind::Check, "false", |cfg| {
+        cfg.echo_commands.to_string()
+    }),
+    spec("MoveSummary", OptionKind::Check, "false", |cfg| {
+        cfg.move_summary.to_string()
+    }),
+    spec(
+        "MaxMoveList",
+        spin(1, MAX_MOVES_PER_COMMAND_CEILING as i32),
+        "512",
+        |cfg| cfg.max_moves_per_command.to_string(),
+    ),
+];
+
+/// Options `setoption` accepts only in debug mode; `uci` never announces them
+pub const DEBUG_OPTION_SPECS: &[OptionSpec] = &[spec("SearchNps", spin(0, i32::MAX), "0", |cfg| {
+    cfg.search_nps.to_string()
+})];
+
+/// Look up an option by name, ignoring case as the UCI protocol requires
+pub fn find_option(name: &str) -> Option<&'static OptionSpec> {
+    OPTION_SPECS
+        .iter()
+        .find(|spec| spec.name.eq_ignore_ascii_case(name))
+}
+
+/// Look up a debug-only option by name, ignoring case
+pub fn find_debug_option(name: &str) -> Option<&'static OptionSpec> {
+    DEBUG_OPTION_SPECS
+        .iter()
+        .find(|spec| spec.name.eq_ignore_ascii_case(name))
+}
+
+impl OptionSpec {
+    /// Check a `setoption` value against the declaration and parse it
+    pub fn validate(&self, value: &str) -> UCIResult<OptionValue> {
+        let invalid = |expected: String| UCIError::Configuration {
+            message: format!(
+                "Invalid value for {}: {} (expected {})",
+                self.name, value, expected
+            ),
+        };
+
+        match self.kind {
+            OptionKind::Check => {
+                if value.eq_ignore_ascii_case("true") {
+                    Ok(OptionValue::Check(true))
+                } else if value.eq_ignore_ascii_case("false") {
+                    Ok(OptionValue::Check(false))
+                } else {
+                    Err(invalid("true or false".to_string()))
+                }
+            }
+            OptionKind::Spin { min, max } => match value.parse::<i32>() {
+                Ok(number) if (min..=max).contains(&number) => Ok(OptionValue::Spin(number)),
+                _ => Err(invalid(format!("an integer from {} to {}", min, max))),
+            },
+            OptionKind::Combo { values } => {
+                let values = values();
+                match values.iter().find(|var| var.eq_ignore_ascii_case(value)) {
+                    Some(var) => Ok(OptionValue::Combo(var.clone())),
+                    None => Err(invalid(format!("one of {}", values.join(", ")))),
+                }
+            }
+            OptionKind::Button => Ok(OptionValue::Button),
+            OptionKind::String => Ok(OptionValue::String(value.to_string())),
+        }
+    }
+
+    /// Build the `option` line announcing this option with `current` as its default
+    pub fn to_response(&self, current: &str) -> UCIResponse {
+        let (option_type, min, max) = match self.kind {
+            OptionKind::Check => (OptionType::Check, None, None),
+            OptionKind::Spin { min, max } => (OptionType::Spin { min, max }, Some(min), Some(max)),
+            OptionKind::Combo { values } => (OptionType::Combo { values: values() }, None, None),
+            OptionKind::Button => (OptionType::Button, None, None),
+            OptionKind::String => (OptionType::String, None, None),
+        };
+        let default = match self.kind {
+            OptionKind::Button => None,
+            OptionKind::String if current.is_empty() => Some("<empty>".to_string()),
+            _ => Some(current.to_string()),
+        };
+
+        UCIResponse::Option {
+            name: self.name.to_string(),
+            option_type,
+            default,
+            min,
+            max,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_option_ignores_case() {
+        assert_eq!(find_option("hash").unwrap().name, "Hash");
+        assert_eq!(find_option("CLEAR HASH").unwrap().name, "Clear Hash");
+        assert!(find_option("Book").is_none());
+
+        // Debug-only options are kept out of the announced registry
+        assert!(find_option("SearchNps").is_none());
+        assert_eq!(find_debug_option("searchnps").unwrap().name, "SearchNps");
+    }
+
+    #[test]
+    fn test_spin_rejects_out_of_range() {
+        let hash = find_option("Hash").unwrap();
+        assert!(hash.validate("1").is_ok());
+        assert!(hash.validate("2048").is_ok());
+
+        let error = hash.validate("4096").unwrap_err();
+        assert!(matches!(error, UCIError::Configuration { .. }));
+        assert!(error.to_string().contains("from 1 to 2048"));
+        assert!(hash.validate("0").is_err());
+        assert!(hash.validate("lots").is_err());
+
+        let contempt = find_option("Contempt").unwrap();
+        assert!(contempt.validate("-200").is_ok());
+        assert!(contempt.validate("-201").is_err());
+    }
+
+    #[test]
+    fn test_check_rejects_non_boolean() {
+        let ponder = find_option("Ponder").unwrap();
+        assert!(ponder.validate("true").is_ok());
+        assert!(ponder.validate("False").is_ok());
+        assert!(matches!(
+            ponder.validate("yes"),
+            Err(UCIError::Configuration { .. })
+        ));
+        assert!(ponder.validate("1").is_err());
+    }
+
+    #[test]
+    fn test_combo_accepts_declared_values() {
+        let style = find_option("Style").unwrap();
+        assert!(style.validate("morphy").is_ok());
+        assert!(style.validate("Aggressive").is_err());
+    }
+
+    #[test]
+    fn test_validate_returns_parsed_value() {
+        let hash = find_option("Hash").unwrap();
+        assert_eq!(hash.validate("64").unwrap(), OptionValue::Spin(64));
+        assert_eq!(hash.validate("64").unwrap().as_spin::<u32>(), 64);
+
+        let ponder = find_option("Ponder").unwrap();
+        assert!(ponder.validate("TRUE").unwrap().as_bool());
+        assert!(!ponder.validate("false").unwrap().as_bool());
+
+        // Combo values come back spelled as declared
+        let style = find_option("Style").unwrap();
+        assert_eq!(style.validate("morphy").unwrap().as_str(), "Morphy");
+    }
+
+    #[test]
+    fn test_current_reads_config() {
+        let config = EngineConfig {
+            hash_size_mb: 64,
+            ..EngineConfig::default()
+        };
+        for spec in OPTION_SPECS
+            .iter()
+            .filter(|spec| !matches!(spec.kind, OptionKind::Button))
+        {
+            let expected = if spec.name == "Hash" {
+                "64"
+            } else {
+                spec.default
+            };
+            assert_eq!((spec.current)(&config), expected, "{}", spec.name);
+        }
+    }
+
+    #[test]
+    fn test_to_response_formats_declaration() {
+        let hash = find_option("Hash").unwrap();
+        assert_eq!(
+            hash.to_response("64").to_uci_string().unwrap(),
+            "option name Hash type spin default 64 min 1 max 2048"
+        );
+
+        let notes = spec("Notes", OptionKind::String, "", |_| String::new());
+        assert_eq!(
+            notes.to_response("").to_uci_string().unwrap(),
+            "option name Notes type string default <empty>"
+        );
+
+        let clear_hash = find_option("Clear Hash").unwrap();
+        assert_eq!(
+            clear_hash.to_response("").to_uci_string().unwrap(),
+            "option name Clear Hash type button"
+        );
+    }
+}
@@ -3,14 +3,20 @@
 // This parser processes UCI commands using zero-allocation string slicing wherever possible,
 // with comprehensive input validation and fuzzing resistance for production use.
 
-use crate::error::{UCIError, UCIResult};
+use crate::error::{ErrorToken, UCIError, UCIResult};
+use crate::uci::bench::{BENCH_POSITIONS, DEFAULT_BENCH_DEPTH};
 use crate::uci::commands::{ChessMove, Position, RawCommand, SafeParse, TimeControl, UCICommand};
 use crate::uci::sanitizer::InputSanitizer;
+use tracing::warn;
+
+/// Deepest `go perft` accepted; startpos perft 7 is already ~3.2 billion leaves
+const MAX_PERFT_DEPTH: u32 = 7;
 
 /// High-performance zero-copy UCI command parser
 pub struct ZeroCopyParser {
     sanitizer: InputSanitizer,
     stats: ParserStats,
+    strict_allocations: bool,
 }
 
 /// Parser performance statistics
@@ -22,6 +28,8 @@ pub struct ParserStats {
     pub validation_errors: u64,
     pub zero_copy_hits: u64,
     pub allocation_fallbacks: u64,
+    /// Commands that allocated more than expected while strict allocation checks were on
+    pub allocation_budget_violations: u64,
 }
 
 impl Default for ZeroCopyParser {
@@ -36,6 +44,7 @@ impl ZeroCopyParser {
         Self {
             sanitizer: InputSanitizer::default(),
             stats: ParserStats::default(),
+            strict_allocations: false,
         }
     }
 
@@ -44,9 +53,25 @@ impl ZeroCopyParser {
         Self {
             sanitizer,
             stats: ParserStats::default(),
+            strict_allocations: false,
         }
     }
 
+    /// Replace the sanitizer's input limits, e.g. after `setoption` changed them
+    pub fn set_input_limits(&mut self, limits: crate::uci::sanitizer::InputLimits) {
+        self.sanitizer.set_limits(limits);
+    }
+
+    /// Enable strict allocation checks (intended for tests)
+    ///
+    /// Every command is expected to parse without allocating, except `position fen`
+    /// which may fall back once to rebuild the FEN. Exceeding that emits a warning and
+    /// bumps `allocation_budget_violations`, so zero-copy regressions show up loudly.
+    pub fn with_strict_allocations(mut self, strict: bool) -> Self {
+        self.strict_allocations = strict;
+        self
+    }
+
     /// Parse a UCI command line into structured command
     pub fn parse_command<'a>(&mut self, line: &'a str) -> UCIResult<UCICommand<'a>> {
         self.stats.commands_parsed += 1;
@@ -71,8 +96,10 @@ impl ZeroCopyParser {
             e
         })?;
 
+        let fallbacks_before = self.stats.allocation_fallbacks;
+
         // Dispatch to specific command parser
-        match raw.command.to_lowercase().as_str() {
+        let result = match raw.command.to_lowercase().as_str() {
             "uci" => {
                 self.stats.zero_copy_hits += 1;
                 self.parse_uci(&raw)
@@ -117,12 +144,64 @@ impl ZeroCopyParser {
                 self.stats.zero_copy_hits += 1;
                 self.parse_quit(&raw)
             }
+            "legalmoves" => {
+                self.stats.zero_copy_hits += 1;
+                self.parse_legalmoves(&raw)
+            }
+            "d" => {
+                self.stats.zero_copy_hits += 1;
+                self.parse_display(&raw)
+            }
+            "eval" => {
+                self.stats.zero_copy_hits += 1;
+                self.parse_eval(&raw)
+            }
+            "stats" => {
+                self.stats.zero_copy_hits += 1;
+                self.parse_stats(&raw)
+            }
+            "bench" => {
+                self.stats.zero_copy_hits += 1;
+                self.parse_bench(&raw)
+            }
             _ => {
                 self.stats.parse_errors += 1;
                 Err(UCIError::Protocol {
                     message: format!("Unknown command: '{}'", raw.command),
+                    token: None,
                 })
             }
+        };
+
+        if self.strict_allocations {
+            self.check_allocation_budget(line, &result, fallbacks_before);
+        }
+
+        result
+    }
+
+    /// Warn when a command allocated more than its expected number of fallbacks
+    fn check_allocation_budget(
+        &mut self,
+        line: &str,
+        result: &UCIResult<UCICommand<'_>>,
+        fallbacks_before: u64,
+    ) {
+        let expected = match result {
+            Ok(UCICommand::Position {
+                position: Position::Fen(_),
+                ..
+            }) => 1,
+            _ => 0,
+        };
+        let actual = self.stats.allocation_fallbacks - fallbacks_before;
+
+        if actual > expected {
+            self.stats.allocation_budget_violations += 1;
+            warn!(
+                command = line,
+                expected, actual, "Parser allocation budget exceeded - zero-copy regression?"
+            );
         }
     }
 
@@ -142,6 +221,7 @@ impl ZeroCopyParser {
         if !raw.args.is_empty() {
             return Err(UCIError::Protocol {
                 message: "uci command takes no arguments".to_string(),
+                token: None,
             });
         }
         Ok(UCICommand::Uci)
@@ -151,6 +231,7 @@ impl ZeroCopyParser {
         if raw.args.len() != 1 {
             return Err(UCIError::Protocol {
                 message: "debug command requires exactly one argument".to_string(),
+                token: None,
             });
         }
 
@@ -162,6 +243,7 @@ impl ZeroCopyParser {
         if !raw.args.is_empty() {
             return Err(UCIError::Protocol {
                 message: "isready command takes no arguments".to_string(),
+                token: None,
             });
         }
         Ok(UCICommand::IsReady)
@@ -172,6 +254,7 @@ impl ZeroCopyParser {
 
         let name = pairs.get("name").ok_or_else(|| UCIError::Protocol {
             message: "setoption command missing 'name' parameter".to_string(),
+            token: None,
         })?;
 
         // Validate option name and value
@@ -185,6 +268,7 @@ impl ZeroCopyParser {
         if raw.args.is_empty() {
             return Err(UCIError::Protocol {
                 message: "register command requires arguments".to_string(),
+                token: None,
             });
         }
 
@@ -203,6 +287,7 @@ impl ZeroCopyParser {
                     if i + 1 >= raw.args.len() {
                         return Err(UCIError::Protocol {
                             message: "register name parameter missing value".to_string(),
+                            token: None,
                         });
                     }
                     name = Some(raw.args[i + 1]);
@@ -212,6 +297,7 @@ impl ZeroCopyParser {
                     if i + 1 >= raw.args.len() {
                         return Err(UCIError::Protocol {
                             message: "register code parameter missing value".to_string(),
+                            token: None,
                         });
                     }
                     code = Some(raw.args[i + 1]);
@@ -220,6 +306,7 @@ impl ZeroCopyParser {
                 _ => {
                     return Err(UCIError::Protocol {
                         message: format!("Invalid register parameter: '{}'", raw.args[i]),
+                        token: None,
                     });
                 }
             }
@@ -232,6 +319,7 @@ impl ZeroCopyParser {
         if !raw.args.is_empty() {
             return Err(UCIError::Protocol {
                 message: "ucinewgame command takes no arguments".to_string(),
+                token: None,
             });
         }
         Ok(UCICommand::UciNewGame)
@@ -241,6 +329,7 @@ impl ZeroCopyParser {
         if raw.args.is_empty() {
             return Err(UCIError::Protocol {
                 message: "position command requires arguments".to_string(),
+                token: None,
             });
         }
 
@@ -250,6 +339,7 @@ impl ZeroCopyParser {
                 if raw.args.len() < 7 {
                     return Err(UCIError::Protocol {
                         message: "position fen command requires FEN string (6 parts)".to_string(),
+                        token: None,
                     });
                 }
 
@@ -260,19 +350,17 @@ impl ZeroCopyParser {
                 // Validate FEN format
                 self.sanitizer.validate_fen(&fen_string)?;
 
-                // For zero-copy operation, we store the original slice
-                // This requires the FEN to be a single token, which it isn't in practice
-                // So we need to allocate here
+                // The FEN spans six tokens, so it cannot be a single zero-copy slice
                 self.stats.allocation_fallbacks += 1;
 
-                // We'll use the first part as a representative slice
-                // In a real implementation, we'd need to store the reconstructed string
-                Position::Fen(raw.args[1])
+                Position::Fen(fen_string)
             }
             _ => {
-                return Err(UCIError::Protocol {
-                    message: format!("Invalid position type: '{}'", raw.args[0]),
-                });
+                return Err(token_error(
+                    raw,
+                    0,
+                    format!("invalid position type '{}'", raw.args[0]),
+                ));
             }
         };
 
@@ -285,7 +373,7 @@ impl ZeroCopyParser {
     fn parse_moves_from_position<'a>(
         &mut self,
         raw: &RawCommand<'a>,
-        position: &Position<'a>,
+        position: &Position,
     ) -> UCIResult<Vec<ChessMove<'a>>> {
         // Find "moves" keyword
         let moves_index = match position {
@@ -319,17 +407,50 @@ impl ZeroCopyParser {
     }
 
     fn parse_go<'a>(&mut self, raw: &RawCommand<'a>) -> UCIResult<UCICommand<'a>> {
+        // `go perft N` is a debugging command and cannot be combined with search limits
+        if raw.args.contains(&"perft") {
+            if raw.args.len() != 2 || raw.args[0] != "perft" {
+                return Err(UCIError::Protocol {
+                    message: "go perft takes exactly one depth and no other parameters".to_string(),
+                    token: None,
+                });
+            }
+
+            let depth: u32 = raw.args[1].parse().map_err(|_| {
+                token_error(raw, 1, format!("invalid perft depth '{}'", raw.args[1]))
+            })?;
+            if depth > MAX_PERFT_DEPTH {
+                return Err(token_error(
+                    raw,
+                    1,
+                    format!(
+                        "perft depth {} exceeds the maximum of {}",
+                        depth, MAX_PERFT_DEPTH
+                    ),
+                ));
+            }
+            return Ok(UCICommand::Perft(depth));
+        }
+
         let mut time_control = TimeControl::default();
 
         let mut i = 0;
         while i < raw.args.len() {
             match raw.args[i] {
                 "searchmoves" => {
-                    // Skip searchmoves for now - not commonly used
+                    // Collect restricted root moves until the next go keyword
                     i += 1;
                     while i < raw.args.len() && !self.is_go_parameter(raw.args[i]) {
+                        time_control.search_moves.push(ChessMove::new(raw.args[i])?);
                         i += 1;
                     }
+
+                    if time_control.search_moves.is_empty() {
+                        return Err(UCIError::Protocol {
+                            message: "go searchmoves requires at least one move".to_string(),
+                            token: None,
+                        });
+                    }
                 }
                 "ponder" => {
                     time_control.ponder = true;
@@ -376,13 +497,21 @@ impl ZeroCopyParser {
                     i += 1;
                 }
                 _ => {
-                    return Err(UCIError::Protocol {
-                        message: format!("Invalid go parameter: '{}'", raw.args[i]),
-                    });
+                    return Err(token_error(
+                        raw,
+                        i,
+                        format!("invalid parameter '{}'", raw.args[i]),
+                    ));
                 }
             }
         }
 
+        // A `go` without limits searches until `stop`; pondering waits for
+        // `ponderhit` or `stop` anyway
+        if !time_control.ponder && !time_control.has_limits() {
+            time_control.infinite = true;
+        }
+
         Ok(UCICommand::Go(time_control))
     }
 
@@ -393,13 +522,21 @@ impl ZeroCopyParser {
         param_name: &str,
     ) -> UCIResult<u64> {
         if *i + 1 >= raw.args.len() {
-            return Err(UCIError::Protocol {
-                message: format!("go {} parameter missing value", param_name),
-            });
+            return Err(token_error(
+                raw,
+                *i + 1,
+                format!("missing value for '{}'", param_name),
+            ));
         }
 
         *i += 1;
-        let value = u64::safe_parse(raw.args[*i], param_name)?;
+        let value = raw.args[*i].parse().map_err(|_| {
+            token_error(
+                raw,
+                *i,
+                format!("invalid value '{}' for '{}'", raw.args[*i], param_name),
+            )
+        })?;
         *i += 1;
 
         Ok(value)
@@ -427,6 +564,7 @@ impl ZeroCopyParser {
         if !raw.args.is_empty() {
             return Err(UCIError::Protocol {
                 message: "stop command takes no arguments".to_string(),
+                token: None,
             });
         }
         Ok(UCICommand::Stop)
@@ -436,6 +574,7 @@ impl ZeroCopyParser {
         if !raw.args.is_empty() {
             return Err(UCIError::Protocol {
                 message: "ponderhit command takes no arguments".to_string(),
+                token: None,
             });
         }
         Ok(UCICommand::PonderHit)
@@ -445,10 +584,98 @@ impl ZeroCopyParser {
         if !raw.args.is_empty() {
             return Err(UCIError::Protocol {
                 message: "quit command takes no arguments".to_string(),
+                token: None,
             });
         }
         Ok(UCICommand::Quit)
     }
+
+    fn parse_legalmoves<'a>(&mut self, raw: &RawCommand<'a>) -> UCIResult<UCICommand<'a>> {
+        match raw.args.as_slice() {
+            [] => Ok(UCICommand::LegalMoves { san: false }),
+            [notation] if notation.eq_ignore_ascii_case("san") => {
+                Ok(UCICommand::LegalMoves { san: true })
+            }
+            _ => Err(UCIError::Protocol {
+                message: "legalmoves takes no arguments or 'san'".to_string(),
+                token: None,
+            }),
+        }
+    }
+
+    fn parse_display<'a>(&mut self, raw: &RawCommand<'a>) -> UCIResult<UCICommand<'a>> {
+        if !raw.args.is_empty() {
+            return Err(UCIError::Protocol {
+                message: "d command takes no arguments".to_string(),
+                token: None,
+            });
+        }
+        Ok(UCICommand::Display)
+    }
+
+    fn parse_eval<'a>(&mut self, raw: &RawCommand<'a>) -> UCIResult<UCICommand<'a>> {
+        if !raw.args.is_empty() {
+            return Err(UCIError::Protocol {
+                message: "eval command takes no arguments".to_string(),
+                token: None,
+            });
+        }
+        Ok(UCICommand::Eval)
+    }
+
+    fn parse_stats<'a>(&mut self, raw: &RawCommand<'a>) -> UCIResult<UCICommand<'a>> {
+        if !raw.args.is_empty() {
+            return Err(UCIError::Protocol {
+                message: "stats command takes no arguments".to_string(),
+                token: None,
+            });
+        }
+        Ok(UCICommand::Stats)
+    }
+
+    fn parse_bench<'a>(&mut self, raw: &RawCommand<'a>) -> UCIResult<UCICommand<'a>> {
+        if raw.args.len() > 2 {
+            return Err(UCIError::Protocol {
+                message: "bench takes at most a depth and a position count".to_string(),
+                token: None,
+            });
+        }
+
+        let depth = match raw.args.first() {
+            Some(depth) => u32::safe_parse(depth, "bench depth")?,
+            None => DEFAULT_BENCH_DEPTH,
+        };
+        let positions = match raw.args.get(1) {
+            Some(count) => u32::safe_parse(count, "bench position count")? as usize,
+            None => BENCH_POSITIONS.len(),
+        };
+        if depth == 0 || positions == 0 {
+            return Err(UCIError::Protocol {
+                message: "bench depth and position count must be positive".to_string(),
+                token: None,
+            });
+        }
+
+        Ok(UCICommand::Bench {
+            depth,
+            positions: positions.min(BENCH_POSITIONS.len()),
+        })
+    }
+}
+
+/// Protocol error pointing at argument `arg` of `raw`
+///
+/// Token indices count the command itself as token 0, so `abc` in
+/// `go depth abc` is token 2. `arg` may be one past the last argument when a
+/// value is missing, in which case the error carries no token text.
+fn token_error(raw: &RawCommand<'_>, arg: usize, message: String) -> UCIError {
+    UCIError::Protocol {
+        message: format!("{}: {}", raw.command, message),
+        token: Some(Box::new(ErrorToken {
+            index: arg + 1,
+            text: raw.args.get(arg).map(|token| token.to_string()),
+        })),
+    }
 }
 
 /// Simplified batch parser for processing multiple commands
@@ -547,6 +774,17 @@ mod tests {
         } else {
             panic!("Expected Position command");
         }
+
+        // Test FEN with moves preserves the full FEN string
+        let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+        let input = format!("position fen {} moves e7e5", fen);
+        let cmd = parser.parse_command(&input).unwrap();
+        if let UCICommand::Position { position, moves } = cmd {
+            assert_eq!(position, Position::Fen(fen.to_string()));
+            assert_eq!(moves.len(), 1);
+        } else {
+            panic!("Expected Position command");
+        }
     }
 
     #[test]
@@ -591,6 +829,195 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bare_go_is_infinite() {
+        let mut parser = ZeroCopyParser::new();
+
+        let cmd = parser.parse_command("go").unwrap();
+        assert!(matches!(cmd, UCICommand::Go(tc) if tc.infinite && !tc.has_limits()));
+
+        let cmd = parser.parse_command("go searchmoves e2e4").unwrap();
+        assert!(matches!(cmd, UCICommand::Go(tc) if tc.infinite));
+
+        let cmd = parser.parse_command("go depth 5").unwrap();
+        assert!(matches!(cmd, UCICommand::Go(tc) if !tc.infinite && tc.depth == Some(5)));
+
+        let cmd = parser.parse_command("go ponder").unwrap();
+        assert!(matches!(cmd, UCICommand::Go(tc) if !tc.infinite && tc.ponder));
+    }
+
+    #[test]
+    fn test_go_searchmoves() {
+        let mut parser = ZeroCopyParser::new();
+
+        let cmd = parser
+            .parse_command("go searchmoves e2e4 d2d4 depth 10")
+            .unwrap();
+        if let UCICommand::Go(tc) = cmd {
+            assert_eq!(tc.search_moves.len(), 2);
+            assert_eq!(tc.search_moves[0].to_string(), "e2e4");
+            assert_eq!(tc.search_moves[1].to_string(), "d2d4");
+            assert_eq!(tc.depth, Some(10));
+        } else {
+            panic!("Expected Go command");
+        }
+
+        // searchmoves at the end of the command
+        let cmd = parser
+            .parse_command("go movetime 500 searchmoves g1f3")
+            .unwrap();
+        if let UCICommand::Go(tc) = cmd {
+            assert_eq!(tc.search_moves.len(), 1);
+            assert_eq!(tc.move_time_ms, Some(500));
+        } else {
+            panic!("Expected Go command");
+        }
+
+        // searchmoves with no moves is rejected
+        assert!(parser.parse_command("go searchmoves").is_err());
+        assert!(parser.parse_command("go searchmoves depth 10").is_err());
+
+        // Invalid moves in the list are rejected
+        assert!(parser.parse_command("go searchmoves e2e4 z9z9").is_err());
+    }
+
+    #[test]
+    fn test_legalmoves_command() {
+        let mut parser = ZeroCopyParser::new();
+
+        let cmd = parser.parse_command("legalmoves").unwrap();
+        assert!(matches!(cmd, UCICommand::LegalMoves { san: false }));
+
+        let cmd = parser.parse_command("legalmoves san").unwrap();
+        assert!(matches!(cmd, UCICommand::LegalMoves { san: true }));
+
+        assert!(parser.parse_command("legalmoves lan").is_err());
+        assert!(parser.parse_command("legalmoves san extra").is_err());
+    }
+
+    #[test]
+    fn test_display_command() {
+        let mut parser = ZeroCopyParser::new();
+
+        let cmd = parser.parse_command("d").unwrap();
+        assert!(matches!(cmd, UCICommand::Display));
+        assert!(parser.parse_command("d board").is_err());
+    }
+
+    #[test]
+    fn test_eval_command() {
+        let mut parser = ZeroCopyParser::new();
+
+        let cmd = parser.parse_command("eval").unwrap();
+        assert!(matches!(cmd, UCICommand::Eval));
+        assert!(parser.parse_command("eval now").is_err());
+    }
+
+    #[test]
+    fn test_stats_command() {
+        let mut parser = ZeroCopyParser::new();
+
+        let cmd = parser.parse_command("stats").unwrap();
+        assert!(matches!(cmd, UCICommand::Stats));
+        assert!(parser.parse_command("stats game").is_err());
+    }
+
+    #[test]
+    fn test_bench_command() {
+        let mut parser = ZeroCopyParser::new();
+
+        let cmd = parser.parse_command("bench").unwrap();
+        assert!(matches!(
+            cmd,
+            UCICommand::Bench { depth: DEFAULT_BENCH_DEPTH, positions } if positions == BENCH_POSITIONS.len()
+        ));
+        let cmd = parser.parse_command("bench 4 3").unwrap();
+        assert!(matches!(
+            cmd,
+            UCICommand::Bench {
+                depth: 4,
+                positions: 3
+            }
+        ));
+
+        // More positions than the suite holds runs the whole suite
+        let cmd = parser.parse_command("bench 2 1000").unwrap();
+        assert!(
+            matches!(cmd, UCICommand::Bench { depth: 2, positions } if positions == BENCH_POSITIONS.len())
+        );
+
+        assert!(parser.parse_command("bench 0").is_err());
+        assert!(parser.parse_command("bench 4 0").is_err());
+        assert!(parser.parse_command("bench deep").is_err());
+        assert!(parser.parse_command("bench 4 3 1").is_err());
+    }
+
+    #[test]
+    fn test_go_perft() {
+        let mut parser = ZeroCopyParser::new();
+
+        let cmd = parser.parse_command("go perft 3").unwrap();
+        assert!(matches!(cmd, UCICommand::Perft(3)));
+
+        // perft needs a numeric depth
+        assert!(parser.parse_command("go perft").is_err());
+        assert!(parser.parse_command("go perft abc").is_err());
+
+        // perft depth is capped so a typo cannot start an hours-long count
+        assert!(matches!(
+            parser.parse_command("go perft 7").unwrap(),
+            UCICommand::Perft(7)
+        ));
+        assert!(parser.parse_command("go perft 8").is_err());
+
+        // perft cannot be mixed with search parameters
+        assert!(parser.parse_command("go perft 3 depth 5").is_err());
+        assert!(parser.parse_command("go depth 5 perft 3").is_err());
+    }
+
+    #[test]
+    fn test_go_error_names_offending_token() {
+        let mut parser = ZeroCopyParser::new();
+
+        let error = parser.parse_command("go depth abc").unwrap_err();
+        assert_eq!(
+            error,
+            UCIError::Protocol {
+                message: "go: invalid value 'abc' for 'depth'".to_string(),
+                token: Some(Box::new(ErrorToken {
+                    index: 2,
+                    text: Some("abc".to_string()),
+                })),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "UCI protocol error: go: invalid value 'abc' for 'depth' at token 2"
+        );
+
+        let error = parser
+            .parse_command("go wtime 1000 movetime -5")
+            .unwrap_err();
+        assert!(matches!(
+            &error,
+            UCIError::Protocol { token: Some(token), .. }
+                if token.index == 4 && token.text.as_deref() == Some("-5")
+        ));
+        assert!(error.to_string().contains("'-5' for 'movetime'"));
+
+        let error = parser.parse_command("go movetime").unwrap_err();
+        assert!(matches!(
+            &error,
+            UCIError::Protocol { token: Some(token), .. }
+                if token.index == 2 && token.text.is_none()
+        ));
+
+        let error = parser.parse_command("position startfen").unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("position: invalid position type 'startfen' at token 1"));
+    }
+
     #[test]
     fn test_setoption_command() {
         let mut parser = ZeroCopyParser::new();
@@ -622,6 +1049,33 @@ mod tests {
         assert_eq!(stats.zero_copy_hits, 1);
     }
 
+    #[test]
+    fn test_allocation_fallbacks_only_for_fen() {
+        let mut parser = ZeroCopyParser::new().with_strict_allocations(true);
+
+        let commands = [
+            "uci",
+            "isready",
+            "ucinewgame",
+            "setoption name Hash value 64",
+            "position startpos",
+            "position startpos moves e2e4 e7e5 g1f3",
+            "go wtime 60000 btime 60000 winc 1000 binc 1000",
+            "go depth 8",
+            "stop",
+        ];
+        for command in commands {
+            parser.parse_command(command).unwrap();
+        }
+        assert_eq!(parser.stats().allocation_fallbacks, 0);
+
+        parser
+            .parse_command("position fen 4k3/8/8/8/8/8/8/4K3 w - - 0 1 moves e1e2")
+            .unwrap();
+        assert_eq!(parser.stats().allocation_fallbacks, 1);
+        assert_eq!(parser.stats().allocation_budget_violations, 0);
+    }
+
     #[test]
     fn test_input_sanitization() {
         let mut parser = ZeroCopyParser::new();
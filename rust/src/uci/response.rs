@@ -8,7 +8,7 @@ use std::time::Duration;
 
 use tracing::{debug, instrument};
 
-use crate::error::UCIResult;
+use crate::error::{UCIError, UCIResult};
 
 /// UCI response types with structured formatting
 #[derive(Debug, Clone, PartialEq)]
@@ -34,7 +34,9 @@ pub enum UCIResponse {
     /// Search information output
     Info {
         depth: Option<u8>,
-        score: Option<i32>,
+        score: Option<ScoreKind>,
+        /// Bound flag emitted right after the score
+        score_bound: Option<ScoreBound>,
         time: Option<Duration>,
         nodes: Option<u64>,
         nps: Option<u64>,
@@ -65,11 +67,51 @@ pub enum OptionType {
     String,
 }
 
+/// Score reported in search information
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreKind {
+    /// Centipawn evaluation from the engine's point of view
+    Cp(i32),
+    /// Mate in the given number of moves (not plies); negative when the engine is getting mated
+    Mate(i32),
+}
+
+impl ScoreKind {
+    /// Build a mate score from a distance in plies, converting to UCI full-move count
+    pub fn mate_in_plies(plies: i32) -> Self {
+        if plies > 0 {
+            Self::Mate((plies + 1) / 2)
+        } else {
+            Self::Mate(plies / 2)
+        }
+    }
+}
+
+/// Whether a reported score is exact or only a bound (aspiration fail-high/fail-low)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreBound {
+    /// Score is a lower bound (search failed high)
+    Lower,
+    /// Score is an upper bound (search failed low)
+    Upper,
+    /// Score is exact
+    Exact,
+}
+
+impl fmt::Display for ScoreKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoreKind::Cp(cp) => write!(f, "cp {}", cp),
+            ScoreKind::Mate(moves) => write!(f, "mate {}", moves),
+        }
+    }
+}
+
 /// Additional info fields for search information
 #[derive(Debug, Clone, PartialEq)]
 pub enum InfoField {
     SelDepth(u8),
-    MultiPv(u8),
+    MultiPv(u16),
     CurrMove(String),
     CurrMoveNumber(u16),
     HashFull(u16),
@@ -77,6 +119,31 @@ pub enum InfoField {
     Cpuload(u16),
     Refutation(Vec<String>),
     CurrLine(Vec<String>),
+    /// Win/draw/loss probabilities in per-mille from the engine's point of view,
+    /// summing to 1000; reported right after the score
+    Wdl {
+        /// Chance of winning
+        win: u16,
+        /// Chance of a draw
+        draw: u16,
+        /// Chance of losing
+        loss: u16,
+    },
+}
+
+/// Reject a win/draw/loss triple whose per-mille values do not sum to 1000
+fn check_wdl(win: u16, draw: u16, loss: u16) -> UCIResult<()> {
+    let total = u32::from(win) + u32::from(draw) + u32::from(loss);
+    if total != 1000 {
+        return Err(UCIError::Protocol {
+            message: format!(
+                "WDL per-mille must sum to 1000, got {} {} {} ({})",
+                win, draw, loss, total
+            ),
+            token: None,
+        });
+    }
+    Ok(())
 }
 
 impl UCIResponse {
@@ -121,6 +188,27 @@ impl UCIResponse {
         }
     }
 
+    /// Create a combo option response listing its values in order
+    pub fn combo_option<I, S>(
+        name: impl Into<String>,
+        default: impl Into<String>,
+        values: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Option {
+            name: name.into(),
+            option_type: OptionType::Combo {
+                values: values.into_iter().map(Into::into).collect(),
+            },
+            default: Some(default.into()),
+            min: None,
+            max: None,
+        }
+    }
+
     /// Create a ready response
     pub fn ready() -> Self {
         Self::Ready
@@ -198,12 +286,12 @@ impl UCIResponse {
                     }
                     OptionType::Combo { values } => {
                         parts.push("type combo".to_string());
-                        for value in values {
-                            parts.push(format!("var {}", value));
-                        }
                         if let Some(def) = default {
                             parts.push(format!("default {}", def));
                         }
+                        for value in values {
+                            parts.push(format!("var {}", value));
+                        }
                     }
                     OptionType::Button => {
                         parts.push("type button".to_string());
@@ -220,6 +308,7 @@ impl UCIResponse {
             UCIResponse::Info {
                 depth,
                 score,
+                score_bound,
                 time,
                 nodes,
                 nps,
@@ -233,7 +322,21 @@ impl UCIResponse {
                 }
 
                 if let Some(s) = score {
-                    parts.push(format!("score cp {}", s));
+                    parts.push(format!("score {}", s));
+
+                    match score_bound {
+                        Some(ScoreBound::Lower) => parts.push("lowerbound".to_string()),
+                        Some(ScoreBound::Upper) => parts.push("upperbound".to_string()),
+                        Some(ScoreBound::Exact) | None => {}
+                    }
+                }
+
+                // GUIs read the WDL triple as part of the score
+                for field in additional {
+                    if let InfoField::Wdl { win, draw, loss } = field {
+                        check_wdl(*win, *draw, *loss)?;
+                        parts.push(format!("wdl {} {} {}", win, draw, loss));
+                    }
                 }
 
                 if let Some(t) = time {
@@ -248,12 +351,6 @@ impl UCIResponse {
                     parts.push(format!("nps {}", nps_val));
                 }
 
-                if let Some(pv_moves) = pv {
-                    if !pv_moves.is_empty() {
-                        parts.push(format!("pv {}", pv_moves.join(" ")));
-                    }
-                }
-
                 // Add additional info fields
                 for field in additional {
                     match field {
@@ -276,6 +373,14 @@ impl UCIResponse {
                                 parts.push(format!("currline {}", line_moves.join(" ")));
                             }
                         }
+                        InfoField::Wdl { .. } => {}
+                    }
+                }
+
+                // pv consumes the rest of the line, so it must come last
+                if let Some(pv_moves) = pv {
+                    if !pv_moves.is_empty() {
+                        parts.push(format!("pv {}", pv_moves.join(" ")));
                     }
                 }
 
@@ -316,7 +421,8 @@ impl fmt::Display for UCIResponse {
 /// Builder for constructing info responses
 pub struct InfoBuilder {
     depth: Option<u8>,
-    score: Option<i32>,
+    score: Option<ScoreKind>,
+    score_bound: Option<ScoreBound>,
     time: Option<Duration>,
     nodes: Option<u64>,
     nps: Option<u64>,
@@ -329,6 +435,7 @@ impl InfoBuilder {
         Self {
             depth: None,
             score: None,
+            score_bound: None,
             time: None,
             nodes: None,
             nps: None,
@@ -343,10 +450,28 @@ impl InfoBuilder {
     }
 
     pub fn score(mut self, score: i32) -> Self {
+        self.score = Some(ScoreKind::Cp(score));
+        self
+    }
+
+    /// Report an already resolved centipawn or mate score
+    pub fn score_kind(mut self, score: ScoreKind) -> Self {
         self.score = Some(score);
         self
     }
 
+    /// Report a mate score given as distance to mate in plies (negative when being mated)
+    pub fn mate(mut self, plies: i32) -> Self {
+        self.score = Some(ScoreKind::mate_in_plies(plies));
+        self
+    }
+
+    /// Mark the score as a lower/upper bound; `Exact` emits no flag
+    pub fn bound(mut self, bound: ScoreBound) -> Self {
+        self.score_bound = Some(bound);
+        self
+    }
+
     pub fn time(mut self, time: Duration) -> Self {
         self.time = Some(time);
         self
@@ -372,15 +497,43 @@ impl InfoBuilder {
         self
     }
 
+    /// Tag this info line with its principal variation index (1-based)
+    pub fn multipv(mut self, multipv: u16) -> Self {
+        self.additional.push(InfoField::MultiPv(multipv));
+        self
+    }
+
+    /// Root move currently being searched
+    pub fn currmove(mut self, root_move: impl Into<String>) -> Self {
+        self.additional.push(InfoField::CurrMove(root_move.into()));
+        self
+    }
+
+    /// 1-based position of the current root move in search order
+    pub fn currmovenumber(mut self, number: u16) -> Self {
+        self.additional.push(InfoField::CurrMoveNumber(number));
+        self
+    }
+
+    /// Hash occupancy in per-mille, clamped to 1000
     pub fn hashfull(mut self, hashfull: u16) -> Self {
-        self.additional.push(InfoField::HashFull(hashfull));
+        self.additional
+            .push(InfoField::HashFull(hashfull.min(1000)));
         self
     }
 
+    /// Win/draw/loss probabilities in per-mille; they must sum to 1000
+    pub fn wdl(mut self, win: u16, draw: u16, loss: u16) -> UCIResult<Self> {
+        check_wdl(win, draw, loss)?;
+        self.additional.push(InfoField::Wdl { win, draw, loss });
+        Ok(self)
+    }
+
     pub fn build(self) -> UCIResponse {
         UCIResponse::Info {
             depth: self.depth,
             score: self.score,
+            score_bound: self.score_bound,
             time: self.time,
             nodes: self.nodes,
             nps: self.nps,
@@ -485,6 +638,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_combo_option_response() {
+        let response = UCIResponse::combo_option("Style", "Normal", ["Solid", "Normal", "Risky"]);
+        let formatted = response
+            .to_uci_string()
+            .expect("Should format successfully");
+
+        assert_eq!(
+            formatted,
+            "option name Style type combo default Normal var Solid var Normal var Risky"
+        );
+    }
+
     #[test]
     fn test_check_option_response() {
         let response = UCIResponse::check_option("MorphyStyle", false);
@@ -542,6 +708,97 @@ mod tests {
         assert!(formatted.contains("pv e2e4 e7e5"));
     }
 
+    #[test]
+    fn test_info_mate_score() {
+        // Mate in 3 for the side to move is 5 plies away
+        let response = UCIResponse::info().depth(6).mate(5).build();
+        let formatted = response
+            .to_uci_string()
+            .expect("Should format successfully");
+
+        assert_eq!(formatted, "info depth 6 score mate 3");
+        assert!(!formatted.contains("score cp"));
+    }
+
+    #[test]
+    fn test_info_negative_mate_score() {
+        // Getting mated on the opponent's second move is 4 plies away
+        let response = UCIResponse::info().mate(-4).build();
+        let formatted = response
+            .to_uci_string()
+            .expect("Should format successfully");
+
+        assert_eq!(formatted, "info score mate -2");
+    }
+
+    #[test]
+    fn test_info_score_bounds_cp() {
+        let lower = UCIResponse::info()
+            .depth(9)
+            .score(34)
+            .bound(ScoreBound::Lower)
+            .nodes(5000)
+            .build();
+        assert_eq!(
+            lower.to_uci_string().unwrap(),
+            "info depth 9 score cp 34 lowerbound nodes 5000"
+        );
+
+        let upper = UCIResponse::info()
+            .score(-12)
+            .bound(ScoreBound::Upper)
+            .build();
+        assert_eq!(
+            upper.to_uci_string().unwrap(),
+            "info score cp -12 upperbound"
+        );
+
+        let exact = UCIResponse::info()
+            .score(34)
+            .bound(ScoreBound::Exact)
+            .build();
+        assert_eq!(exact.to_uci_string().unwrap(), "info score cp 34");
+    }
+
+    #[test]
+    fn test_info_score_bounds_mate() {
+        let lower = UCIResponse::info().mate(5).bound(ScoreBound::Lower).build();
+        assert_eq!(
+            lower.to_uci_string().unwrap(),
+            "info score mate 3 lowerbound"
+        );
+
+        let upper = UCIResponse::info()
+            .mate(-4)
+            .bound(ScoreBound::Upper)
+            .build();
+        assert_eq!(
+            upper.to_uci_string().unwrap(),
+            "info score mate -2 upperbound"
+        );
+
+        let exact = UCIResponse::info().mate(5).bound(ScoreBound::Exact).build();
+        assert_eq!(exact.to_uci_string().unwrap(), "info score mate 3");
+    }
+
+    #[test]
+    fn test_info_bound_without_score_is_ignored() {
+        let response = UCIResponse::info()
+            .depth(4)
+            .bound(ScoreBound::Lower)
+            .build();
+        assert_eq!(response.to_uci_string().unwrap(), "info depth 4");
+    }
+
+    #[test]
+    fn test_mate_in_plies_conversion() {
+        assert_eq!(ScoreKind::mate_in_plies(1), ScoreKind::Mate(1));
+        assert_eq!(ScoreKind::mate_in_plies(2), ScoreKind::Mate(1));
+        assert_eq!(ScoreKind::mate_in_plies(5), ScoreKind::Mate(3));
+        assert_eq!(ScoreKind::mate_in_plies(-2), ScoreKind::Mate(-1));
+        assert_eq!(ScoreKind::mate_in_plies(-6), ScoreKind::Mate(-3));
+    }
+
     #[test]
     fn test_bestmove_response() {
         let response = UCIResponse::best_move("e2e4").ponder("e7e5").build();
@@ -649,6 +906,75 @@ mod tests {
         assert!(formatted.contains("hashfull 500"));
     }
 
+    #[test]
+    fn test_info_hashfull_clamped() {
+        let formatted = UCIResponse::info()
+            .depth(3)
+            .hashfull(1500)
+            .build()
+            .to_uci_string()
+            .unwrap();
+
+        assert_eq!(formatted, "info depth 3 hashfull 1000");
+    }
+
+    #[test]
+    fn test_info_wdl_follows_score() {
+        let formatted = UCIResponse::info()
+            .depth(12)
+            .score(35)
+            .nodes(4000)
+            .wdl(234, 512, 254)
+            .unwrap()
+            .pv(vec!["e2e4".to_string()])
+            .build()
+            .to_uci_string()
+            .unwrap();
+
+        assert_eq!(
+            formatted,
+            "info depth 12 score cp 35 wdl 234 512 254 nodes 4000 pv e2e4"
+        );
+    }
+
+    #[test]
+    fn test_info_wdl_after_score_bound() {
+        let formatted = UCIResponse::info()
+            .score(-80)
+            .bound(ScoreBound::Upper)
+            .wdl(20, 380, 600)
+            .unwrap()
+            .build()
+            .to_uci_string()
+            .unwrap();
+
+        assert_eq!(formatted, "info score cp -80 upperbound wdl 20 380 600");
+    }
+
+    #[test]
+    fn test_info_wdl_must_sum_to_1000() {
+        assert!(UCIResponse::info().wdl(500, 500, 1).is_err());
+        assert!(UCIResponse::info().wdl(0, 0, 0).is_err());
+        assert!(UCIResponse::info().wdl(1000, 0, 0).is_ok());
+
+        // A hand-built field is checked when formatted
+        let response = UCIResponse::Info {
+            depth: None,
+            score: Some(ScoreKind::Cp(0)),
+            score_bound: None,
+            time: None,
+            nodes: None,
+            nps: None,
+            pv: None,
+            additional: vec![InfoField::Wdl {
+                win: 400,
+                draw: 400,
+                loss: 400,
+            }],
+        };
+        assert!(response.to_uci_string().is_err());
+    }
+
     #[test]
     fn test_empty_info_response() {
         let response = UCIResponse::info().build();
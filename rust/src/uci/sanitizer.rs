@@ -7,7 +7,11 @@
 
 use crate::error::{UCIError, UCIResult};
 
+/// Hard ceiling on moves per `position` command, whatever the configured limit
+pub const MAX_MOVES_PER_COMMAND_CEILING: usize = 4096;
+
 /// Maximum allowed lengths for various input components
+#[derive(Debug, Clone)]
 pub struct InputLimits {
     pub max_command_length: usize,
     pub max_fen_length: usize,
@@ -16,6 +20,8 @@ pub struct InputLimits {
     pub max_option_value_length: usize,
     pub max_moves_per_command: usize,
     pub max_tokens_per_command: usize,
+    /// Accept Shredder-FEN castling files `A-H`/`a-h` (`UCI_Chess960`)
+    pub chess960: bool,
 }
 
 impl Default for InputLimits {
@@ -28,10 +34,35 @@ impl Default for InputLimits {
             max_option_value_length: 256, // Option values can be paths
             max_moves_per_command: 512,   // Support very long games
             max_tokens_per_command: 1024, // Prevent token explosion
+            chess960: false,
         }
     }
 }
 
+impl InputLimits {
+    /// Accept up to `max_moves` moves per command (capped at the absolute ceiling)
+    ///
+    /// Command length and token limits grow with it so a full move list still fits.
+    pub fn with_max_moves_per_command(mut self, max_moves: usize) -> Self {
+        let max_moves = max_moves.min(MAX_MOVES_PER_COMMAND_CEILING);
+        self.max_moves_per_command = max_moves;
+        self.max_move_list_length = self.max_move_list_length.max(max_moves * 6);
+
+        // "position fen <6 fields> moves" plus one token of up to 5 chars per move
+        self.max_tokens_per_command = self.max_tokens_per_command.max(max_moves + 16);
+        self.max_command_length = self
+            .max_command_length
+            .max(self.max_fen_length + 32 + max_moves * 6);
+        self
+    }
+
+    /// Accept Shredder-FEN castling rights such as `HAha` when `enabled`
+    pub fn with_chess960(mut self, enabled: bool) -> Self {
+        self.chess960 = enabled;
+        self
+    }
+}
+
 /// UCI input sanitizer with configurable limits and validation rules
 pub struct InputSanitizer {
     limits: InputLimits,
@@ -49,6 +80,11 @@ impl InputSanitizer {
         Self { limits }
     }
 
+    /// Replace the limits, e.g. after an option changed them
+    pub fn set_limits(&mut self, limits: InputLimits) {
+        self.limits = limits;
+    }
+
     /// Sanitize and validate a complete UCI command line
     pub fn sanitize_command_line(&self, line: &str) -> UCIResult<String> {
         // Remove dangerous characters and normalize whitespace
@@ -66,6 +102,7 @@ impl InputSanitizer {
         if input.contains('\0') {
             return Err(UCIError::Protocol {
                 message: "Input contains null bytes".to_string(),
+                token: None,
             });
         }
 
@@ -81,6 +118,7 @@ impl InputSanitizer {
         if normalized.is_empty() && !input.trim().is_empty() {
             return Err(UCIError::Protocol {
                 message: "Input contains only invalid characters".to_string(),
+                token: None,
             });
         }
 
@@ -97,6 +135,7 @@ impl InputSanitizer {
                     command.len(),
                     self.limits.max_command_length
                 ),
+                token: None,
             });
         }
 
@@ -109,12 +148,14 @@ impl InputSanitizer {
                     tokens.len(),
                     self.limits.max_tokens_per_command
                 ),
+                token: None,
             });
         }
 
         if tokens.is_empty() {
             return Err(UCIError::Protocol {
                 message: "Empty command".to_string(),
+                token: None,
             });
         }
 
@@ -160,6 +201,9 @@ impl InputSanitizer {
         // Validate active color
         self.validate_active_color(parts[1])?;
 
+        // Validate the position itself: kings, pawns and check
+        self.validate_position(parts[0], parts[1] == "w")?;
+
         // Validate castling rights
         self.validate_castling_rights(parts[2])?;
 
@@ -206,6 +250,69 @@ impl InputSanitizer {
         Ok(())
     }
 
+    /// Reject placements no legal game can reach: missing or extra kings, more
+    /// than 8 pawns a side, pawns on the back ranks, or the side that just moved
+    /// still in check
+    fn validate_position(&self, placement: &str, white_to_move: bool) -> UCIResult<()> {
+        let grid = parse_grid(placement)?;
+        let pieces = || {
+            grid.iter().enumerate().flat_map(|(rank, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter_map(move |(file, piece)| piece.map(|piece| (rank, file, piece)))
+            })
+        };
+        let count = |wanted: char| pieces().filter(|&(_, _, piece)| piece == wanted).count();
+
+        for (king, side) in [('K', "White"), ('k', "Black")] {
+            let kings = count(king);
+            if kings != 1 {
+                return Err(UCIError::Position {
+                    message: format!("{} has {} kings (expected exactly 1)", side, kings),
+                });
+            }
+        }
+
+        for (pawn, side) in [('P', "White"), ('p', "Black")] {
+            let pawns = count(pawn);
+            if pawns > 8 {
+                return Err(UCIError::Position {
+                    message: format!("{} has {} pawns (max 8)", side, pawns),
+                });
+            }
+        }
+
+        if let Some((rank, file, pawn)) = pieces()
+            .find(|&(rank, _, piece)| piece.eq_ignore_ascii_case(&'p') && (rank == 0 || rank == 7))
+        {
+            return Err(UCIError::Position {
+                message: format!(
+                    "Pawn '{}' on back rank at {}",
+                    pawn,
+                    square_name(rank, file)
+                ),
+            });
+        }
+
+        // The side that just moved cannot have left its own king attacked
+        let waiting_king = if white_to_move { 'k' } else { 'K' };
+        if let Some((rank, file, _)) = pieces().find(|&(_, _, piece)| piece == waiting_king) {
+            if is_attacked(&grid, rank, file, white_to_move) {
+                let side = if white_to_move { "Black" } else { "White" };
+                return Err(UCIError::Position {
+                    message: format!(
+                        "{} king on {} is in check but it is not {} to move",
+                        side,
+                        square_name(rank, file),
+                        side
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     fn validate_active_color(&self, color: &str) -> UCIResult<()> {
         if !matches!(color, "w" | "b") {
             return Err(UCIError::Position {
@@ -221,7 +328,8 @@ impl InputSanitizer {
         }
 
         for c in castling.chars() {
-            if !matches!(c, 'K' | 'Q' | 'k' | 'q') {
+            let shredder_file = self.limits.chess960 && matches!(c, 'A'..='H' | 'a'..='h');
+            if !shredder_file && !matches!(c, 'K' | 'Q' | 'k' | 'q') {
                 return Err(UCIError::Position {
                     message: format!("Invalid castling right: '{}'", c),
                 });
@@ -320,13 +428,14 @@ impl InputSanitizer {
 
     /// Validate a move list
     pub fn validate_move_list(&self, moves: &[&str]) -> UCIResult<()> {
-        if moves.len() > self.limits.max_moves_per_command {
+        let max_moves = self
+            .limits
+            .max_moves_per_command
+            .min(MAX_MOVES_PER_COMMAND_CEILING);
+        if moves.len() > max_moves {
             return Err(UCIError::Protocol {
-                message: format!(
-                    "Too many moves: {} (max {})",
-                    moves.len(),
-                    self.limits.max_moves_per_command
-                ),
+                message: format!("Too many moves: {} (max {})", moves.len(), max_moves),
+                token: None,
             });
         }
 
@@ -388,15 +497,18 @@ impl InputSanitizer {
             if count > max_char_count && count > 100 {
                 return Err(UCIError::Protocol {
                     message: format!("Excessive repetition of character '{}' ({})", c, count),
+                    token: None,
                 });
             }
         }
 
         // Check for potential zip bomb patterns in command structure
+        // (a lone short token such as `d` is a command, not a pattern)
         let token_count = input.split_whitespace().count();
-        if token_count > 0 && input.len() / token_count < 2 {
+        if token_count > 1 && input.len() / token_count < 2 {
             return Err(UCIError::Protocol {
                 message: "Suspiciously dense token structure".to_string(),
+                token: None,
             });
         }
 
@@ -404,6 +516,99 @@ impl InputSanitizer {
     }
 }
 
+/// Board squares indexed `[rank][file]`, rank 0 being rank 1
+type Grid = [[Option<char>; 8]; 8];
+
+/// Expand a piece placement whose characters were already checked into squares
+fn parse_grid(placement: &str) -> UCIResult<Grid> {
+    let mut grid: Grid = [[None; 8]; 8];
+
+    for (row, rank_str) in placement.split('/').enumerate() {
+        let rank = 7 - row;
+        let mut file = 0;
+        for c in rank_str.chars() {
+            match c.to_digit(10) {
+                Some(empty) => file += empty as usize,
+                None => {
+                    if let Some(square) = grid[rank].get_mut(file) {
+                        *square = Some(c);
+                    }
+                    file += 1;
+                }
+            }
+        }
+
+        if file != 8 {
+            return Err(UCIError::Position {
+                message: format!("Rank {} covers {} squares (expected 8)", rank + 1, file),
+            });
+        }
+    }
+
+    Ok(grid)
+}
+
+/// Algebraic name of a square, e.g. `e4`
+fn square_name(rank: usize, file: usize) -> String {
+    format!("{}{}", (b'a' + file as u8) as char, rank + 1)
+}
+
+/// Whether the square at `rank`/`file` is attacked by White (`by_white`) or Black
+fn is_attacked(grid: &Grid, rank: usize, file: usize, by_white: bool) -> bool {
+    let piece_at = |dr: i32, df: i32| -> Option<Option<char>> {
+        let r = usize::try_from(rank as i32 + dr).ok().filter(|&r| r < 8)?;
+        let f = usize::try_from(file as i32 + df).ok().filter(|&f| f < 8)?;
+        Some(grid[r][f])
+    };
+    let is = |square: Option<Option<char>>, kinds: &str| {
+        matches!(square, Some(Some(piece))
+            if piece.is_ascii_uppercase() == by_white
+                && kinds.contains(piece.to_ascii_lowercase()))
+    };
+
+    // White pawns attack upwards, so they sit one rank below the square
+    let pawn_rank = if by_white { -1 } else { 1 };
+    if is(piece_at(pawn_rank, -1), "p") || is(piece_at(pawn_rank, 1), "p") {
+        return true;
+    }
+
+    const KNIGHT: [(i32, i32); 8] = [
+        (1, 2),
+        (2, 1),
+        (2, -1),
+        (1, -2),
+        (-1, -2),
+        (-2, -1),
+        (-2, 1),
+        (-1, 2),
+    ];
+    const KING: [(i32, i32); 8] = [
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+        (0, -1),
+        (1, -1),
+    ];
+    if KNIGHT.iter().any(|&(dr, df)| is(piece_at(dr, df), "n"))
+        || KING.iter().any(|&(dr, df)| is(piece_at(dr, df), "k"))
+    {
+        return true;
+    }
+
+    // Sliders: the first piece along each ray decides
+    KING.iter().any(|&(dr, df)| {
+        let sliders = if dr == 0 || df == 0 { "rq" } else { "bq" };
+        (1..8)
+            .map(|distance| piece_at(dr * distance, df * distance))
+            .take_while(Option::is_some)
+            .find(|square| matches!(square, Some(Some(_))))
+            .is_some_and(|square| is(square, sliders))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -463,6 +668,94 @@ mod tests {
         assert!(sanitizer.validate_fen(&long_fen).is_err());
     }
 
+    #[test]
+    fn test_fen_king_counts() {
+        let sanitizer = InputSanitizer::default();
+        let position_error = |fen: &str| match sanitizer.validate_fen(fen) {
+            Err(UCIError::Position { message }) => message,
+            other => format!("unexpected {:?}", other),
+        };
+
+        assert_eq!(
+            position_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKKNR w kq - 0 1"),
+            "White has 2 kings (expected exactly 1)"
+        );
+        assert_eq!(
+            position_error("8/8/8/8/8/8/8/4K3 w - - 0 1"),
+            "Black has 0 kings (expected exactly 1)"
+        );
+        assert_eq!(
+            position_error("4k3/pppppppp/p7/8/8/8/8/4K3 w - - 0 1"),
+            "Black has 9 pawns (max 8)"
+        );
+        assert_eq!(
+            position_error("4k3/8/8/8/8/8/8/4K32 w - - 0 1"),
+            "Rank 1 covers 10 squares (expected 8)"
+        );
+    }
+
+    #[test]
+    fn test_fen_pawn_on_back_rank() {
+        let sanitizer = InputSanitizer::default();
+
+        let error = sanitizer
+            .validate_fen("P3k3/8/8/8/8/8/8/4K3 w - - 0 1")
+            .unwrap_err();
+        assert!(matches!(error, UCIError::Position { .. }));
+        assert!(error.to_string().contains("Pawn 'P' on back rank at a8"));
+        assert!(sanitizer
+            .validate_fen("4k3/8/8/8/8/8/8/p3K3 b - - 0 1")
+            .is_err());
+    }
+
+    #[test]
+    fn test_fen_side_not_to_move_in_check() {
+        let sanitizer = InputSanitizer::default();
+
+        // Black king attacked by the rook with White to move
+        let error = sanitizer
+            .validate_fen("4k3/8/8/8/8/8/8/4RK2 w - - 0 1")
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Black king on e8 is in check but it is not Black to move"));
+
+        // The same position is a plain check with Black to move
+        assert!(sanitizer
+            .validate_fen("4k3/8/8/8/8/8/8/4RK2 b - - 0 1")
+            .is_ok());
+
+        // Blocked sliders, knights and pawns
+        assert!(sanitizer
+            .validate_fen("4k3/4p3/8/8/8/8/8/4RK2 w - - 0 1")
+            .is_ok());
+        assert!(sanitizer
+            .validate_fen("4k3/8/3N4/8/8/8/8/5K2 w - - 0 1")
+            .is_err());
+        assert!(sanitizer
+            .validate_fen("4k3/8/8/8/8/8/3p4/4K3 b - - 0 1")
+            .is_err());
+        assert!(sanitizer
+            .validate_fen("4k3/8/8/8/8/8/4p3/4K3 b - - 0 1")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_chess960_castling_rights() {
+        let shredder = "bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w HAha - 0 1";
+
+        assert!(InputSanitizer::default().validate_fen(shredder).is_err());
+
+        let sanitizer = InputSanitizer::new(InputLimits::default().with_chess960(true));
+        assert!(sanitizer.validate_fen(shredder).is_ok());
+        assert!(sanitizer
+            .validate_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
+            .is_ok());
+        assert!(sanitizer
+            .validate_fen("bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w HAhz - 0 1")
+            .is_err());
+    }
+
     #[test]
     fn test_move_validation() {
         let sanitizer = InputSanitizer::default();
@@ -488,6 +781,35 @@ mod tests {
         assert!(sanitizer.validate_move_list(&many_moves).is_err());
     }
 
+    #[test]
+    fn test_raised_move_limit() {
+        let limits = InputLimits::default().with_max_moves_per_command(1200);
+        let sanitizer = InputSanitizer::new(limits);
+
+        let many_moves: Vec<&str> = (0..1000).map(|_| "e2e4").collect();
+        assert!(sanitizer.validate_move_list(&many_moves).is_ok());
+
+        // The whole command line must still pass structural validation
+        let command = format!("position startpos moves {}", many_moves.join(" "));
+        assert!(sanitizer.validate_command_structure(&command).is_ok());
+    }
+
+    #[test]
+    fn test_move_limit_ceiling() {
+        let limits = InputLimits::default().with_max_moves_per_command(usize::MAX);
+        assert_eq!(limits.max_moves_per_command, MAX_MOVES_PER_COMMAND_CEILING);
+
+        // Limits set directly are still held to the ceiling
+        let sanitizer = InputSanitizer::new(InputLimits {
+            max_moves_per_command: usize::MAX,
+            ..InputLimits::default()
+        });
+        let pathological: Vec<&str> = (0..MAX_MOVES_PER_COMMAND_CEILING + 1)
+            .map(|_| "e2e4")
+            .collect();
+        assert!(sanitizer.validate_move_list(&pathological).is_err());
+    }
+
     #[test]
     fn test_option_validation() {
         let sanitizer = InputSanitizer::default();
@@ -513,6 +835,7 @@ mod tests {
         assert!(sanitizer
             .check_resource_exhaustion("go movetime 1000")
             .is_ok());
+        assert!(sanitizer.check_resource_exhaustion("d").is_ok());
 
         // Excessive repetition
         let repetitive = "a".repeat(200);
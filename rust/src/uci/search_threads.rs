@@ -0,0 +1,39 @@
+// Search Thread Pool
+//
+// The `Threads` option sizes the C++ Lazy-SMP pool: one main search thread and
+// helpers sharing the transposition table. The pool is sized as each `go`
+// starts, so a change made mid-search applies to the next search. Sizing is
+// pluggable so tests can observe it without depending on the C++ pool.
+
+use tracing::debug;
+
+use crate::ffi::ffi;
+
+/// Largest worker set a search may use, matching the `Threads` option range
+pub const MAX_SEARCH_THREADS: u32 = 64;
+
+/// Sizes the worker set of the next search
+///
+/// Called on the event loop just before a search starts; implementations must
+/// not block for long.
+pub trait SearchThreads: Send + Sync {
+    /// Run the next search on `thread_count` workers; false if the pool could
+    /// not be resized, leaving its previous size in place
+    fn set_threads(&self, thread_count: u32) -> bool;
+}
+
+/// Default sizing that resizes the C++ engine's Lazy-SMP pool through FFI
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EngineSearchThreads;
+
+impl SearchThreads for EngineSearchThreads {
+    fn set_threads(&self, thread_count: u32) -> bool {
+        debug!(thread_count, "Sizing C++ search thread pool");
+        ffi::engine_set_threads(thread_count)
+    }
+}
+
+/// Workers a search runs on for a configured `Threads` value
+pub fn worker_count(thread_count: u32) -> u32 {
+    thread_count.clamp(1, MAX_SEARCH_THREADS)
+}
@@ -4,11 +4,15 @@
 // and async-compatible locking for high-performance concurrent operation.
 
 use parking_lot::RwLock;
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
 use tokio::sync::broadcast;
 use tracing::{debug, info, warn};
 
 use crate::error::{UCIError, UCIResult};
+use crate::time::TimePolicyKind;
 use crate::uci::commands::TimeControl;
 
 /// UCI Engine operational states
@@ -27,6 +31,8 @@ pub enum EngineState {
     Stopping = 4,
     /// Engine has encountered an error and needs reset
     Error = 5,
+    /// Engine is analyzing without a time limit (`go infinite` or analysis mode)
+    Analyzing = 6,
 }
 
 impl EngineState {
@@ -37,7 +43,10 @@ impl EngineState {
 
     /// Check if the engine is actively computing
     pub fn is_computing(&self) -> bool {
-        matches!(self, EngineState::Searching | EngineState::Pondering)
+        matches!(
+            self,
+            EngineState::Searching | EngineState::Pondering | EngineState::Analyzing
+        )
     }
 
     /// Check if the engine is in a terminal state
@@ -54,6 +63,7 @@ impl EngineState {
             EngineState::Pondering => "Pondering on opponent time",
             EngineState::Stopping => "Shutting down gracefully",
             EngineState::Error => "Error state - reset required",
+            EngineState::Analyzing => "Analyzing until stopped",
         }
     }
 }
@@ -67,6 +77,7 @@ impl From<u8> for EngineState {
             3 => EngineState::Pondering,
             4 => EngineState::Stopping,
             5 => EngineState::Error,
+            6 => EngineState::Analyzing,
             _ => EngineState::Error, // Default to error for invalid values
         }
     }
@@ -81,6 +92,9 @@ pub struct StateChangeEvent {
     pub reason: String,
 }
 
+/// State transitions kept for post-mortem debugging
+const STATE_HISTORY_CAPACITY: usize = 128;
+
 /// Thread-safe UCI engine state manager
 pub struct UCIState {
     /// Current engine state (atomic for lock-free reads)
@@ -89,32 +103,199 @@ pub struct UCIState {
     /// Debug mode flag
     debug_mode: AtomicBool,
 
+    /// Transposition table clears requested from the C++ engine
+    hash_clears: AtomicU64,
+
+    /// Position at the end of the latest reported principal variation
+    pv_fen: RwLock<Option<String>>,
+
     /// Search statistics (atomic counters)
     searches_started: AtomicU64,
     searches_completed: AtomicU64,
     total_nodes_searched: AtomicU64,
 
+    /// C++ search counters, accumulated at each search completion
+    tt_hits: AtomicU64,
+    lmr_reductions: AtomicU64,
+    null_move_prunes: AtomicU64,
+    qsearch_nodes: AtomicU64,
+
+    /// Games seen so far, the current one included
+    games_played: AtomicU64,
+    /// Search totals since the current game started (`ucinewgame`)
+    game_totals: RwLock<SearchTotals>,
+    /// Search totals across all games
+    cumulative_totals: RwLock<SearchTotals>,
+
     /// Current search context (protected by RwLock for infrequent updates)
     search_context: RwLock<Option<SearchContext>>,
 
     /// State change notification channel
     state_change_tx: broadcast::Sender<StateChangeEvent>,
 
+    /// Most recent state transitions, oldest first
+    state_history: RwLock<VecDeque<StateChangeEvent>>,
+
     /// Engine configuration (protected by RwLock)
     config: RwLock<EngineConfig>,
 }
 
+/// Totals over the searches of one game, or of the whole session
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchTotals {
+    /// Searches that ended with a `bestmove`
+    pub moves: u64,
+    /// Nodes searched
+    pub nodes: u64,
+    /// Time spent searching in milliseconds
+    pub time_ms: u64,
+}
+
+impl SearchTotals {
+    fn add_search(&mut self, nodes: u64, time_ms: u64) {
+        self.moves += 1;
+        self.nodes += nodes;
+        self.time_ms += time_ms;
+    }
+}
+
+impl fmt::Display for SearchTotals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "moves {} nodes {} time {}",
+            self.moves, self.nodes, self.time_ms
+        )
+    }
+}
+
+/// Counters reported by the C++ search for one search
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchCounters {
+    /// Transposition table probes that found the position
+    pub tt_hits: u64,
+    /// Moves searched at reduced depth by late move reductions
+    pub lmr_reductions: u64,
+    /// Subtrees cut off by null move pruning
+    pub null_move_prunes: u64,
+    /// Nodes visited in quiescence search
+    pub qsearch_nodes: u64,
+}
+
 /// Current search context information
 #[derive(Debug, Clone)]
 pub struct SearchContext {
     pub start_time: std::time::Instant,
-    pub time_control: TimeControl,
+    pub time_control: TimeControl<'static>,
+    /// Root moves the search is restricted to (empty means all legal moves)
+    pub search_moves: Vec<String>,
     pub max_depth: Option<u32>,
     pub max_nodes: Option<u64>,
     pub is_infinite: bool,
     pub is_ponder: bool,
 }
 
+/// Why a search ended, reported in debug mode before `bestmove`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// Reached the `go depth` limit
+    Depth,
+    /// Reached the `go nodes` limit
+    Nodes,
+    /// Soft time limit passed between iterations
+    SoftLimit,
+    /// Hard time limit expired mid-iteration
+    HardLimit,
+    /// GUI sent `stop`
+    Stop,
+    /// A forced mate was found
+    Mate,
+    /// Search space exhausted without hitting any limit
+    Completed,
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            StopReason::Depth => "depth",
+            StopReason::Nodes => "nodes",
+            StopReason::SoftLimit => "soft_limit",
+            StopReason::HardLimit => "hard_limit",
+            StopReason::Stop => "stop",
+            StopReason::Mate => "mate",
+            StopReason::Completed => "completed",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Playing style selected by the `Style` option
+///
+/// Each style is a preset of the individual style options, which can still be
+/// tuned one by one afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlayingStyle {
+    /// Sacrificial attacking play in the manner of Paul Morphy
+    Morphy,
+    /// Quiet play that avoids speculative material investments
+    Positional,
+    /// The engine defaults
+    #[default]
+    Balanced,
+    /// Solid play that keeps material and welcomes draws
+    Defensive,
+}
+
+impl PlayingStyle {
+    /// Every style, in the order the option lists them
+    pub const ALL: [PlayingStyle; 4] = [
+        PlayingStyle::Morphy,
+        PlayingStyle::Positional,
+        PlayingStyle::Balanced,
+        PlayingStyle::Defensive,
+    ];
+
+    /// Set the style options in `config` to this style's preset
+    pub fn apply(&self, config: &mut EngineConfig) {
+        let (morphy_style, sacrifice_threshold, tactical_depth, contempt_factor) = match self {
+            PlayingStyle::Morphy => (true, 300, 4, 20),
+            PlayingStyle::Positional => (false, 50, 2, 0),
+            PlayingStyle::Balanced => (false, 100, 2, 0),
+            PlayingStyle::Defensive => (false, 0, 1, -20),
+        };
+        config.style = *self;
+        config.morphy_style = morphy_style;
+        config.sacrifice_threshold = sacrifice_threshold;
+        config.tactical_depth = tactical_depth;
+        config.contempt_factor = contempt_factor;
+    }
+}
+
+impl fmt::Display for PlayingStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PlayingStyle::Morphy => "Morphy",
+            PlayingStyle::Positional => "Positional",
+            PlayingStyle::Balanced => "Balanced",
+            PlayingStyle::Defensive => "Defensive",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for PlayingStyle {
+    type Err = UCIError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|style| style.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UCIError::Configuration {
+                message: format!("Unknown style: {}", s),
+            })
+    }
+}
+
 /// Engine configuration parameters
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
@@ -123,7 +304,56 @@ pub struct EngineConfig {
     pub ponder_enabled: bool,
     pub multithread_enabled: bool,
     pub analysis_mode: bool,
+    /// Draw score offset in centipawns, -200..=200 (`Contempt`)
     pub contempt_factor: i32,
+    /// Number of principal variations reported per search (`MultiPV`)
+    pub multi_pv: u32,
+    /// Pre-touch hash memory on a blocking thread after allocation (`WarmupHash`)
+    pub warmup_hash: bool,
+    /// Echo each processed command back as `info string cmd: ...` (`EchoCommands`)
+    pub echo_commands: bool,
+    /// Evaluate with the Morphy-style evaluator (`MorphyStyle`)
+    pub morphy_style: bool,
+    /// Most compensation in centipawns the Morphy-style evaluator credits for
+    /// sacrificed material (`SacrificeThreshold`)
+    pub sacrifice_threshold: i32,
+    /// Plies check extensions and quiescence search may add to a line beyond
+    /// the nominal depth (`TacticalDepth`); handed to the C++ engine at each `go`
+    pub tactical_depth: u32,
+    /// Moves accepted in a single `position` command (capped at `MAX_MOVES_PER_COMMAND_CEILING`)
+    pub max_moves_per_command: usize,
+    /// Drop a ponder move from `bestmove` unless it is legal after the best move
+    pub check_ponder_move: bool,
+    /// Resign after sustained scores at or below minus this many centipawns; 0 disables (`ResignThreshold`)
+    pub resign_threshold: i32,
+    /// Largest absolute score in centipawns still counted as level (`DrawThreshold`)
+    pub draw_threshold: i32,
+    /// Level searches in a row before offering a draw; 0 disables (`DrawMoveCount`)
+    pub draw_move_count: u32,
+    /// Search weight preset last selected with `Style`
+    pub style: PlayingStyle,
+    /// Fischer Random: accept Shredder-FEN castling rights (`UCI_Chess960`)
+    pub chess960: bool,
+    /// Play at `uci_elo` instead of full strength (`UCI_LimitStrength`)
+    pub limit_strength: bool,
+    /// Target strength when limiting is on (`UCI_Elo`); kept but unused otherwise
+    pub uci_elo: u32,
+    /// Time policy override; `Auto` keeps the engine's own choice (`TimePolicy`)
+    pub time_policy: TimePolicyKind,
+    /// Milliseconds reserved per move for network and GUI latency (`MoveOverhead`)
+    pub move_overhead_ms: u64,
+    /// Minimum milliseconds between `info` reports of completed search iterations;
+    /// 0 reports every iteration. The final iteration is always reported.
+    pub info_interval_ms: u64,
+    /// Follow each `bestmove` with an `info string summary` line (`MoveSummary`)
+    pub move_summary: bool,
+    /// Send each reported iteration as an `info string json:` telemetry frame too
+    pub telemetry_json: bool,
+    /// First iteration that streams `currmove`/`currmovenumber` for each root move
+    pub currmove_min_depth: u8,
+    /// Nodes per second the simulated search consumes in debug mode; 0 keeps one
+    /// node per root move per iteration (`SearchNps`, debug only)
+    pub search_nps: u64,
 }
 
 impl Default for EngineConfig {
@@ -135,10 +365,113 @@ impl Default for EngineConfig {
             multithread_enabled: false,
             analysis_mode: false,
             contempt_factor: 0, // Neutral contempt
+            multi_pv: 1,
+            warmup_hash: false, // Avoid startup delay for small hashes
+            echo_commands: false,
+            morphy_style: false,
+            sacrifice_threshold: 100,
+            tactical_depth: 2,
+            max_moves_per_command: 512, // Matches the sanitizer default
+            check_ponder_move: true,
+            resign_threshold: 0, // Never resign in normal play
+            draw_threshold: 10,
+            draw_move_count: 0,
+            style: PlayingStyle::Balanced,
+            chess960: false,
+            limit_strength: false,
+            uci_elo: 1320,
+            time_policy: TimePolicyKind::Auto,
+            move_overhead_ms: 30,
+            info_interval_ms: 0,
+            move_summary: false,
+            telemetry_json: false,
+            currmove_min_depth: 5, // Shallow iterations finish too fast to follow
+            search_nps: 0,
         }
     }
 }
 
+impl EngineConfig {
+    /// Elo the engine plays at, or `None` for full strength
+    pub fn strength_elo(&self) -> Option<u32> {
+        self.limit_strength.then_some(self.uci_elo)
+    }
+
+    /// Check every field against the ranges the engine advertises
+    pub fn validate(&self) -> UCIResult<()> {
+        let check = |valid: bool, field: &str, value: String| {
+            if valid {
+                Ok(())
+            } else {
+                Err(UCIError::Configuration {
+                    message: format!("{} out of range: {}", field, value),
+                })
+            }
+        };
+
+        check(
+            (1..=2048).contains(&self.hash_size_mb),
+            "hash_size_mb",
+            self.hash_size_mb.to_string(),
+        )?;
+        check(
+            (1..=64).contains(&self.thread_count),
+            "thread_count",
+            self.thread_count.to_string(),
+        )?;
+        check(
+            (-200..=200).contains(&self.contempt_factor),
+            "contempt_factor",
+            self.contempt_factor.to_string(),
+        )?;
+        check(
+            (1..=256).contains(&self.multi_pv),
+            "multi_pv",
+            self.multi_pv.to_string(),
+        )?;
+        check(
+            (0..=1000).contains(&self.sacrifice_threshold),
+            "sacrifice_threshold",
+            self.sacrifice_threshold.to_string(),
+        )?;
+        check(
+            self.tactical_depth <= 8,
+            "tactical_depth",
+            self.tactical_depth.to_string(),
+        )?;
+        check(
+            (0..=10000).contains(&self.resign_threshold),
+            "resign_threshold",
+            self.resign_threshold.to_string(),
+        )?;
+        check(
+            (1320..=3190).contains(&self.uci_elo),
+            "uci_elo",
+            self.uci_elo.to_string(),
+        )?;
+        check(
+            (0..=1000).contains(&self.draw_threshold),
+            "draw_threshold",
+            self.draw_threshold.to_string(),
+        )?;
+        check(
+            self.move_overhead_ms <= 5000,
+            "move_overhead_ms",
+            self.move_overhead_ms.to_string(),
+        )?;
+        check(
+            self.info_interval_ms <= 60_000,
+            "info_interval_ms",
+            self.info_interval_ms.to_string(),
+        )?;
+        check(
+            self.draw_move_count <= 200,
+            "draw_move_count",
+            self.draw_move_count.to_string(),
+        )
+    }
+}
+
 impl UCIState {
     /// Create new UCI state manager with default configuration
     pub fn new() -> Self {
@@ -147,11 +480,21 @@ impl UCIState {
         Self {
             current_state: AtomicU8::new(EngineState::Initializing as u8),
             debug_mode: AtomicBool::new(false),
+            hash_clears: AtomicU64::new(0),
+            pv_fen: RwLock::new(None),
             searches_started: AtomicU64::new(0),
             searches_completed: AtomicU64::new(0),
             total_nodes_searched: AtomicU64::new(0),
+            tt_hits: AtomicU64::new(0),
+            lmr_reductions: AtomicU64::new(0),
+            null_move_prunes: AtomicU64::new(0),
+            qsearch_nodes: AtomicU64::new(0),
+            games_played: AtomicU64::new(1),
+            game_totals: RwLock::new(SearchTotals::default()),
+            cumulative_totals: RwLock::new(SearchTotals::default()),
             search_context: RwLock::new(None),
             state_change_tx,
+            state_history: RwLock::new(VecDeque::with_capacity(STATE_HISTORY_CAPACITY)),
             config: RwLock::new(EngineConfig::default()),
         }
     }
@@ -189,6 +532,14 @@ impl UCIState {
             reason: reason.to_string(),
         };
 
+        {
+            let mut history = self.state_history.write();
+            if history.len() == STATE_HISTORY_CAPACITY {
+                history.pop_front();
+            }
+            history.push_back(event.clone());
+        }
+
         // Ignore broadcast errors (no active listeners is OK)
         let _ = self.state_change_tx.send(event);
 
@@ -204,7 +555,11 @@ impl UCIState {
             (Initializing, Ready) | (Initializing, Error) => true,
 
             // From Ready
-            (Ready, Searching) | (Ready, Pondering) | (Ready, Stopping) | (Ready, Error) => true,
+            (Ready, Searching)
+            | (Ready, Pondering)
+            | (Ready, Analyzing)
+            | (Ready, Stopping)
+            | (Ready, Error) => true,
 
             // From Searching
             (Searching, Ready)
@@ -215,9 +570,16 @@ impl UCIState {
             // From Pondering
             (Pondering, Ready)
             | (Pondering, Searching)
+            | (Pondering, Analyzing)
             | (Pondering, Stopping)
             | (Pondering, Error) => true,
 
+            // From Analyzing
+            (Analyzing, Ready)
+            | (Analyzing, Pondering)
+            | (Analyzing, Stopping)
+            | (Analyzing, Error) => true,
+
             // From Error (can only reset to Initializing or stop)
             (Error, Initializing) | (Error, Stopping) => true,
 
@@ -241,15 +603,25 @@ impl UCIState {
     }
 
     /// Start a new search with the given context
+    ///
+    /// `go ponder` enters `Pondering`. Infinite searches, and any search while
+    /// `UCI_AnalyseMode` is on, enter `Analyzing` instead of `Searching` since
+    /// they only end on `stop`.
     pub fn start_search(&self, context: SearchContext) -> UCIResult<()> {
-        // Transition to searching state
-        self.transition_to(EngineState::Searching, "Starting new search")?;
+        if context.is_ponder {
+            self.transition_to(EngineState::Pondering, "Starting ponder search")?;
+        } else if context.is_infinite || self.config().analysis_mode {
+            self.transition_to(EngineState::Analyzing, "Starting analysis")?;
+        } else {
+            self.transition_to(EngineState::Searching, "Starting new search")?;
+        }
 
         // Update search context
         {
             let mut search_ctx = self.search_context.write();
             *search_ctx = Some(context);
         }
+        *self.pv_fen.write() = None;
 
         // Increment search counter
         self.searches_started.fetch_add(1, Ordering::Relaxed);
@@ -258,6 +630,29 @@ impl UCIState {
         Ok(())
     }
 
+    /// Turn the running ponder search into a normal search (`ponderhit`)
+    ///
+    /// The in-flight search context is kept, with `is_ponder` cleared, and
+    /// returned so the caller can set the search's clock.
+    pub fn ponder_hit(&self) -> UCIResult<SearchContext> {
+        let context = {
+            let mut search_ctx = self.search_context.write();
+            let context = search_ctx.as_mut().ok_or_else(|| UCIError::Engine {
+                message: "Ponder hit without a search in progress".to_string(),
+            })?;
+            context.is_ponder = false;
+            context.clone()
+        };
+
+        if context.is_infinite {
+            self.transition_to(EngineState::Analyzing, "Ponder hit - continuing analysis")?;
+        } else {
+            self.transition_to(EngineState::Searching, "Ponder hit - converting to search")?;
+        }
+
+        Ok(context)
+    }
+
     /// Complete the current search and return to ready state
     pub fn complete_search(&self, nodes_searched: u64) -> UCIResult<()> {
         // Update statistics
@@ -266,10 +661,15 @@ impl UCIState {
             .fetch_add(nodes_searched, Ordering::Relaxed);
 
         // Clear search context
-        {
-            let mut search_ctx = self.search_context.write();
-            *search_ctx = None;
-        }
+        let finished = self.search_context.write().take();
+
+        let time_ms = finished
+            .map(|context| context.start_time.elapsed().as_millis() as u64)
+            .unwrap_or(0);
+        self.game_totals.write().add_search(nodes_searched, time_ms);
+        self.cumulative_totals
+            .write()
+            .add_search(nodes_searched, time_ms);
 
         // Transition back to ready
         self.transition_to(EngineState::Ready, "Search completed")?;
@@ -278,6 +678,29 @@ impl UCIState {
         Ok(())
     }
 
+    /// Add the counters of a finished search to the session totals
+    pub fn record_search_counters(&self, counters: SearchCounters) {
+        self.tt_hits.fetch_add(counters.tt_hits, Ordering::Relaxed);
+        self.lmr_reductions
+            .fetch_add(counters.lmr_reductions, Ordering::Relaxed);
+        self.null_move_prunes
+            .fetch_add(counters.null_move_prunes, Ordering::Relaxed);
+        self.qsearch_nodes
+            .fetch_add(counters.qsearch_nodes, Ordering::Relaxed);
+    }
+
+    /// Begin a new game (`ucinewgame`), resetting the per-game totals
+    ///
+    /// A game in which no search finished is not counted, so the
+    /// `ucinewgame` GUIs send before their first game does not add one.
+    pub fn start_new_game(&self) {
+        let mut game_totals = self.game_totals.write();
+        if game_totals.moves > 0 {
+            *game_totals = SearchTotals::default();
+            self.games_played.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
     /// Enable or disable debug mode
     pub fn set_debug_mode(&self, enabled: bool) {
         let old_value = self.debug_mode.swap(enabled, Ordering::Relaxed);
@@ -291,6 +714,16 @@ impl UCIState {
         self.debug_mode.load(Ordering::Relaxed)
     }
 
+    /// Count a successful transposition table clear
+    pub fn record_hash_clear(&self) {
+        self.hash_clears.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record the FEN at the end of the principal variation just reported
+    pub fn set_pv_fen(&self, fen: String) {
+        *self.pv_fen.write() = Some(fen);
+    }
+
     /// Get current search context (if searching)
     pub fn search_context(&self) -> Option<SearchContext> {
         self.search_context.read().clone()
@@ -303,7 +736,16 @@ impl UCIState {
             searches_started: self.searches_started.load(Ordering::Relaxed),
             searches_completed: self.searches_completed.load(Ordering::Relaxed),
             total_nodes_searched: self.total_nodes_searched.load(Ordering::Relaxed),
+            tt_hits: self.tt_hits.load(Ordering::Relaxed),
+            lmr_reductions: self.lmr_reductions.load(Ordering::Relaxed),
+            null_move_prunes: self.null_move_prunes.load(Ordering::Relaxed),
+            qsearch_nodes: self.qsearch_nodes.load(Ordering::Relaxed),
             debug_mode: self.is_debug_mode(),
+            hash_clears: self.hash_clears.load(Ordering::Relaxed),
+            pv_fen: self.pv_fen.read().clone(),
+            games_played: self.games_played.load(Ordering::Relaxed),
+            game_totals: *self.game_totals.read(),
+            cumulative_totals: *self.cumulative_totals.read(),
         }
     }
 
@@ -329,6 +771,13 @@ impl UCIState {
         self.state_change_tx.subscribe()
     }
 
+    /// Get the last state transitions, oldest first
+    ///
+    /// Holds at most 128 transitions.
+    pub fn state_history(&self) -> Vec<StateChangeEvent> {
+        self.state_history.read().iter().cloned().collect()
+    }
+
     /// Force engine into error state with reason
     pub fn set_error_state(&self, reason: &str) {
         warn!(reason, "Engine forced into error state");
@@ -379,7 +828,25 @@ pub struct EngineStatistics {
     pub searches_started: u64,
     pub searches_completed: u64,
     pub total_nodes_searched: u64,
+    /// Transposition table hits across all searches
+    pub tt_hits: u64,
+    /// Late move reductions across all searches
+    pub lmr_reductions: u64,
+    /// Null move prunes across all searches
+    pub null_move_prunes: u64,
+    /// Quiescence search nodes across all searches
+    pub qsearch_nodes: u64,
     pub debug_mode: bool,
+    /// Transposition table clears (`Clear Hash` and `ucinewgame`)
+    pub hash_clears: u64,
+    /// FEN at the end of the latest principal variation, for analysis tooling
+    pub pv_fen: Option<String>,
+    /// Games seen so far, the current one included
+    pub games_played: u64,
+    /// Search totals for the current game
+    pub game_totals: SearchTotals,
+    /// Search totals across all games
+    pub cumulative_totals: SearchTotals,
 }
 
 // Thread safety: UCIState is designed to be Send + Sync
@@ -425,6 +892,52 @@ mod tests {
         assert_eq!(state.current_state(), EngineState::Ready);
     }
 
+    #[tokio::test]
+    async fn test_analyzing_transitions() {
+        let state = UCIState::new();
+        state.transition_to(EngineState::Ready, "Ready").unwrap();
+
+        state
+            .transition_to(EngineState::Analyzing, "Starting analysis")
+            .unwrap();
+        state
+            .transition_to(EngineState::Pondering, "Analysis to ponder")
+            .unwrap();
+        state
+            .transition_to(EngineState::Analyzing, "Ponder to analysis")
+            .unwrap();
+        state.transition_to(EngineState::Ready, "Stopped").unwrap();
+        assert_eq!(state.current_state(), EngineState::Ready);
+
+        // Analysis is never entered straight from initialization
+        let fresh = UCIState::new();
+        assert!(fresh
+            .transition_to(EngineState::Analyzing, "Invalid transition")
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_infinite_search_enters_analyzing() {
+        let state = UCIState::new();
+        state.transition_to(EngineState::Ready, "Ready").unwrap();
+
+        let context = SearchContext {
+            start_time: std::time::Instant::now(),
+            time_control: TimeControl::default(),
+            search_moves: Vec::new(),
+            max_depth: None,
+            max_nodes: None,
+            is_infinite: true,
+            is_ponder: false,
+        };
+
+        state.start_search(context).unwrap();
+        assert_eq!(state.current_state(), EngineState::Analyzing);
+
+        state.complete_search(0).unwrap();
+        assert_eq!(state.current_state(), EngineState::Ready);
+    }
+
     #[tokio::test]
     async fn test_invalid_state_transitions() {
         let state = UCIState::new();
@@ -443,6 +956,7 @@ mod tests {
         let context = SearchContext {
             start_time: std::time::Instant::now(),
             time_control: TimeControl::default(),
+            search_moves: Vec::new(),
             max_depth: Some(10),
             max_nodes: None,
             is_infinite: false,
@@ -465,6 +979,26 @@ mod tests {
         assert_eq!(stats.total_nodes_searched, 1000);
     }
 
+    #[test]
+    fn test_search_counters_accumulate() {
+        let state = UCIState::new();
+        let counters = SearchCounters {
+            tt_hits: 40,
+            lmr_reductions: 12,
+            null_move_prunes: 3,
+            qsearch_nodes: 900,
+        };
+
+        state.record_search_counters(counters);
+        state.record_search_counters(counters);
+
+        let stats = state.statistics();
+        assert_eq!(stats.tt_hits, 80);
+        assert_eq!(stats.lmr_reductions, 24);
+        assert_eq!(stats.null_move_prunes, 6);
+        assert_eq!(stats.qsearch_nodes, 1800);
+    }
+
     #[tokio::test]
     async fn test_debug_mode() {
         let state = UCIState::new();
@@ -516,6 +1050,49 @@ mod tests {
         assert_eq!(event.reason, "Test transition");
     }
 
+    #[tokio::test]
+    async fn test_state_history_in_order_and_bounded() {
+        let state = UCIState::new();
+        let mut receiver = state.subscribe_state_changes();
+
+        state.transition_to(EngineState::Ready, "init").unwrap();
+        state.transition_to(EngineState::Searching, "go").unwrap();
+        state.transition_to(EngineState::Ready, "done").unwrap();
+
+        let history = state.state_history();
+        let steps: Vec<_> = history
+            .iter()
+            .map(|event| (event.from, event.to, event.reason.as_str()))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                (EngineState::Initializing, EngineState::Ready, "init"),
+                (EngineState::Ready, EngineState::Searching, "go"),
+                (EngineState::Searching, EngineState::Ready, "done"),
+            ]
+        );
+
+        // Subscribers still see every transition
+        assert_eq!(receiver.recv().await.unwrap().reason, "init");
+
+        for search in 0..100 {
+            state
+                .transition_to(EngineState::Searching, &format!("go {}", search))
+                .unwrap();
+            state
+                .transition_to(EngineState::Ready, &format!("done {}", search))
+                .unwrap();
+        }
+        let history = state.state_history();
+        assert_eq!(history.len(), STATE_HISTORY_CAPACITY);
+        assert_eq!(history[0].reason, "go 36");
+        assert_eq!(history[STATE_HISTORY_CAPACITY - 1].reason, "done 99");
+        assert!(history
+            .windows(2)
+            .all(|pair| pair[0].to == pair[1].from && pair[0].timestamp <= pair[1].timestamp));
+    }
+
     #[tokio::test]
     async fn test_concurrent_state_access() {
         use std::sync::Arc;
@@ -569,6 +1146,23 @@ mod tests {
         assert_eq!(state.current_state(), EngineState::Ready);
     }
 
+    #[test]
+    fn test_engine_config_validate() {
+        assert!(EngineConfig::default().validate().is_ok());
+
+        let config = EngineConfig {
+            thread_count: 0,
+            ..EngineConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = EngineConfig {
+            contempt_factor: 250,
+            ..EngineConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_engine_state_properties() {
         assert!(EngineState::Ready.can_accept_commands());
@@ -577,7 +1171,9 @@ mod tests {
 
         assert!(EngineState::Searching.is_computing());
         assert!(EngineState::Pondering.is_computing());
+        assert!(EngineState::Analyzing.is_computing());
         assert!(!EngineState::Ready.is_computing());
+        assert!(!EngineState::Analyzing.can_accept_commands());
 
         assert!(EngineState::Stopping.is_terminal());
         assert!(EngineState::Error.is_terminal());
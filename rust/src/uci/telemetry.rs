@@ -0,0 +1,96 @@
+// Structured Search Telemetry
+//
+// Front ends such as the Workbench want search progress as data rather than as
+// `info` lines to re-parse. When telemetry is enabled, every reported iteration
+// is also sent as one compact JSON object on an `info string json:` line, which
+// GUIs that do not know about it ignore like any other info string.
+
+use serde::Serialize;
+
+use crate::error::{UCIError, UCIResult};
+use crate::uci::response::ScoreKind;
+
+/// Prefix of the `info string` lines that carry a telemetry frame
+pub const TELEMETRY_PREFIX: &str = "info string json:";
+
+/// Search progress after one reported iteration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TelemetryFrame {
+    /// Nominal search depth in plies
+    pub depth: u8,
+    /// Deepest ply reached, including extensions; absent until measured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seldepth: Option<u8>,
+    /// Nodes searched so far
+    pub nodes: u64,
+    /// Nodes per second
+    pub nps: u64,
+    /// Hash table occupancy in per-mille
+    pub hashfull: u16,
+    /// Principal variations, best first
+    pub multipv: Vec<TelemetryLine>,
+}
+
+/// One principal variation of a telemetry frame
+///
+/// Exactly one of `scoreCp` and `mate` is present.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryLine {
+    /// Moves of the variation in UCI notation
+    pub pv: Vec<String>,
+    /// Centipawn score from the engine's point of view
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_cp: Option<i32>,
+    /// Moves to mate; negative when the engine is getting mated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mate: Option<i32>,
+}
+
+impl TelemetryLine {
+    /// Build a line from a variation and its reported score
+    pub fn new(pv: Vec<String>, score: ScoreKind) -> Self {
+        let (score_cp, mate) = match score {
+            ScoreKind::Cp(cp) => (Some(cp), None),
+            ScoreKind::Mate(moves) => (None, Some(moves)),
+        };
+        Self { pv, score_cp, mate }
+    }
+}
+
+impl TelemetryFrame {
+    /// Format the frame as a single `info string json:` line
+    pub fn to_info_string(&self) -> UCIResult<String> {
+        let json = serde_json::to_string(self).map_err(|e| UCIError::Internal {
+            message: format!("Failed to serialize telemetry frame: {}", e),
+        })?;
+        Ok(format!("{}{}", TELEMETRY_PREFIX, json))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_serializes_compactly() {
+        let frame = TelemetryFrame {
+            depth: 3,
+            seldepth: Some(5),
+            nodes: 1200,
+            nps: 40000,
+            hashfull: 12,
+            multipv: vec![
+                TelemetryLine::new(vec!["e2e4".to_string()], ScoreKind::Cp(35)),
+                TelemetryLine::new(vec!["d1h5".to_string()], ScoreKind::Mate(-2)),
+            ],
+        };
+
+        assert_eq!(
+            frame.to_info_string().unwrap(),
+            "info string json:{\"depth\":3,\"seldepth\":5,\"nodes\":1200,\"nps\":40000,\
+             \"hashfull\":12,\"multipv\":[{\"pv\":[\"e2e4\"],\"scoreCp\":35},\
+             {\"pv\":[\"d1h5\"],\"mate\":-2}]}"
+        );
+    }
+}
@@ -0,0 +1,35 @@
+// Hash Table Warmup
+//
+// Touching freshly allocated hash memory before the first search moves the
+// page-fault cost out of the search itself. The warmup is pluggable so tests
+// can observe it without depending on the C++ table.
+
+use tracing::{debug, warn};
+
+use crate::ffi::ffi;
+
+/// Pre-faults hash table memory so the first search runs at full speed
+///
+/// Implementations are called on a blocking thread and may take a while for
+/// large tables; they must never be invoked from the async event loop directly.
+pub trait HashWarmup: Send + Sync {
+    /// Touch every page of a hash table of `size_mb` megabytes
+    fn warm_up(&self, size_mb: u32);
+}
+
+/// Default warmup that touches the C++ engine's transposition table through FFI
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EngineHashWarmup;
+
+impl HashWarmup for EngineHashWarmup {
+    fn warm_up(&self, size_mb: u32) {
+        debug!(size_mb, "Warming up C++ transposition table");
+
+        if !ffi::engine_warmup_hash() {
+            warn!(
+                size_mb,
+                "C++ engine failed to warm up the transposition table"
+            );
+        }
+    }
+}
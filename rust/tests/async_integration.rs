@@ -180,7 +180,7 @@ async fn test_async_error_propagation() -> UCIResult<()> {
         .await;
 
     match result {
-        Err(UCIError::Protocol { message }) if message.contains("test error") => Ok(()),
+        Err(UCIError::Protocol { message, .. }) if message.contains("test error") => Ok(()),
         Ok(_) => Err(UCIError::Internal {
             message: "Expected error propagation".to_string(),
         }),
@@ -195,6 +195,7 @@ async fn async_operation_that_fails() -> UCIResult<()> {
     tokio::time::sleep(Duration::from_millis(1)).await;
     Err(UCIError::Protocol {
         message: "test error".to_string(),
+        token: None,
     })
 }
 
@@ -126,6 +126,7 @@ async fn test_error_handling() -> UCIResult<()> {
     // Test that our error types work correctly
     let protocol_error = UCIError::Protocol {
         message: "Test protocol error".to_string(),
+        token: None,
     };
 
     let engine_error = UCIError::Engine {
@@ -142,6 +143,7 @@ async fn test_error_handling() -> UCIResult<()> {
     // Test error comparison
     let same_error = UCIError::Protocol {
         message: "Test protocol error".to_string(),
+        token: None,
     };
     assert_eq!(protocol_error, same_error);
 
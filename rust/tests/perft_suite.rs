@@ -0,0 +1,92 @@
+// Perft regression suite
+//
+// Runs the standard perft positions through the move generator and compares
+// the leaf node counts with the published reference values.
+
+use opera_uci::testing::{run_perft_suite, PerftResult};
+
+/// Initial position
+const STARTPOS: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+/// Castling, en passant and promotions in one position
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+/// Discovered checks and en passant pins
+const POSITION_3: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+/// Promotions and castling out of check
+const POSITION_4: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+/// Promotion with capture on the back rank
+const POSITION_5: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+/// Symmetrical middlegame
+const POSITION_6: &str = "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+/// Standard perft positions with reference counts, kept shallow enough for debug builds
+const STANDARD_PERFT_SUITE: &[(&str, u32, u64)] = &[
+    (STARTPOS, 1, 20),
+    (STARTPOS, 2, 400),
+    (STARTPOS, 3, 8_902),
+    (STARTPOS, 4, 197_281),
+    (KIWIPETE, 1, 48),
+    (KIWIPETE, 2, 2_039),
+    (KIWIPETE, 3, 97_862),
+    (POSITION_3, 1, 14),
+    (POSITION_3, 2, 191),
+    (POSITION_3, 3, 2_812),
+    (POSITION_3, 4, 43_238),
+    (POSITION_4, 1, 6),
+    (POSITION_4, 2, 264),
+    (POSITION_4, 3, 9_467),
+    (POSITION_5, 1, 44),
+    (POSITION_5, 2, 1_486),
+    (POSITION_5, 3, 62_379),
+    (POSITION_6, 1, 46),
+    (POSITION_6, 2, 2_079),
+    (POSITION_6, 3, 89_890),
+];
+
+/// One line per mismatch, with the root moves sorted for diffing against a reference engine
+fn describe(mismatches: &[PerftResult]) -> String {
+    mismatches
+        .iter()
+        .map(|result| {
+            let mut divide = result.divide.clone();
+            divide.sort();
+            let moves: Vec<String> = divide
+                .iter()
+                .map(|(uci_move, nodes)| format!("{}: {}", uci_move, nodes))
+                .collect();
+            format!(
+                "{} depth {}: expected {}, got {} [{}]",
+                result.fen,
+                result.depth,
+                result.expected,
+                result.actual,
+                moves.join(", ")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_standard_perft_suite() {
+    let mismatches = run_perft_suite(STANDARD_PERFT_SUITE);
+    assert!(mismatches.is_empty(), "\n{}", describe(&mismatches));
+}
+
+#[test]
+fn test_perft_suite_reports_divide_of_mismatch() {
+    let mismatches = run_perft_suite(&[(STARTPOS, 2, 400), (STARTPOS, 2, 401)]);
+
+    assert_eq!(mismatches.len(), 1);
+    let mismatch = &mismatches[0];
+    assert_eq!((mismatch.expected, mismatch.actual), (401, 400));
+    assert_eq!(mismatch.divide.len(), 20);
+    assert!(mismatch.divide.iter().all(|(_, nodes)| *nodes == 20));
+}
+
+#[test]
+fn test_perft_suite_reports_unloadable_position() {
+    let mismatches = run_perft_suite(&[("not a fen", 1, 20)]);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].actual, 0);
+    assert!(mismatches[0].divide.is_empty());
+}
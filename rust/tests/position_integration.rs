@@ -3,7 +3,7 @@
 // Tests the complete position handling functionality including
 // position setup, move application, and state management.
 
-use opera_uci::{ChessMove, Position, PositionCommandHandler, UCICommand};
+use opera_uci::{ChessMove, Position, PositionCommandHandler, UCICommand, ZeroCopyParser};
 
 #[test]
 fn test_complete_position_workflow() {
@@ -77,7 +77,7 @@ fn test_fen_position_handling() {
     // Test custom FEN position
     let test_fen = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 4 4";
     let fen_cmd = UCICommand::Position {
-        position: Position::Fen(test_fen),
+        position: Position::Fen(test_fen.to_string()),
         moves: vec![],
     };
 
@@ -107,7 +107,7 @@ fn test_error_handling() {
 
     // Test invalid FEN
     let invalid_fen_cmd = UCICommand::Position {
-        position: Position::Fen("invalid_fen"),
+        position: Position::Fen("invalid_fen".to_string()),
         moves: vec![],
     };
 
@@ -158,7 +158,7 @@ fn test_move_sequence_from_fen() {
     // Start from a FEN position and apply moves
     let test_fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
     let cmd = UCICommand::Position {
-        position: Position::Fen(test_fen),
+        position: Position::Fen(test_fen.to_string()),
         moves: vec![ChessMove {
             from_square: "e7",
             to_square: "e5",
@@ -181,3 +181,26 @@ fn test_move_sequence_from_fen() {
         "Position should have changed after move"
     );
 }
+
+#[test]
+fn test_parsed_fen_round_trip() {
+    let mut parser = ZeroCopyParser::new();
+    let mut handler = PositionCommandHandler::new().expect("Should create handler");
+
+    // Parse a non-starting FEN and hand it straight to the handler
+    let test_fen = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3";
+    let input = format!("position fen {}", test_fen);
+    let cmd = parser
+        .parse_command(&input)
+        .expect("Should parse FEN position");
+
+    handler
+        .handle_position_command(&cmd)
+        .expect("Should handle parsed FEN");
+
+    let fen = handler.get_current_position().expect("Should get FEN");
+    assert_eq!(
+        fen, test_fen,
+        "Parsed FEN should round-trip through the board"
+    );
+}
@@ -60,6 +60,7 @@ mod tests {
 
         let error = UCIError::Protocol {
             message: "Test error".to_string(),
+            token: None,
         };
 
         let display = format!("{}", error);
@@ -134,6 +135,7 @@ mod tests {
         // Test ResultExt trait
         let result: Result<(), UCIError> = Err(UCIError::Protocol {
             message: "test".to_string(),
+            token: None,
         });
 
         let contextual_result = result.with_operation("test op");
@@ -33,6 +33,9 @@ async fn test_event_loop_creation_and_config() {
         input_buffer_size: 4096,
         enable_monitoring: false,
         shutdown_timeout_ms: 2000,
+        max_commands_per_second: None,
+        info_string_budget_per_sec: None,
+        telemetry_json: false,
     };
 
     let event_loop_custom = UCIEventLoop::with_config(engine, custom_config.clone())
@@ -57,6 +60,9 @@ async fn test_shutdown_signal_handling() {
         input_buffer_size: 1024,
         enable_monitoring: false,
         shutdown_timeout_ms: 1000,
+        max_commands_per_second: None,
+        info_string_budget_per_sec: None,
+        telemetry_json: false,
     };
 
     let event_loop = UCIEventLoop::with_config(engine, config)
@@ -82,6 +88,9 @@ async fn test_event_loop_statistics() {
         input_buffer_size: 1024,
         enable_monitoring: true,
         shutdown_timeout_ms: 1000,
+        max_commands_per_second: None,
+        info_string_budget_per_sec: None,
+        telemetry_json: false,
     };
 
     let mut event_loop =
@@ -144,6 +153,9 @@ async fn test_configuration_validation() {
         input_buffer_size: 256,
         enable_monitoring: false,
         shutdown_timeout_ms: 500,
+        max_commands_per_second: None,
+        info_string_budget_per_sec: None,
+        telemetry_json: false,
     };
 
     let engine = Arc::new(UCIEngine::new());
@@ -169,6 +181,9 @@ async fn test_engine_state_integration() {
         input_buffer_size: 1024,
         enable_monitoring: false,
         shutdown_timeout_ms: 1000,
+        max_commands_per_second: None,
+        info_string_budget_per_sec: None,
+        telemetry_json: false,
     };
 
     let event_loop = UCIEventLoop::with_config(engine.clone(), config)
@@ -201,6 +216,9 @@ async fn test_command_timeout_handling() {
         input_buffer_size: 1024,
         enable_monitoring: true,
         shutdown_timeout_ms: 100,
+        max_commands_per_second: None,
+        info_string_budget_per_sec: None,
+        telemetry_json: false,
     };
 
     let mut event_loop =
@@ -228,6 +246,9 @@ async fn test_response_handling() {
         input_buffer_size: 1024,
         enable_monitoring: false,
         shutdown_timeout_ms: 1000,
+        max_commands_per_second: None,
+        info_string_budget_per_sec: None,
+        telemetry_json: false,
     };
 
     let event_loop = UCIEventLoop::with_config(engine.clone(), config)
@@ -263,6 +284,9 @@ async fn test_graceful_shutdown() {
         input_buffer_size: 1024,
         enable_monitoring: false,
         shutdown_timeout_ms: 100, // Short timeout for testing
+        max_commands_per_second: None,
+        info_string_budget_per_sec: None,
+        telemetry_json: false,
     };
 
     let mut event_loop = UCIEventLoop::with_config(engine.clone(), config)
@@ -289,6 +313,9 @@ async fn test_concurrent_operations() {
         input_buffer_size: 2048,
         enable_monitoring: true,
         shutdown_timeout_ms: 1000,
+        max_commands_per_second: None,
+        info_string_budget_per_sec: None,
+        telemetry_json: false,
     };
 
     let event_loop = UCIEventLoop::with_config(engine.clone(), config)
@@ -322,6 +349,9 @@ async fn test_run_uci_event_loop_function() {
         input_buffer_size: 512,
         enable_monitoring: false,
         shutdown_timeout_ms: 100,
+        max_commands_per_second: None,
+        info_string_budget_per_sec: None,
+        telemetry_json: false,
     };
 
     // We can't easily test the full run function due to stdin/stdout,
@@ -347,6 +377,9 @@ async fn test_error_handling() {
         input_buffer_size: 64,
         enable_monitoring: false,
         shutdown_timeout_ms: 1,
+        max_commands_per_second: None,
+        info_string_budget_per_sec: None,
+        telemetry_json: false,
     };
 
     let event_loop =
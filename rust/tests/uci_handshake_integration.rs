@@ -44,8 +44,8 @@ async fn test_complete_uci_handshake() {
     let mut uci_responses = Vec::new();
     let mut uciok_received = false;
 
-    for _ in 0..20 {
-        // Allow up to 20 responses (id + options + uciok)
+    for _ in 0..32 {
+        // Allow up to 32 responses (id + options + uciok)
         if let Ok(Ok(response)) = timeout(Duration::from_millis(50), responses.recv()).await {
             if response == "uciok" {
                 uciok_received = true;
@@ -176,7 +176,7 @@ async fn test_uci_options_declaration() {
 
     // Collect all responses
     let mut all_responses = Vec::new();
-    for _ in 0..20 {
+    for _ in 0..32 {
         if let Ok(Ok(response)) = timeout(Duration::from_millis(10), responses.recv()).await {
             let is_uciok = response == "uciok";
             all_responses.push(response);
@@ -440,7 +440,7 @@ async fn test_full_uci_session() {
 
     // Wait for uciok
     let mut uciok_received = false;
-    for _ in 0..20 {
+    for _ in 0..32 {
         if let Ok(Ok(response)) = timeout(Duration::from_millis(10), responses.recv()).await {
             if response == "uciok" {
                 uciok_received = true;
@@ -102,12 +102,17 @@ mod integration_tests {
             panic!("Expected Position command");
         }
 
-        // Test FEN position (simplified for testing)
+        // Test FEN position keeps all six fields
         let result = parser
             .parse_command("position fen rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
         assert!(result.is_ok());
         if let UCICommand::Position { position, .. } = result.unwrap() {
-            assert!(matches!(position, Position::Fen(_)));
+            assert_eq!(
+                position,
+                Position::Fen(
+                    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string()
+                )
+            );
         } else {
             panic!("Expected Position command");
         }
@@ -397,10 +402,11 @@ mod sanitization_tests {
     fn test_dangerous_input_rejection() {
         let mut parser = ZeroCopyParser::new();
 
+        let long_command = "x".repeat(5000);
         let dangerous_inputs = vec![
-            "uci\0",           // Null byte
-            "uci\x01\x02",     // Control characters
-            &"x".repeat(5000), // Extremely long command
+            "uci\0",       // Null byte
+            "uci\x01\x02", // Control characters
+            &long_command, // Extremely long command
         ];
 
         for input in dangerous_inputs {
@@ -502,7 +508,6 @@ mod chess_validation_tests {
         let valid_fens = vec![
             "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", // Starting position
             "r1bqk2r/pppp1ppp/2n2n2/2b1p3/2B1P3/3P1N2/PPP2PPP/RNBQK2R w KQkq - 4 4", // Italian Game
-            "8/8/8/8/8/8/8/8 w - - 0 1",                                // Empty board
         ];
 
         for fen in valid_fens {
@@ -520,6 +525,7 @@ mod chess_validation_tests {
             "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq z9 0 1",  // Invalid en passant
             "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - abc 1", // Invalid halfmove
             "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 xyz", // Invalid fullmove
+            "8/8/8/8/8/8/8/8 w - - 0 1",                                  // No kings
         ];
 
         for fen in invalid_fens {
@@ -623,11 +629,14 @@ mod fuzz_tests {
         let mut parser = ZeroCopyParser::new();
 
         // Test with random-ish input that could cause panics
+        let long_input = "x".repeat(10000);
+        let many_params = format!("go {}", "param ".repeat(100));
+        let long_fen = format!("position fen {}", "x".repeat(300));
         let problematic_inputs = vec![
             "\0\0\0\0",
-            &"x".repeat(10000),
-            "go " + &"param ".repeat(100),
-            "position fen " + &"x".repeat(300),
+            &long_input,
+            &many_params,
+            &long_fen,
             "\x01\x02\x03\x04",
             "uci\nuci\nuci",
             "go wtime 18446744073709551615", // Max u64